@@ -1,69 +1,24 @@
-use std::fs;
-use std::io;
-use std::path::PathBuf;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use clap::Subcommand;
-use thiserror::Error;
+use crate::puzzle::PuzzleInput;
 
-use crate::puzzle::{ParsePuzzleInputError, PuzzleInput};
+pub mod puzzle;
 
-mod puzzle;
+pub struct Day;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    #[command(subcommand)]
-    command: Commands
-}
-
-#[derive(Subcommand)]
-enum Commands {
-    Part1 {
-        input: PathBuf
-    },
-    Part2 {
-        input: PathBuf
-    }
-}
-
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let result = match options.command {
-        Commands::Part1 { input } => run_part1(input),
-        Commands::Part2 { input } => run_part2(input)
-    }?;
-
-    Ok(result.to_string())
-}
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 1;
 
-fn run_part1(input: PathBuf) -> Result<String, ApplicationError> {
-    let puzzle_input = read_puzzle_input(input)?;
+    type Input = PuzzleInput;
+    type Answer1 = i32;
+    type Answer2 = usize;
 
-    Ok(puzzle_input.eval().to_string())
-}
-
-fn run_part2(input: PathBuf) -> Result<String, ApplicationError> {
-    let puzzle_input = read_puzzle_input(input)?;
-    
-    puzzle_input.first_basement()
-        .map(|f| f.to_string())
-        .ok_or(ApplicationError::NoAnswer)
-}
-
-fn read_puzzle_input(filename: PathBuf) -> Result<PuzzleInput, ApplicationError> {
-    let puzzle_input = fs::read_to_string(filename)?;
-    let puzzle_input: PuzzleInput = puzzle_input.parse()?;
+    fn part1(puzzle_input: &PuzzleInput) -> Result<i32, SolutionError> {
+        Ok(puzzle_input.eval())
+    }
 
-    Ok(puzzle_input)
+    fn part2(puzzle_input: &PuzzleInput) -> Result<usize, SolutionError> {
+        puzzle_input.first_basement().ok_or(SolutionError::NoSolution)
+    }
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse puzzle input: {0}")]
-    CouldntParseInput(#[from] ParsePuzzleInputError),
-    #[error("couldn't find answer")]
-    NoAnswer
-}
\ No newline at end of file
@@ -42,7 +42,7 @@ impl FromStr for PuzzleInput {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut instructions = vec![];
 
-        for c in s.chars() {
+        for c in s.trim_end().chars() {
             let instruction = match c {
                 '(' => Instruction::Up,
                 ')' => Instruction::Down,
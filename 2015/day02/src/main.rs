@@ -2,12 +2,13 @@ use std::process;
 
 use clap::Parser;
 
-use advent_of_code_2015_day_2::{run, CliOptions};
+use aoc_common::{run, CliOptions};
+use advent_of_code_2015_day_2::Day;
 
 fn main() {
     let cli_options = CliOptions::parse();
     
-    let result = run(cli_options).unwrap_or_else(|err| {
+    let result = run::<Day>(cli_options).unwrap_or_else(|err| {
         println!("Application error: {}", err);
         process::exit(1);
     });
@@ -1,4 +1,3 @@
-use std::cmp::min;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
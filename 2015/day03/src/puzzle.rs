@@ -35,13 +35,9 @@ impl PuzzleInput {
         visited.insert(santa.pos());
 
         let mut iterator = self.directions.iter();
-        loop {
-            if let Some(direction) = iterator.next() {
-                santa.go(*direction);
-                visited.insert(santa.pos());
-            } else {
-                break;
-            }
+        while let Some(direction) = iterator.next() {
+            santa.go(*direction);
+            visited.insert(santa.pos());
 
             if let Some(direction) = iterator.next() {
                 robosanta.go(*direction);
@@ -61,7 +57,7 @@ impl FromStr for PuzzleInput {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut directions = vec![];
 
-        for c in s.chars() {
+        for c in s.trim_end().chars() {
             let direction = match c {
                 '^' => Direction::North,
                 '>' => Direction::East,
@@ -97,6 +93,12 @@ pub struct Santa {
     y: i32
 }
 
+impl Default for Santa {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Santa {
     pub fn new() -> Santa {
         Santa { x: 0, y: 0 }
@@ -1,56 +1,24 @@
-use std::fs;
-use std::io;
-use std::path::PathBuf;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use clap::Subcommand;
-use thiserror::Error;
+use crate::puzzle::PuzzleInput;
 
-use crate::puzzle::{ParsePuzzleInputError, PuzzleInput};
+pub mod puzzle;
 
-mod puzzle;
+pub struct Day;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    #[command(subcommand)]
-    command: Commands
-}
-
-#[derive(Subcommand)]
-enum Commands {
-    Part1 {
-        input: String
-    },
-    Part2 {
-        input: String
-    }
-}
-
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let result = match options.command {
-        Commands::Part1 { input } => run_part1(input),
-        Commands::Part2 { input } => run_part2(input)
-    }?;
-
-    Ok(result.to_string())
-}
-
-fn run_part1(input: String) -> Result<String, ApplicationError> {
-    let puzzle_input: PuzzleInput = input.parse().unwrap();
+impl Solution for Day {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 4;
 
-    Ok(puzzle_input.mine(5).to_string())
-}
+    type Input = PuzzleInput;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-fn run_part2(input: String) -> Result<String, ApplicationError> {   
-    let puzzle_input: PuzzleInput = input.parse().unwrap();
+    fn part1(puzzle_input: &PuzzleInput) -> Result<u32, SolutionError> {
+        Ok(puzzle_input.mine(5))
+    }
 
-    Ok(puzzle_input.mine(6).to_string())
+    fn part2(puzzle_input: &PuzzleInput) -> Result<u32, SolutionError> {
+        Ok(puzzle_input.mine(6))
+    }
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
-}
\ No newline at end of file
@@ -30,10 +30,10 @@ impl PuzzleInput {
 }
 
 impl FromStr for PuzzleInput {
-    type Err = ();
+    type Err = ParsePuzzleInputError;
     
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(PuzzleInput::new(s.to_string()))
+        Ok(PuzzleInput::new(s.trim().to_string()))
     }
 }
 
@@ -46,7 +46,8 @@ mod tests {
     use super::*;
 
     #[test]
-    pub fn test() {
-        todo!()
+    pub fn part1_examples() {
+        assert_eq!("abcdef".parse::<PuzzleInput>().unwrap().mine(5), 609043);
+        assert_eq!("pqrstuv".parse::<PuzzleInput>().unwrap().mine(5), 1048970);
     }
 }
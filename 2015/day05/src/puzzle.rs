@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use thiserror::Error;
+
 #[derive(Debug)]
 pub struct PuzzleInput {
     strings: Vec<String>
@@ -10,17 +12,17 @@ impl PuzzleInput {
         PuzzleInput { strings }
     }
 
-    pub fn part1_nice_strings<'a>(&'a self) -> impl Iterator<Item = &'a str> {
-        self.strings.iter().filter_map(|s| if part1::is_nice_string(&s) { Some(s.as_str()) } else { None })
+    pub fn part1_nice_strings(&self) -> impl Iterator<Item = &str> {
+        self.strings.iter().filter_map(|s| if part1::is_nice_string(s) { Some(s.as_str()) } else { None })
     }
 
-    pub fn part2_nice_strings<'a>(&'a self) -> impl Iterator<Item = &'a str> {
-        self.strings.iter().filter_map(|s| if part2::is_nice_string(&s) { Some(s.as_str()) } else { None })
+    pub fn part2_nice_strings(&self) -> impl Iterator<Item = &str> {
+        self.strings.iter().filter_map(|s| if part2::is_nice_string(s) { Some(s.as_str()) } else { None })
     }
 }
 
 impl FromStr for PuzzleInput {
-    type Err = ();
+    type Err = ParsePuzzleInputError;
     
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let strings = s.lines().map(|line| line.to_owned()).collect();
@@ -28,6 +30,10 @@ impl FromStr for PuzzleInput {
     }
 }
 
+#[derive(Error, Debug)]
+pub enum ParsePuzzleInputError {
+}
+
 mod part1 {
     pub fn is_nice_string(s: &str) -> bool {
         has_three_vowels(s) &&
@@ -40,10 +46,7 @@ mod part1 {
     }
 
     fn is_vowel(c: char) -> bool {
-        match c {
-            'a' | 'e' | 'i' | 'o' | 'u' => true,
-            _ => false
-        }
+        matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
     }
 
     fn has_doubled_letter(s: &str) -> bool {
@@ -84,7 +87,7 @@ mod part2 {
             }
         }
 
-        return false;
+        false
     }
 
     fn has_doubled_letter_with_gap_of_one(s: &str) -> bool {
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
@@ -44,7 +44,7 @@ impl FromStr for PuzzleInput {
     
         let mut instructions = Vec::new();
 
-        for (_, [operation, x1, y1, x2, y2]) in re.captures_iter(&s).map(|c| c.extract()) {
+        for (_, [operation, x1, y1, x2, y2]) in re.captures_iter(s).map(|c| c.extract()) {
             let operation = match operation {
                 "turn on" => Operation::TurnOn,
                 "turn off" => Operation::TurnOff,
@@ -105,6 +105,12 @@ pub struct LightGrid {
     lights: HashMap<Point, usize>
 }
 
+impl Default for LightGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LightGrid {
     pub fn new() -> LightGrid {
         LightGrid { lights: HashMap::new() }
@@ -122,9 +128,7 @@ impl LightGrid {
                         self.lights.remove(&point);
                     }
                     Operation::Toggle => {
-                        if self.lights.contains_key(&point) {
-                            self.lights.remove(&point);
-                        } else {
+                        if self.lights.remove(&point).is_none() {
                             self.lights.insert(point, 1);
                         }
                     }
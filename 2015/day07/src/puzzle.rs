@@ -39,8 +39,6 @@ impl FromStr for PuzzleInput {
         let mut statements = Vec::new();
 
         for line in s.lines() {
-            println!("{:?}", line);
-
             let statement = line.parse::<Statement>()?;
             statements.push(statement);
         }
@@ -61,7 +59,7 @@ struct EvaluationContext<'a> {
 }
 
 impl<'a> EvaluationContext<'a> {
-    pub fn new(statements: &'a Vec<Statement>) -> EvaluationContext {
+    pub fn new(statements: &'a Vec<Statement>) -> EvaluationContext<'a> {
         let values = HashMap::new();
         EvaluationContext { statements, values }
     }
@@ -80,8 +78,6 @@ impl<'a> EvaluationContext<'a> {
         } else {
             let statement_for_wire = self.statements.iter().find(|s| s.output == wire);
             if let Some(statement) = statement_for_wire {
-                println!("{:?}", statement);
-
                 let result = self.eval_expression(&statement.expression)?;
 
                 self.values.insert(wire.to_owned(), result);
@@ -228,7 +224,27 @@ mod tests {
     use super::*;
 
     #[test]
-    pub fn test() {
-        todo!()
+    pub fn example_circuit() {
+        let puzzle_input: PuzzleInput = [
+            "123 -> x",
+            "456 -> y",
+            "x AND y -> d",
+            "x OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "NOT y -> i"
+        ].join("\n").parse().unwrap();
+
+        let mut eval_context = EvaluationContext::new(&puzzle_input.statements);
+
+        assert_eq!(eval_context.eval_statement("d"), Some(72));
+        assert_eq!(eval_context.eval_statement("e"), Some(507));
+        assert_eq!(eval_context.eval_statement("f"), Some(492));
+        assert_eq!(eval_context.eval_statement("g"), Some(114));
+        assert_eq!(eval_context.eval_statement("h"), Some(65412));
+        assert_eq!(eval_context.eval_statement("i"), Some(65079));
+        assert_eq!(eval_context.eval_statement("x"), Some(123));
+        assert_eq!(eval_context.eval_statement("y"), Some(456));
     }
 }
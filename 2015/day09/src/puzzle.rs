@@ -1,7 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::num::ParseIntError;
 use std::str::FromStr;
-use std::usize;
 
 use itertools::Itertools;
 use thiserror::Error;
@@ -16,10 +15,9 @@ impl PuzzleInput {
     pub fn shortest_distance(&self) -> usize {
         let mut shortest = usize::MAX;
         for permutation in self.locations.iter().permutations(self.locations.len()) {
-            if let Some(distance) = self.distance_for_route(&permutation) {
-                if distance < shortest {
-                    shortest = distance;
-                }
+            if let Some(distance) = self.distance_for_route(&permutation)
+                && distance < shortest {
+                shortest = distance;
             }            
         }
 
@@ -29,10 +27,9 @@ impl PuzzleInput {
     pub fn longest_distance(&self) -> usize {
         let mut longest = 0;
         for permutation in self.locations.iter().permutations(self.locations.len()) {
-            if let Some(distance) = self.distance_for_route(&permutation) {
-                if distance > longest {
-                    longest = distance;
-                }
+            if let Some(distance) = self.distance_for_route(&permutation)
+                && distance > longest {
+                longest = distance;
             }            
         }
 
@@ -101,12 +98,6 @@ struct Edge {
     distance: usize
 }
 
-impl Edge {
-    pub fn invert(&self) -> Edge {
-        Edge { start: self.end.clone(), end: self.start.clone(), distance: self.distance }
-    }
-}
-
 impl FromStr for Edge {
     type Err = ParseEdgeError;
     
@@ -4,13 +4,26 @@ use thiserror::Error;
 
 #[derive(Debug)]
 pub struct PuzzleInput {
+    lines: Vec<String>
+}
+
+impl PuzzleInput {
+    pub fn new(lines: Vec<String>) -> PuzzleInput {
+        PuzzleInput { lines }
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
 }
 
 impl FromStr for PuzzleInput {
     type Err = ParsePuzzleInputError;
     
-    fn from_str(_s: &str) -> Result<Self, Self::Err> {
-        todo!()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().map(|line| line.to_string()).collect();
+
+        Ok(PuzzleInput::new(lines))
     }
 }
 
@@ -23,7 +36,9 @@ mod tests {
     use super::*;
 
     #[test]
-    pub fn test() {
-        todo!()
+    pub fn parse_example() {
+        let puzzle_input = "first\nsecond\n".parse::<PuzzleInput>().unwrap();
+
+        assert_eq!(puzzle_input.lines(), &["first", "second"]);
     }
 }
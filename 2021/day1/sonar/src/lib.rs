@@ -4,6 +4,8 @@ use std::fs::{File};
 use std::io::{self, BufRead};
 use std::path::Path;
 
+use aoc_common::{Part, Puzzle};
+
 use config::Config;
 
 pub mod config;
@@ -27,6 +29,28 @@ pub fn run(config: Config) -> Result<u32, Box<dyn Error>> {
     Ok(increase_count)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::ONE
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let config = Config { filename: input.to_string_lossy().to_string() };
+
+        Ok(run(config)?.to_string())
+    }
+}
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where P: AsRef<Path>, {
     let file = File::open(filename)?;
@@ -81,7 +81,7 @@ pub fn parse(line: String) -> ParserResult {
         }
     }
 
-    if state.state_stack.len() > 0 {
+    if !state.state_stack.is_empty() {
         ParserResult::Incomplete
     } else {
         ParserResult::Legal
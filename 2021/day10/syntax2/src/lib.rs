@@ -4,6 +4,7 @@ use std::path::Path;
 
 use clap::Parser;
 use thiserror::Error;
+use aoc_common::{Part, Puzzle};
 
 pub mod parser;
 
@@ -34,6 +35,28 @@ pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
     Ok(*middle_score)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
+}
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where P: AsRef<Path>, {
     let file = File::open(filename)?;
@@ -1,9 +1,8 @@
-use std::env;
 use std::process;
 
 use clap::Parser;
 
-use syntax::{run, CliOptions};
+use syntax2::{run, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
@@ -1,4 +1,3 @@
-use std::net::Incoming;
 
 #[derive(PartialEq, Eq, Debug)]
 pub enum ParserResult {
@@ -52,6 +51,12 @@ pub struct ParserStateStack {
     state_stack: Vec<ParserState>
 }
 
+impl Default for ParserStateStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ParserStateStack {
     pub fn new() -> ParserStateStack {
         let current_state = ParserState::ExpectingAnything;
@@ -120,7 +125,7 @@ pub fn parse(line: String) -> ParserResult {
         }
     }
 
-    if state.state_stack.len() > 0 {
+    if !state.state_stack.is_empty() {
         ParserResult::Incomplete(state)
     } else {
         ParserResult::Legal
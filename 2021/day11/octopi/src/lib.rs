@@ -1,18 +1,30 @@
-use std::collections::btree_map::IterMut;
-use std::fs::{File, read_to_string};
-use std::io::{self, BufRead};
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use thiserror::Error;
 
-use octopi::{OctopiGrid, ParseOctopiGridError};
+use aoc_common::{Part, Puzzle};
+use aoc_grid::ParseGridError;
+use aoc_grid::frames::FrameFormat;
+
+use octopi::OctopiGrid;
 
 pub mod octopi;
 
 #[derive(Parser)]
 pub struct CliOptions {
-    filename: std::path::PathBuf,
-    iterations: u64
+    filename: PathBuf,
+    iterations: u64,
+
+    /// Record a frame per step to this file or directory
+    #[clap(long)]
+    record: Option<PathBuf>,
+
+    /// text, pbm, pgm or cast
+    #[clap(long, default_value = "cast")]
+    record_format: FrameFormat
 }
 
 pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
@@ -21,21 +33,58 @@ pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
 
     let contents = read_to_string(filename)?;
     let mut grid = contents.parse::<OctopiGrid>()?;
-    println!("{}", grid);
+    let mut sink = match options.record {
+        Some(path) => Some(options.record_format.create_sink(path)?),
+        None => None
+    };
+
+    if let Some(sink) = sink.as_mut() {
+        sink.write_frame(&grid.frame(), 0.0)?;
+    }
 
     let mut total_flashes = 0;
-    for _ in 0..iterations {
+    for n in 0..iterations {
         total_flashes += grid.step();
-        println!("{}", grid);
+
+        if let Some(sink) = sink.as_mut() {
+            sink.write_frame(&grid.frame(), (n + 1) as f64)?;
+        }
     }
 
     Ok(total_flashes)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::ONE
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions {
+            filename: input.to_path_buf(),
+            iterations: 100,
+            record: None,
+            record_format: FrameFormat::Asciicast
+        };
+
+        Ok(run(options)?.to_string())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
     CouldntReadInput(#[from] io::Error),
     #[error("couldn't parse puzzle input: {0}")]
-    CouldntParseInput(#[from] ParseOctopiGridError)
+    CouldntParseInput(#[from] ParseGridError)
 }
\ No newline at end of file
@@ -1,9 +1,8 @@
-use std::env;
 use std::process;
 
 use clap::Parser;
 
-use octopi::{run, CliOptions};
+use octopi2::{run, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
@@ -6,6 +6,12 @@ pub struct CaveSystem {
     connections: HashMap<String, Vec<String>>
 }
 
+impl Default for CaveSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CaveSystem {
     pub fn new() -> CaveSystem {
         let caves = HashMap::new();
@@ -28,11 +34,11 @@ impl CaveSystem {
         }
     }
 
-    fn add_connection_single(&mut self, from: &String, to: &String) {
-        let from = from.clone();
-        let to = to.clone();
+    fn add_connection_single(&mut self, from: &str, to: &str) {
+        let from = from.to_string();
+        let to = to.to_string();
 
-        let connections = self.connections.entry(from).or_insert_with(|| vec![]);
+        let connections = self.connections.entry(from).or_default();
         connections.push(to);
     }
 
@@ -71,7 +77,6 @@ impl CaveSystem {
 
 #[derive(Debug)]
 pub struct Cave {
-    id: String,
     is_big: bool
 }
 
@@ -79,12 +84,6 @@ impl Cave {
     pub fn new(id: String) -> Cave {
         let is_big = id.chars().nth(0).unwrap().is_uppercase();
         
-        Cave { id, is_big }
+        Cave { is_big }
     }
 }
-
-#[derive(Debug)]
-pub struct Connection {
-    from: String,
-    to: String
-}
@@ -6,10 +6,9 @@ use clap::Parser;
 use thiserror::Error;
 
 pub mod graph;
-use graph::{
-    Cave,
-    CaveSystem
-};
+use aoc_common::{Part, Puzzle};
+
+use graph::CaveSystem;
 
 #[derive(Parser)]
 pub struct CliOptions {
@@ -36,12 +35,31 @@ pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
 
     let paths = cave_system.paths();
 
-    println!("{:?}", cave_system);
-    println!("{:?}", paths);
-
     Ok(paths.len())
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
+}
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where P: AsRef<Path>, {
     let file = File::open(filename)?;
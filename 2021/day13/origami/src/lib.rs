@@ -1,11 +1,13 @@
 use std::collections::HashSet;
-use std::fs::{File, read_to_string};
-use std::io::{self, BufRead};
+use std::fs::read_to_string;
+use std::io;
 use std::path::Path;
 
 use clap::Parser;
 use thiserror::Error;
 
+use aoc_common::{Part, Puzzle};
+
 use paper::Paper;
 use parser::parse_puzzle_input;
 
@@ -39,10 +41,26 @@ pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
     Ok(paper.len())
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::ONE
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -1,9 +1,8 @@
-use std::env;
 use std::process;
 
 use clap::Parser;
 
-use template::{run, CliOptions};
+use origami::{run, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
@@ -44,6 +44,10 @@ impl Paper {
     pub fn len(&self) -> usize {
         self.dots.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.dots.is_empty()
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
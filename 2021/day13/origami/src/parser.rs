@@ -29,11 +29,11 @@ fn instruction(input: &str) -> IResult<&str, Instruction> {
 }
 
 fn fold_up(input: &str) -> IResult<&str, Instruction> {
-    map(preceded(tag("fold along y="), number), |amount| Instruction::FoldUp(amount))(input)
+    map(preceded(tag("fold along y="), number), Instruction::FoldUp)(input)
 }
 
 fn fold_left(input: &str) -> IResult<&str, Instruction> {
-    map(preceded(tag("fold along x="), number), |amount| Instruction::FoldLeft(amount))(input)
+    map(preceded(tag("fold along x="), number), Instruction::FoldLeft)(input)
 }
 
 fn point_set(input: &str) -> IResult<&str, Vec<Point>> {
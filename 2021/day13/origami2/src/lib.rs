@@ -1,11 +1,13 @@
 use std::collections::HashSet;
-use std::fs::{File, read_to_string};
-use std::io::{self, BufRead};
+use std::fs::read_to_string;
+use std::io;
 use std::path::Path;
 
 use clap::Parser;
 use thiserror::Error;
 
+use aoc_common::{Part, Puzzle};
+
 use paper::Paper;
 use parser::parse_puzzle_input;
 
@@ -19,6 +21,12 @@ pub struct CliOptions {
 }
 
 pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
+    Ok(fold(options)?.len())
+}
+
+/// Folds the paper through every instruction. The code to enter is spelled out by the dots
+/// that are left, so print the result to read it.
+pub fn fold(options: CliOptions) -> Result<Paper, ApplicationError> {
     let filename = options.filename;
 
     let contents = read_to_string(filename)?;
@@ -29,24 +37,36 @@ pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
 
     let mut paper = Paper::new(dot_set);
 
-    println!("{}", paper);
-
     for instruction in puzzle_input.instructions.iter() {
         match instruction {
             puzzle::Instruction::FoldUp(fold_y) => paper.fold_up(*fold_y),
             puzzle::Instruction::FoldLeft(fold_x) => paper.fold_left(*fold_x)
         }
-        
-        println!("{}", paper);
     }
 
-    Ok(paper.len())
+    Ok(paper)
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
 }
 
 #[derive(Debug, Error)]
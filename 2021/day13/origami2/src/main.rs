@@ -1,17 +1,17 @@
-use std::env;
 use std::process;
 
 use clap::Parser;
 
-use template::{run, CliOptions};
+use origami2::{fold, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
     
-    let result = run(cli_options).unwrap_or_else(|err| {
+    let paper = fold(cli_options).unwrap_or_else(|err| {
         println!("Application error: {}", err);
         process::exit(1);
     });
     
-    println!("{}", result);
+    println!("{}", paper);
+    println!("{}", paper.len());
 }
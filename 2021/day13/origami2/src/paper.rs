@@ -1,8 +1,6 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 
-use itertools::Itertools;
-
 #[derive(Debug, PartialEq, Eq)]
 pub struct Paper {
     dots: HashSet<Point>
@@ -48,6 +46,10 @@ impl Paper {
         self.dots.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.dots.is_empty()
+    }
+
     pub fn dimensions(&self) -> (i64, i64) {
         let max_x = self.dots.iter().max_by(|a, b| a.0.cmp(&b.0)).unwrap().0;
         let max_y = self.dots.iter().max_by(|a, b| a.1.cmp(&b.1)).unwrap().1;
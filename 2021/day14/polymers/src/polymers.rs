@@ -24,7 +24,7 @@ impl PolymerChain {
         PolymerChain { polymers }
     }
 
-    pub fn apply_rules(&mut self, rules: &Vec<InsertionRule>) {
+    pub fn apply_rules(&mut self, rules: &[InsertionRule]) {
         let mut next_polymers = vec![];
 
         for i in 0..self.polymers.len() - 1 {
@@ -52,8 +52,8 @@ impl PolymerChain {
 
     pub fn score(&self) -> usize {
         let counts = self.polymers.iter().counts_by(|polymer| &polymer.0);
-        let (_, &minimum) = counts.iter().min_by(|a, b| a.1.cmp(&b.1)).unwrap();
-        let (_, &maximum) = counts.iter().max_by(|a, b| a.1.cmp(&b.1)).unwrap();
+        let (_, &minimum) = counts.iter().min_by(|a, b| a.1.cmp(b.1)).unwrap();
+        let (_, &maximum) = counts.iter().max_by(|a, b| a.1.cmp(b.1)).unwrap();
 
         maximum - minimum
     }
@@ -61,9 +61,11 @@ impl PolymerChain {
 
 impl Display for PolymerChain {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Ok(for polymer in self.polymers.iter() {
+        for polymer in self.polymers.iter() {
             write!(f, "{}", polymer.0)?
-        })
+        }
+
+        Ok(())
     }
 }
 
@@ -71,7 +73,7 @@ impl Display for PolymerChain {
 pub struct Polymer(pub String);
 
 impl Polymer {
-    pub fn from_str(s: &str) -> Polymer {
+    pub fn new(s: &str) -> Polymer {
         Polymer(s.to_string())
     }
 }
@@ -90,9 +92,9 @@ impl InsertionRule {
 
     pub fn from_str(first: &str, second: &str, result: &str) -> InsertionRule {
         InsertionRule::new(
-            Polymer::from_str(first),
-            Polymer::from_str(second),
-            Polymer::from_str(result))
+            Polymer::new(first),
+            Polymer::new(second),
+            Polymer::new(result))
     }
 }
 
@@ -104,22 +106,22 @@ mod tests {
     #[test]
     fn test1() {
         let mut chain = PolymerChain::new(vec![
-            Polymer::from_str("A"),
-            Polymer::from_str("B"),
-            Polymer::from_str("C"),
+            Polymer::new("A"),
+            Polymer::new("B"),
+            Polymer::new("C"),
         ]);
         
-        chain.apply_rules(&vec![
+        chain.apply_rules(&[
             InsertionRule::from_str("A", "B", "C"),
             InsertionRule::from_str("B", "C", "D"),
         ]);
 
         let expected = PolymerChain::new(vec![
-            Polymer::from_str("A"),
-            Polymer::from_str("C"),
-            Polymer::from_str("B"),
-            Polymer::from_str("D"),
-            Polymer::from_str("C")
+            Polymer::new("A"),
+            Polymer::new("C"),
+            Polymer::new("B"),
+            Polymer::new("D"),
+            Polymer::new("C")
         ]);
 
         assert_eq!(expected, chain);
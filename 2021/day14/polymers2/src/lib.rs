@@ -1,8 +1,10 @@
-use std::fs::{File, read_to_string};
-use std::io::{self, BufRead};
+use std::fs::read_to_string;
+use std::io;
 use std::path::Path;
 
 use clap::Parser;
+use aoc_common::{Part, Puzzle};
+
 use parser::parse_puzzle_input;
 use thiserror::Error;
 
@@ -24,7 +26,7 @@ pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
 
     let mut chain = puzzle_input.polymer_template.clone();
 
-    for n in 0..iterations {
+    for _ in 0..iterations {
         chain.apply_rules(&puzzle_input.insertion_rules);
     }
 
@@ -33,6 +35,28 @@ pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
     Ok(score)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf(), iterations: 40 };
+
+        Ok(run(options)?.to_string())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
@@ -32,7 +32,7 @@ fn insertion_rule(input: &str) -> IResult<&str, InsertionRule> {
 fn polymer_chain(input: &str) -> IResult<&str, PolymerChain> {
     map(
         delimited(space0, many1(polymer), space0),
-        |polymers| PolymerChain::new(polymers)
+        PolymerChain::new
     )(input)
 }
 
@@ -53,8 +53,6 @@ fn is_letter(chr: char) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use std::net::ToSocketAddrs;
-
     use indoc::indoc;
 
     use crate::polymers::PolymerChain;
@@ -85,10 +83,10 @@ mod tests {
         ")).unwrap();
         
         assert_eq!(PuzzleInput::new(PolymerChain::new(vec![
-            Polymer::from_str("N"),
-            Polymer::from_str("N"),
-            Polymer::from_str("C"),
-            Polymer::from_str("B")
+            Polymer::new("N"),
+            Polymer::new("N"),
+            Polymer::new("C"),
+            Polymer::new("B")
         ]), vec![
             InsertionRule::from_str("C", "H", "B"),
             InsertionRule::from_str("H", "H", "N"),
@@ -1,7 +1,4 @@
 use std::collections::HashMap;
-use std::fmt::Display;
-
-use itertools::Itertools;
 
 #[derive(PartialEq, Eq, Debug)]
 pub struct PuzzleInput {
@@ -43,7 +40,7 @@ impl PolymerChain {
         PolymerChain { counts, pairs }
     }
 
-    pub fn apply_rules(&mut self, rules: &Vec<InsertionRule>) {
+    pub fn apply_rules(&mut self, rules: &[InsertionRule]) {
         let mut new_pairs = HashMap::new();
 
         for rule in rules.iter() {
@@ -65,8 +62,8 @@ impl PolymerChain {
     }
     
     pub fn score(&self) -> u64 {
-        let (_, &minimum) = self.counts.iter().min_by(|a, b| a.1.cmp(&b.1)).unwrap();
-        let (_, &maximum) = self.counts.iter().max_by(|a, b| a.1.cmp(&b.1)).unwrap();
+        let (_, &minimum) = self.counts.iter().min_by(|a, b| a.1.cmp(b.1)).unwrap();
+        let (_, &maximum) = self.counts.iter().max_by(|a, b| a.1.cmp(b.1)).unwrap();
 
         maximum - minimum
     }
@@ -106,7 +103,7 @@ impl PolymerChain {
 pub struct Polymer(pub String);
 
 impl Polymer {
-    pub fn from_str(s: &str) -> Polymer {
+    pub fn new(s: &str) -> Polymer {
         Polymer(s.to_string())
     }
 }
@@ -126,9 +123,9 @@ impl InsertionRule {
 
     pub fn from_str(first: &str, second: &str, insert: &str) -> InsertionRule {
         InsertionRule::new(
-            Polymer::from_str(first),
-            Polymer::from_str(second),
-            Polymer::from_str(insert)
+            Polymer::new(first),
+            Polymer::new(second),
+            Polymer::new(insert)
         )
     }
 }
@@ -141,22 +138,22 @@ mod tests {
     #[test]
     fn test1() {
         let mut chain = PolymerChain::new(vec![
-            Polymer::from_str("A"),
-            Polymer::from_str("B"),
-            Polymer::from_str("C"),
+            Polymer::new("A"),
+            Polymer::new("B"),
+            Polymer::new("C"),
         ]);
         
-        chain.apply_rules(&vec![
+        chain.apply_rules(&[
             InsertionRule::from_str("A", "B", "C"),
             InsertionRule::from_str("B", "C", "D"),
         ]);
 
         let expected = PolymerChain::new(vec![
-            Polymer::from_str("A"),
-            Polymer::from_str("C"),
-            Polymer::from_str("B"),
-            Polymer::from_str("D"),
-            Polymer::from_str("C")
+            Polymer::new("A"),
+            Polymer::new("C"),
+            Polymer::new("B"),
+            Polymer::new("D"),
+            Polymer::new("C")
         ]);
 
         assert_eq!(expected, chain);
@@ -1,6 +1,5 @@
-use std::fs::{File};
-use std::io::{self, BufRead};
-use std::ops::Add;
+use std::fs;
+use std::io;
 use std::path::Path;
 
 use clap::Parser;
@@ -8,7 +7,10 @@ use thiserror::Error;
 
 pub mod map;
 
-use map::MapBuilder;
+use aoc_common::{Part, Puzzle};
+use aoc_grid::{ParseGridError, Point};
+
+use map::Map;
 
 #[derive(Parser)]
 pub struct CliOptions {
@@ -18,28 +20,40 @@ pub struct CliOptions {
 pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
     let filename = options.filename;
 
-    let mut map_builder = MapBuilder::new();
+    let contents = fs::read_to_string(filename)?;
+    let map = contents.parse::<Map>()?;
+
+    let shortest_path = map.shortest_path(Point::new(0, 0), map.corner()).unwrap();
+
+    Ok(shortest_path)
+}
+
+pub struct Day;
 
-    let lines = read_lines(filename)?;
-    for line in lines {
-        map_builder.add_line(line?);
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
     }
 
-    let map = map_builder.to_map();
+    fn day(&self) -> u8 {
+        15
+    }
 
-    let shortest_path = map.shortest_path((0, 0), map.corner()).unwrap();
+    fn parts(&self) -> &'static [Part] {
+        Part::ONE
+    }
 
-    Ok(shortest_path)
-}
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+        Ok(run(options)?.to_string())
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
+    CouldntReadInput(#[from] io::Error),
+    #[error("couldn't parse puzzle input: {0}")]
+    CouldntParseInput(#[from] ParseGridError)
 }
\ No newline at end of file
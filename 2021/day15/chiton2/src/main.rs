@@ -1,9 +1,8 @@
-use std::env;
 use std::process;
 
 use clap::Parser;
 
-use chiton::{run, CliOptions};
+use chiton2::{run, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
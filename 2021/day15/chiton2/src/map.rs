@@ -1,149 +1,55 @@
-use std::collections::{HashMap, HashSet, BinaryHeap};
+use std::str::FromStr;
 
-#[derive(Debug)]
-pub struct MapBuilder {
-    values: Vec<u64>,
-    width: Option<usize>,
-    height: usize
-}
-
-impl MapBuilder {
-    pub fn new() -> MapBuilder {
-        let lines = vec![];
-        let width = None;
-
-        MapBuilder { values: lines, width, height: 0 }
-    }
-
-    pub fn add_line(&mut self, line: String) {
-        if self.width == None {
-            self.width = Some(line.len());
-        }
-
-        for value in line.chars().map(|c| c.to_string().parse::<u64>().unwrap()) {
-            self.values.push(value);
-        }
-
-        self.height += 1;
-    }
-
-    pub fn to_map(&self) -> Map {
-        let width: i64 = self.width.unwrap().try_into().unwrap();
-        let height: i64 = self.height.try_into().unwrap();
-
-        let mut nodes = HashMap::new();
-        
-        for x in 0..width {
-            for y in 0..height {
-                if let Some(cost) = self.get((x, y)) {
-                    for x_n in 0..5 {
-                        for y_n in 0..5 {
-                            let x = x + width * x_n;
-                            let y = y + height * y_n;
-                            
-                            let cost = (cost + (x_n as u64) + (y_n as u64)) % 9;
-                            let cost = if cost == 0 { 9 } else { cost };
-                            
-                            nodes.insert((x, y), cost);
-                        }
-                    }
-                }
-            }
-        }
-
-        Map { nodes, width: width * 5, height: height * 5 }
-    }
-
-    fn get(&self, pos: (i64, i64)) -> Option<u64> {
-        let width: i64 = self.width.unwrap().try_into().unwrap();
-        let (x, y) = pos;
-        if x < 0 || y < 0 || x >= width {
-            None
-        } else {
-            let y: usize = y.try_into().unwrap();
-            let x: usize = x.try_into().unwrap();
-            let i: usize = (y * self.width.unwrap()) + x;
-            
-            if let Some(&element) = self.values.get(i) {
-                Some(element)
-            } else {
-                None
-            }
-        }
-    }
-}
+use aoc_grid::{Grid, ParseGridError, Point};
+use aoc_search::astar;
 
 #[derive(Debug)]
 pub struct Map {
-    nodes: HashMap<(i64, i64), u64>,
-    width: i64,
-    height: i64
+    risks: Grid<u64>
 }
 
 impl Map {
-    pub fn corner(&self) -> (i64, i64) {
-        let x = self.width - 1;
-        let y = self.height - 1;
+    pub fn corner(&self) -> Point {
+        let x: i32 = self.risks.width().try_into().unwrap();
+        let y: i32 = self.risks.height().try_into().unwrap();
 
-        (x, y)
+        Point::new(x - 1, y - 1)
     }
 
-    pub fn shortest_path(&self, start: (i64, i64), goal: (i64, i64)) -> Option<u64> {
-        let mut dist = HashMap::new();
-        for (&node, _) in self.nodes.iter() {
-            dist.insert(node, u64::MAX);
-        }
+    pub fn shortest_path(&self, start: Point, goal: Point) -> Option<u64> {
+        let neighbors = |&node: &Point| {
+            self.risks.neighbors4(node).map(|(next, &cost)| (next, cost))
+        };
+        let heuristic = |node: &Point| u64::from(node.manhattan_distance(&goal));
 
-        let mut heap = BinaryHeap::new();
+        let (_, cost) = astar(start, neighbors, heuristic, |&node| node == goal)?;
 
-        dist.insert(start, 0);
-        heap.push(PathFinderState { node: start, cost: 0 });
+        Some(cost)
+    }
+}
 
-        while let Some(PathFinderState { node, cost }) = heap.pop() {
-            if node == goal {
-                return Some(cost);
-            }
+impl FromStr for Map {
+    type Err = ParseGridError;
 
-            if cost > dist[&node] {
-                continue;
-            }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tile = Grid::from_chars(s, |c| c.to_digit(10).map(u64::from))?;
 
-            let (x, y) = node;
-            let north = (x, y - 1);
-            let east = (x + 1, y);
-            let south = (x, y + 1);
-            let west = (x - 1, y);
+        let width = tile.width();
+        let height = tile.height();
 
-            for next in [north, east, south, west] {
-                if let Some(next_cost) = self.nodes.get(&next) {
-                    let state = PathFinderState { cost: cost + next_cost, node: next };
-                    if state.cost < dist[&next] {
-                        dist.insert(next, state.cost);
-                        heap.push(state);
-                    }
-                }
-            }
-        }
+        let risks = Grid::from_fn(width * 5, height * 5, |Point { x, y }| {
+            let x: usize = x.try_into().unwrap();
+            let y: usize = y.try_into().unwrap();
 
-        None
-    }
-}
+            let tile_x: i32 = (x % width).try_into().unwrap();
+            let tile_y: i32 = (y % height).try_into().unwrap();
 
-#[derive(PartialEq, Eq)]
-pub struct PathFinderState {
-    node: (i64, i64),
-    cost: u64
-}
+            let cost = tile[Point::new(tile_x, tile_y)];
+            let cost = (cost + (x / width) as u64 + (y / height) as u64) % 9;
 
-impl Ord for PathFinderState {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost) // Flip cost comparison
-            .then_with(|| self.node.cmp(&other.node))
-    }
-}
+            if cost == 0 { 9 } else { cost }
+        });
 
-impl PartialOrd for PathFinderState {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+        Ok(Map { risks })
     }
 }
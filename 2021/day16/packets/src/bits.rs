@@ -35,7 +35,7 @@ impl PacketContents {
 
     pub fn evaluate(&self) -> usize {
        match self {
-            PacketContents::Literal(value) => (*value) as usize,
+            PacketContents::Literal(value) => *value,
             PacketContents::Operator(operator_id, packets) => {
                 match operator_id {
                     0 => packets.iter().map(|p| p.evaluate()).sum(),
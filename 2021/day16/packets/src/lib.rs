@@ -1,11 +1,12 @@
-use std::fs::{File, read_to_string};
-use std::io::{self, BufRead};
+use std::fs::read_to_string;
+use std::io;
 use std::path::Path;
 
 use clap::Parser;
-use itertools::Itertools;
 use thiserror::Error;
 
+use aoc_common::{Part, Puzzle};
+
 use crate::{
     parser::parse_packet
 };
@@ -23,14 +24,34 @@ pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
 
     let contents = read_to_string(filename)?;
     let packet = parse_packet(&contents);
-    
-    println!("{:?}", packet);
 
     let result = packet.evaluate();
 
     Ok(result)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
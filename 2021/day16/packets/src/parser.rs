@@ -5,9 +5,8 @@ use nom::{
     IResult,
     bits,
     bits::complete::{tag, take},
-    combinator::map,
     multi::{many0, many1, many_m_n},
-    sequence::{pair, tuple}, branch::alt
+    sequence::pair, branch::alt
 };
 
 use crate::bits::{Packet, PacketContents};
@@ -16,7 +15,7 @@ pub fn parse_packet(i: &str) -> Packet {
     let i = get_bytes(i);
     let i = &i[..];
 
-    let (i, packet) = parse_packet_bytes(i).unwrap();
+    let (_, packet) = parse_packet_bytes(i).unwrap();
     packet
 }
 
@@ -57,11 +56,11 @@ fn literal(i: (&[u8], usize)) -> IResult<(&[u8], usize), PacketContents> {
 
     let mut result: usize = 0;
     for value in values {
-        result = result << 4;
+        result <<= 4;
         result |= value as usize;
     }
 
-    result = result << 4;
+    result <<= 4;
     result |= terminating_value as usize;
     
     Ok((i, PacketContents::Literal(result)))
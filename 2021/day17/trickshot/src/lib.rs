@@ -1,22 +1,58 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
 use clap::Parser;
 use thiserror::Error;
 
+use aoc_common::{Part, Puzzle};
+
 use crate::simulation::{Simulator, SimulationResult};
 
 pub mod simulation;
 
 #[derive(Parser)]
 pub struct CliOptions {
-    x1: isize,
-    x2: isize,
-    y1: isize,
-    y2: isize
+    filename: std::path::PathBuf
 }
 
-pub fn run(options: CliOptions) -> Result<u32, ApplicationError> {
-    let CliOptions {x1, x2, y1, y2} = options;
-    
-    let simulator = Simulator::new((x1, x2, y1, y2));
+pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
+    let contents = read_to_string(options.filename)?;
+    let target = parse_target_area(&contents)?;
+
+    let (count, best_starting_velocity, overall_highest_y) = survey(target);
+
+    Ok(format!("total: {}. best: {:?} -> {}", count, best_starting_velocity, overall_highest_y))
+}
+
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        17
+    }
+
+    fn run(&self, part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let contents = read_to_string(input)?;
+        let target = parse_target_area(&contents)?;
+
+        let (count, _, overall_highest_y) = survey(target);
+
+        let result = match part {
+            Part::One => overall_highest_y.to_string(),
+            Part::Two => count.to_string()
+        };
+
+        Ok(result)
+    }
+}
+
+fn survey(target: (isize, isize, isize, isize)) -> (u32, (isize, isize), isize) {
+    let (_, x2, _, _) = target;
+    let simulator = Simulator::new(target);
 
     let mut count = 0;
     let mut best_starting_velocity = (0, 0);
@@ -28,25 +64,53 @@ pub fn run(options: CliOptions) -> Result<u32, ApplicationError> {
             if let SimulationResult::Hit(highest) = result {
                 count += 1;
 
-                println!("hit ({}, {}) -> {}", x, y, highest);
-                
                 if highest > overall_highest_y {
                     overall_highest_y = highest;
                     best_starting_velocity = (x, y);
                 }
-            } else if let simulation::SimulationResult::Missed(furthest) = result {
-                
             }
         }
     }
 
-    println!("total: {}. best: {:?} -> {}", count, best_starting_velocity, overall_highest_y);
+    (count, best_starting_velocity, overall_highest_y)
+}
+
+fn parse_target_area(s: &str) -> Result<(isize, isize, isize, isize), ApplicationError> {
+    let invalid = || ApplicationError::CouldntParseTargetArea(s.trim().to_string());
+
+    let ranges = s.trim().strip_prefix("target area: ").ok_or_else(invalid)?;
+    let (x_range, y_range) = ranges.split_once(", ").ok_or_else(invalid)?;
+    
+    let parse_range = |range: &str, axis: &str| {
+        let (low, high) = range.strip_prefix(axis)?.split_once("..")?;
+        Some((low.parse::<isize>().ok()?, high.parse::<isize>().ok()?))
+    };
+
+    let (x1, x2) = parse_range(x_range, "x=").ok_or_else(invalid)?;
+    let (y1, y2) = parse_range(y_range, "y=").ok_or_else(invalid)?;
 
-    Ok(0)
+    Ok((x1, x2, y1, y2))
 }
 
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] std::io::Error)
-}
\ No newline at end of file
+    CouldntReadInput(#[from] std::io::Error),
+    #[error("couldn't parse target area: {0}")]
+    CouldntParseTargetArea(String)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let target = parse_target_area("target area: x=20..30, y=-10..-5").unwrap();
+        assert_eq!(target, (20, 30, -10, -5));
+
+        let (count, _, highest) = survey(target);
+        assert_eq!(highest, 45);
+        assert_eq!(count, 112);
+    }
+}
@@ -1,6 +1,8 @@
-use std::fs::{File, read_to_string};
-use std::io::{self, BufRead};
+use std::fs::read_to_string;
+use std::io;
 use std::path::Path;
+use aoc_common::{Part, Puzzle};
+
 use parser::parse_puzzle_input;
 use puzzle::Submarine;
 use thiserror::Error;
@@ -11,7 +13,7 @@ pub mod config;
 pub mod parser;
 pub mod puzzle;
 
-pub fn run<'a>(config: Config) -> Result<i64, ApplicationError> {
+pub fn run(config: Config) -> Result<i64, ApplicationError> {
     let filename = config.filename;
 
     let file_contents = read_to_string(filename)?;
@@ -28,10 +30,26 @@ pub fn run<'a>(config: Config) -> Result<i64, ApplicationError> {
     Ok(submarine.value())
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::ONE
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let config = Config { filename: input.to_string_lossy().to_string() };
+
+        Ok(run(config)?.to_string())
+    }
 }
 
 #[derive(Debug, Error)]
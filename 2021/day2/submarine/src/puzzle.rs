@@ -26,6 +26,12 @@ pub struct Submarine {
     depth: i64
 }
 
+impl Default for Submarine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Submarine {
     pub fn new() -> Submarine {
         Submarine { position: 0, depth: 0 }
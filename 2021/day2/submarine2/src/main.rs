@@ -1,7 +1,7 @@
 use std::env;
 use std::process;
 
-use submarine::{run, config::Config};
+use submarine2::{run, config::Config};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
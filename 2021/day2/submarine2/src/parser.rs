@@ -15,7 +15,7 @@ use crate::puzzle::{Instruction, PuzzleInput};
 pub fn parse_puzzle_input(input: &str) -> IResult<&str, PuzzleInput> {
     map(
         separated_list1(line_ending, instruction),
-        |instruction_sets| PuzzleInput::new(instruction_sets)
+        PuzzleInput::new
     )(input)
 }
 
@@ -24,15 +24,15 @@ fn instruction(input: &str) -> IResult<&str, Instruction> {
 }
 
 fn forward(input: &str) -> IResult<&str, Instruction> {
-    map(preceded(tag("forward"), number), |amount| Instruction::Forward(amount))(input)
+    map(preceded(tag("forward"), number), Instruction::Forward)(input)
 }
 
 fn down(input: &str) -> IResult<&str, Instruction> {
-    map(preceded(tag("down"), number), |amount| Instruction::Down(amount))(input)
+    map(preceded(tag("down"), number), Instruction::Down)(input)
 }
 
 fn up(input: &str) -> IResult<&str, Instruction> {
-    map(preceded(tag("up"), number), |amount| Instruction::Up(amount))(input)
+    map(preceded(tag("up"), number), Instruction::Up)(input)
 }
 
 fn number(input: &str) -> IResult<&str, i64> {
@@ -27,6 +27,12 @@ pub struct Submarine {
     aim: i64
 }
 
+impl Default for Submarine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Submarine {
     pub fn new() -> Submarine {
         Submarine { position: 0, depth: 0, aim: 0 }
@@ -2,6 +2,12 @@ pub struct Decoder {
     tracker: Vec<i64>
 }
 
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Decoder {
     pub fn new() -> Decoder {
         Decoder { tracker: vec![] }
@@ -23,7 +29,7 @@ impl Decoder {
                 self.tracker[i] -= 1;
             }
 
-            remaining_value = remaining_value >> 1;
+            remaining_value >>= 1;
 
             i += 1;
         }
@@ -2,6 +2,8 @@ use std::fs::{File};
 use std::io::{self, BufRead};
 use std::num::ParseIntError;
 use std::path::Path;
+use aoc_common::{Part, Puzzle};
+
 use decoder::Decoder;
 use thiserror::Error;
 
@@ -24,6 +26,28 @@ pub fn run(config: Config) -> Result<u64, ApplicationError> {
     Ok(decoder.calculate_power_consumption())
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::ONE
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let config = Config { filename: input.to_string_lossy().to_string() };
+
+        Ok(run(config)?.to_string())
+    }
+}
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where P: AsRef<Path>, {
     let file = File::open(filename)?;
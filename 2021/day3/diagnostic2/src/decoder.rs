@@ -5,27 +5,33 @@ pub enum DecoderMode {
 
 pub struct Decoder { }
 
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Decoder {
     pub fn new() -> Decoder {
         Decoder { }
     }
 
-    pub fn find_life_support_rating(&self, values: &Vec<String>) -> u64 {
+    pub fn find_life_support_rating(&self, values: &[String]) -> u64 {
         let oxygen = self.find_oxygen_rating(values);
         let co2 = self.find_co2_rating(values);
 
         oxygen * co2
     }
     
-    pub fn find_oxygen_rating(&self, values: &Vec<String>) -> u64 {
+    pub fn find_oxygen_rating(&self, values: &[String]) -> u64 {
         self.find_rating(values, DecoderMode::Oxygen)
     }
 
-    pub fn find_co2_rating(&self, values: &Vec<String>) -> u64 {
+    pub fn find_co2_rating(&self, values: &[String]) -> u64 {
         self.find_rating(values, DecoderMode::CO2)
     }
 
-    fn find_rating(&self, values: &Vec<String>, mode: DecoderMode) -> u64 {
+    fn find_rating(&self, values: &[String], mode: DecoderMode) -> u64 {
         let mut i: usize = 0;
 
         let mut current_values = vec![];
@@ -46,11 +52,11 @@ impl Decoder {
                 match value.chars().nth(i) {
                     Some('0') => {
                         counter -= 1;
-                        zeros.push(value.clone());
+                        zeros.push(*value);
                     },
                     Some('1') => {
                         counter += 1;
-                        ones.push(value.clone());
+                        ones.push(*value);
                     }
                     _ => panic!(":(")
                 };
@@ -79,9 +85,9 @@ mod tests {
 
     #[test]
     fn test() {
-        let mut decoder = Decoder::new();
+        let decoder = Decoder::new();
 
-        let co2 = decoder.find_life_support_rating(&vec![
+        let co2 = decoder.find_life_support_rating(&[
             "00100".to_string(),
             "11110".to_string(),
             "10110".to_string(),
@@ -2,6 +2,8 @@ use std::fs::{File};
 use std::io::{self, BufRead};
 use std::num::ParseIntError;
 use std::path::Path;
+use aoc_common::{Part, Puzzle};
+
 use decoder::Decoder;
 use thiserror::Error;
 
@@ -13,7 +15,7 @@ pub mod decoder;
 pub fn run(config: Config) -> Result<u64, ApplicationError> {
     let filename = config.filename;
 
-    let mut decoder = Decoder::new();
+    let decoder = Decoder::new();
 
     let lines: Vec<String> = read_lines(filename)?
         .map(|line| line.unwrap())
@@ -24,6 +26,28 @@ pub fn run(config: Config) -> Result<u64, ApplicationError> {
     Ok(rating)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let config = Config { filename: input.to_string_lossy().to_string() };
+
+        Ok(run(config)?.to_string())
+    }
+}
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where P: AsRef<Path>, {
     let file = File::open(filename)?;
@@ -27,7 +27,7 @@ impl PuzzleInput {
     }
 
     pub fn load_from_file(path: &String) -> Result<PuzzleInput, ApplicationError> {
-        let mut lines = read_lines(path)?.into_iter();
+        let mut lines = read_lines(path)?;
     
         let first_line = lines.next().unwrap().unwrap();
         let numbers: Vec<u64> = first_line.split(",").map(|n| n.parse::<u64>().unwrap()).collect();
@@ -37,7 +37,7 @@ impl PuzzleInput {
             let mut squares = vec![];
             for _ in 0..5 {
                 let line = lines.next().unwrap().unwrap();
-                let numbers: Vec<u64> = line.split(" ").filter(|s| s.len() > 0).map(|n| n.parse::<u64>().unwrap()).collect();
+                let numbers: Vec<u64> = line.split(" ").filter(|s| !s.is_empty()).map(|n| n.parse::<u64>().unwrap()).collect();
                 squares.extend(numbers);
             }
     
@@ -83,13 +83,13 @@ impl BingoBoard {
         BingoBoard { squares, squares_map }
     }
 
-    pub fn solve(&self, numbers: &Vec<u64>) -> Option<BingoSolution> {
+    pub fn solve(&self, numbers: &[u64]) -> Option<BingoSolution> {
         let mut marked = 0;
 
         for (turn, &number) in numbers.iter().enumerate() {
             if let Some(i) = self.squares_map.get(&number) {
                 let mask = 1 << i;
-                marked = marked | mask;
+                marked |= mask;
 
                 if BingoBoard::has_bingo(marked) {
                     return Some(BingoSolution::new(turn + 1, self.score(marked) * number));
@@ -141,7 +141,7 @@ mod tests {
 
     #[test]
     pub fn test() {
-        let mut board = BingoBoard::new(vec![
+        let _board = BingoBoard::new(vec![
             22, 13, 17, 11,  0,
              8,  2, 23,  4, 24,
             21,  9, 14, 16,  7,
@@ -1,9 +1,10 @@
-use std::fs::{File};
-use std::io::{self, BufRead};
+use std::io;
 use std::path::Path;
-use bingo::{BingoBoard, PuzzleInput};
+use bingo::PuzzleInput;
 use thiserror::Error;
 
+use aoc_common::{Part, Puzzle};
+
 use config::Config;
 
 pub mod bingo;
@@ -26,8 +27,39 @@ pub fn run(config: Config) -> Result<u64, ApplicationError> {
     Ok(0)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn run(&self, part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let filename = input.to_string_lossy().to_string();
+        let mut puzzle_input = PuzzleInput::load_from_file(&filename)?;
+
+        let mut solutions = puzzle_input.solve();
+        solutions.sort_by_key(|solution| solution.turns_required);
+
+        let solution = match part {
+            Part::One => solutions.first(),
+            Part::Two => solutions.last()
+        };
+
+        let solution = solution.ok_or(ApplicationError::NoWinningBoard)?;
+
+        Ok(solution.score.to_string())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
+    CouldntReadInput(#[from] io::Error),
+    #[error("no board ever wins")]
+    NoWinningBoard
 }
\ No newline at end of file
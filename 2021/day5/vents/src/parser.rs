@@ -10,7 +10,7 @@ use crate::puzzle::{Vector2, PuzzleInput, LineSegment};
 pub fn parse_puzzle_input(input: &str) -> IResult<&str, PuzzleInput> {
     map(
         separated_list1(line_ending, line_segment),
-        |line_segments| PuzzleInput::new(line_segments)
+        PuzzleInput::new
     )(input)
 }
 
@@ -1,7 +1,5 @@
 use std::collections::HashMap;
-use std::hash::Hash;
 
-use crate::parser::line_segment;
 
 #[derive(Debug, PartialEq)]
 pub struct PuzzleInput {
@@ -13,7 +11,7 @@ impl PuzzleInput {
         PuzzleInput { line_segments }
     }
 
-    pub fn line_segments(&self) -> std::slice::Iter<LineSegment> {
+    pub fn line_segments(&self) -> std::slice::Iter<'_, LineSegment> {
         self.line_segments.iter()
     }
 }
@@ -76,6 +74,12 @@ pub struct Solver {
     overlap_map: HashMap<(i64, i64), u64>
 }
 
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Solver {
     pub fn new() -> Solver {
         Solver { overlap_map: HashMap::new() }
@@ -1,9 +1,12 @@
+use std::path::Path;
 use thiserror::Error;
 
 pub mod config;
 pub mod parser;
 pub mod puzzle;
 
+use aoc_common::{Part, Puzzle};
+
 use crate::config::Config;
 use crate::parser::{parse_puzzle_input};
 use crate::puzzle::Solver;
@@ -18,7 +21,7 @@ pub fn run(config: Config) -> Result<u64, ApplicationError> {
     
     let mut solver = Solver::new();
     for line_segment in puzzle_input.line_segments() {
-        solver.ingest(&line_segment);
+        solver.ingest(line_segment);
     }
 
     let overlaps = solver.count_overlaps();
@@ -26,6 +29,28 @@ pub fn run(config: Config) -> Result<u64, ApplicationError> {
     Ok(overlaps)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let config = Config { filename: input.to_string_lossy().to_string() };
+
+        Ok(run(config)?.to_string())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
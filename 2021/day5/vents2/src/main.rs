@@ -1,7 +1,7 @@
 use std::env;
 use std::process;
 
-use vents::{run, config::Config};
+use vents2::{run, config::Config};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
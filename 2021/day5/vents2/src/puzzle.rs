@@ -1,6 +1,5 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::parser::line_segment;
 
 #[derive(Debug, PartialEq)]
 pub struct PuzzleInput {
@@ -12,7 +11,7 @@ impl PuzzleInput {
         PuzzleInput { line_segments }
     }
 
-    pub fn line_segments(&self) -> std::slice::Iter<LineSegment> {
+    pub fn line_segments(&self) -> std::slice::Iter<'_, LineSegment> {
         self.line_segments.iter()
     }
 }
@@ -75,14 +74,18 @@ pub struct Solver {
     overlap_map: HashMap<(i64, i64), u64>
 }
 
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Solver {
     pub fn new() -> Solver {
         Solver { overlap_map: HashMap::new() }
     }
     
     pub fn ingest(&mut self, line_segment: &LineSegment) {
-        println!("{:?}", line_segment);
-
         let vector = line_segment.vector();
         let length = vector.length().round() as u64;
         let unit = vector.unit();
@@ -96,8 +99,6 @@ impl Solver {
             let snapped_point = (point.x.round() as i64, point.y.round() as i64);
 
             if !points_traveled.contains(&snapped_point) {
-                println!("{:?}", snapped_point);
-
                 let count = self.overlap_map.get(&snapped_point).unwrap_or(&0) + 1;
                 self.overlap_map.insert(snapped_point, count);
                 points_traveled.insert(snapped_point);
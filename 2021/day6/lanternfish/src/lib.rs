@@ -1,7 +1,10 @@
 use std::collections::HashMap;
+use std::path::Path;
 
 use thiserror::Error;
 
+use aoc_common::{Part, Puzzle};
+
 use config::Config;
 
 pub mod config;
@@ -18,11 +21,39 @@ pub fn run(config: Config) -> Result<u64, ApplicationError> {
     Ok(count)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn run(&self, part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let generations = match part {
+            Part::One => 80,
+            Part::Two => 256
+        };
+        let config = Config { filename: input.to_string_lossy().to_string(), generations };
+
+        Ok(run(config)?.to_string())
+    }
+}
+
 pub struct FishMap {
     map: HashMap<u64, u64>,
     count: u64
 }
 
+impl Default for FishMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FishMap {
     pub fn new() -> FishMap {
         FishMap { map: HashMap::new(), count: 0 }
@@ -31,10 +62,10 @@ impl FishMap {
     pub fn insert(&mut self, fish: u64, count: u64) {
         let new_count = self.map.get(&fish).unwrap_or(&0) + count;
         self.map.insert(fish, new_count);
-        self.count = self.count + count;
+        self.count += count;
     }
 
-    pub fn iter(&self) -> std::collections::hash_map::Iter<u64, u64> {
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, u64, u64> {
         self.map.iter()
     }
 
@@ -51,7 +82,7 @@ fn solve(starting_fishes: Vec<u64>, generations: u64) -> u64 {
 
     let mut next_fishes = FishMap::new();
 
-    for generation in 0..generations {
+    for _ in 0..generations {
         for (&fish, &count) in fishes.iter() {
             if fish == 0 {  
                 next_fishes.insert(6, count);
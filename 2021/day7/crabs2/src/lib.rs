@@ -1,6 +1,8 @@
+use std::path::Path;
 use clap::Parser;
 use itertools::Itertools;
 use thiserror::Error;
+use aoc_common::{Part, Puzzle};
 
 #[derive(Parser)]
 pub struct CliOptions {
@@ -18,6 +20,28 @@ pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
     Ok(fuel_used)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
+}
+
 fn solve(crabs: Vec<u64>) -> (u64, u64) {
     let (first, last) = match crabs.iter().minmax() {
         itertools::MinMaxResult::NoElements => panic!("no crabs"),
@@ -32,11 +56,7 @@ fn solve(crabs: Vec<u64>) -> (u64, u64) {
         let mut total_fuel_used = 0;
 
         for &crab in crabs.iter() {
-            let difference = if crab > position {
-                crab - position
-            } else {
-                position - crab
-            };
+            let difference = crab.abs_diff(position);
             let fuel_used =  difference * (difference + 1) / 2;
 
             total_fuel_used += fuel_used;
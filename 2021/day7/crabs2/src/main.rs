@@ -1,9 +1,8 @@
-use std::env;
 use std::process;
 
 use clap::Parser;
 
-use crabs::{run, CliOptions};
+use crabs2::{run, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
@@ -5,6 +5,7 @@ use std::path::Path;
 
 use clap::Parser;
 use thiserror::Error;
+use aoc_common::{Part, Puzzle};
 
 #[derive(Parser)]
 pub struct CliOptions {
@@ -20,11 +21,11 @@ pub fn run(options: CliOptions) -> Result<u32, ApplicationError> {
     for line in lines {
         let split: Vec<String> = line?.split("|").take(2).map(|s| s.to_string()).collect();
         let digits: Vec<HashSet<char>> = split[0].split(" ")
-            .filter(|s| s.len() > 0)
+            .filter(|s| !s.is_empty())
             .map(|s| s.chars().collect::<HashSet<char>>())
             .collect();
         let output: Vec<HashSet<char>> = split[1].split(" ")
-            .filter(|s| s.len() > 0)
+            .filter(|s| !s.is_empty())
             .map(|s| s.chars().collect::<HashSet<char>>())
             .collect();
 
@@ -160,6 +161,28 @@ pub fn run(options: CliOptions) -> Result<u32, ApplicationError> {
     Ok(total)
 }
 
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::TWO
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
+}
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where P: AsRef<Path>, {
     let file = File::open(filename)?;
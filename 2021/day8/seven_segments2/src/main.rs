@@ -2,7 +2,7 @@ use std::process;
 
 use clap::Parser;
 
-use seven_segments::{run, CliOptions};
+use seven_segments2::{run, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
@@ -1,9 +1,12 @@
-use std::fs::{File};
-use std::io::{self, BufRead};
+use std::fs;
+use std::io;
 use std::path::Path;
 
 use clap::Parser;
-use map::MapBuilder;
+use aoc_common::{Part, Puzzle};
+use aoc_grid::ParseGridError;
+
+use map::Map;
 use thiserror::Error;
 
 pub mod map;
@@ -16,26 +19,38 @@ pub struct CliOptions {
 pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
     let filename = options.filename;
 
-    let mut map_builder = MapBuilder::new();
-
-    let lines = read_lines(filename)?;
-    for line in lines {
-        map_builder.add_line(&line?);
-    }
-    
-    let map = map_builder.to_map();
+    let contents = fs::read_to_string(filename)?;
+    let map = contents.parse::<Map>()?;
 
     Ok(map.calculate_risk_level())
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub struct Day;
+
+impl Puzzle for Day {
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        Part::ONE
+    }
+
+    fn run(&self, _part: Part, input: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let options = CliOptions { filename: input.to_path_buf() };
+
+        Ok(run(options)?.to_string())
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum ApplicationError {
     #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
+    CouldntReadInput(#[from] io::Error),
+    #[error("couldn't parse puzzle input: {0}")]
+    CouldntParseInput(#[from] ParseGridError)
 }
\ No newline at end of file
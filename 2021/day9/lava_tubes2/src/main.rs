@@ -2,7 +2,7 @@ use std::process;
 
 use clap::Parser;
 
-use lava_tubes::{run, CliOptions};
+use lava_tubes2::{run, CliOptions};
 
 fn main() {
     let cli_options = CliOptions::parse();
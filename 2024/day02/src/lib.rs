@@ -1,47 +1,46 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
 use std::str::FromStr;
 
-use clap::Parser;
 use thiserror::Error;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+use aoc_common::{Solution, SolutionError};
+
+pub struct Day;
 
-pub fn run(options: CliOptions) -> Result<i32, ApplicationError> {
-    let filename = options.filename;
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
 
-    let mut reports = vec![];
+    type Input = ReportList;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let lines = read_lines(filename)?;
-    for line in lines {
-        let line = line?;
-        
-        let report = line.parse::<Report>()?;
-        reports.push(report);
+    fn part1(report_list: &ReportList) -> Result<usize, SolutionError> {
+        let score = report_list.reports.iter().filter(|r| r.is_safe()).count();
+
+        Ok(score)
     }
 
-    match options.part {
-        1 => run_part1(reports),
-        2 => run_part2(reports),
-        _ => Err(ApplicationError::UnknownPart)
+    fn part2(report_list: &ReportList) -> Result<usize, SolutionError> {
+        let score = report_list.reports.iter().filter(|r| r.is_safe_with_dampener()).count();
+
+        Ok(score)
     }
 }
 
-fn run_part1(reports: Vec<Report>) -> Result<i32, ApplicationError> {
-    let score: i32 = reports.iter().filter(|r| r.is_safe()).count().try_into().unwrap();
-
-    Ok(score)
+pub struct ReportList {
+    reports: Vec<Report>
 }
 
-fn run_part2(reports: Vec<Report>) -> Result<i32, ApplicationError> {
-    let score: i32 = reports.iter().filter(|r| r.is_safe_with_dampener()).count().try_into().unwrap();
+impl FromStr for ReportList {
+    type Err = ParseReportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let reports = s.lines()
+            .map(|line| line.parse::<Report>())
+            .collect::<Result<Vec<Report>, _>>()?;
 
-    Ok(score)
+        Ok(ReportList { reports })
+    }
 }
 
 pub struct Report {
@@ -126,43 +125,27 @@ pub enum ParseReportError {
     ParseIntError(#[from] std::num::ParseIntError)
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("invalid report: {0}")]
-    ParseReportError(#[from] ParseReportError)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_report() {
-        assert_eq!(Report::new(vec![7, 6, 4, 2, 1]).is_safe(), true);
-        assert_eq!(Report::new(vec![1, 2, 7, 8, 9]).is_safe(), false);
-        assert_eq!(Report::new(vec![9, 7, 6, 2, 1]).is_safe(), false);
-        assert_eq!(Report::new(vec![1, 3, 2, 4, 5]).is_safe(), false);
-        assert_eq!(Report::new(vec![8, 6, 4, 4, 1]).is_safe(), false);
-        assert_eq!(Report::new(vec![1, 3, 6, 7, 9]).is_safe(), true);
+        assert!(Report::new(vec![7, 6, 4, 2, 1]).is_safe());
+        assert!(!Report::new(vec![1, 2, 7, 8, 9]).is_safe());
+        assert!(!Report::new(vec![9, 7, 6, 2, 1]).is_safe());
+        assert!(!Report::new(vec![1, 3, 2, 4, 5]).is_safe());
+        assert!(!Report::new(vec![8, 6, 4, 4, 1]).is_safe());
+        assert!(Report::new(vec![1, 3, 6, 7, 9]).is_safe());
     }
 
     #[test]
     fn test_report_with_dampener() {
-        assert_eq!(Report::new(vec![7, 6, 4, 2, 1]).is_safe_with_dampener(), true);
-        assert_eq!(Report::new(vec![1, 2, 7, 8, 9]).is_safe_with_dampener(), false);
-        assert_eq!(Report::new(vec![9, 7, 6, 2, 1]).is_safe_with_dampener(), false);
-        assert_eq!(Report::new(vec![1, 3, 2, 4, 5]).is_safe_with_dampener(), true);
-        assert_eq!(Report::new(vec![8, 6, 4, 4, 1]).is_safe_with_dampener(), true);
-        assert_eq!(Report::new(vec![1, 3, 6, 7, 9]).is_safe_with_dampener(), true);
+        assert!(Report::new(vec![7, 6, 4, 2, 1]).is_safe_with_dampener());
+        assert!(!Report::new(vec![1, 2, 7, 8, 9]).is_safe_with_dampener());
+        assert!(!Report::new(vec![9, 7, 6, 2, 1]).is_safe_with_dampener());
+        assert!(Report::new(vec![1, 3, 2, 4, 5]).is_safe_with_dampener());
+        assert!(Report::new(vec![8, 6, 4, 4, 1]).is_safe_with_dampener());
+        assert!(Report::new(vec![1, 3, 6, 7, 9]).is_safe_with_dampener());
     }
-}
\ No newline at end of file
+}
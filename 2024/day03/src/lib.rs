@@ -1,34 +1,28 @@
+use std::convert::Infallible;
 use std::str::FromStr;
-use clap::Parser;
+
 use once_cell::sync::Lazy;
 use regex::Regex;
-use thiserror::Error;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+use aoc_common::{Solution, SolutionError};
 
-pub fn run(options: CliOptions) -> Result<i32, ApplicationError> {
-    let filename = options.filename;
+pub struct Day;
 
-    let contents = std::fs::read_to_string(filename)?;
-    let program: ShopProgram = contents.parse().unwrap();
-    
-    match options.part {
-        1 => run_part1(program),
-        2 => run_part2(program),
-        _ => Err(ApplicationError::UnknownPart)
-    }
-}
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
 
-fn run_part1(program: ShopProgram) -> Result<i32, ApplicationError> {
-    Ok(program.evaluate(ShopLanguageVersion::One))
-}
+    type Input = ShopProgram;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn part1(program: &ShopProgram) -> Result<i32, SolutionError> {
+        Ok(program.evaluate(ShopLanguageVersion::One))
+    }
 
-fn run_part2(program: ShopProgram) -> Result<i32, ApplicationError> {
-    Ok(program.evaluate(ShopLanguageVersion::Two))
+    fn part2(program: &ShopProgram) -> Result<i32, SolutionError> {
+        Ok(program.evaluate(ShopLanguageVersion::Two))
+    }
 }
 
 #[derive(Debug)]
@@ -52,10 +46,9 @@ impl ShopProgram {
         let mut accum = 0;
         
         for instruction in self.instructions.iter() {
-            match instruction {
-                ShopProgramInstruction::Mul(a, b) => accum += a * b,
-                _ => ()
-            };
+            if let ShopProgramInstruction::Mul(a, b) = instruction {
+                accum += a * b;
+            }
         }
 
         accum
@@ -79,7 +72,7 @@ impl ShopProgram {
 }
 
 impl FromStr for ShopProgram {
-    type Err = ();
+    type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         static LANGUAGE_RE: Lazy<Regex> = Lazy::new(|| {
@@ -124,14 +117,6 @@ pub enum ShopProgramInstruction {
     Dont
 }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] std::io::Error)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +132,4 @@ mod tests {
         let program: ShopProgram = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))".parse().unwrap();
         assert_eq!(program.evaluate(ShopLanguageVersion::Two), 48);
     }
-}
\ No newline at end of file
+}
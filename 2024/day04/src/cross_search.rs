@@ -3,7 +3,7 @@ pub struct CrossSearch {
     row_size: usize
 }
 
-impl<'a> CrossSearch {
+impl CrossSearch {
     pub fn new(input: String, row_size: usize) -> CrossSearch {
         CrossSearch { input, row_size }
     }
@@ -13,9 +13,9 @@ impl<'a> CrossSearch {
 
         let input = self.input.as_bytes();
 
-        const M: u8 = 'M' as u8;
-        const A: u8 = 'A' as u8;
-        const S: u8 = 'S' as u8;
+        const M: u8 = b'M';
+        const A: u8 = b'A';
+        const S: u8 = b'S';
 
         let starting_index = self.row_size * 2 + 2;
         for i in starting_index..input.len() {
@@ -26,13 +26,11 @@ impl<'a> CrossSearch {
                 let top_left = input[i - 2 - self.row_size * 2];
                 let top_right = input[i - self.row_size * 2];
                 
-                let is_match = match (top_left, top_right, center, bottom_left, bottom_right) {
+                let is_match = matches!((top_left, top_right, center, bottom_left, bottom_right),
                       (M, M, A, S, S)
                     | (M, S, A, M, S)
                     | (S, S, A, M, M)
-                    | (S, M, A, S, M) => true,
-                    _ => false
-                };
+                    | (S, M, A, S, M));
 
                 if is_match {
                     count += 1;
@@ -1,62 +1,51 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
 use cross_search::CrossSearch;
-use thiserror::Error;
 use crate::word_search::WordSearch;
 
-mod word_search;
-mod cross_search;
+pub mod word_search;
+pub mod cross_search;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+pub struct Day;
 
-pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
-    let filename = options.filename;
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 4;
 
-    let lines = read_lines(filename)?;
-    let mut lines_vec = vec![];    
-    
-    for line in lines {
-        lines_vec.push(line?);
-    }
+    type Input = LetterGrid;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let row_size = lines_vec[0].len();
-    let input = lines_vec.join("");
+    fn part1(letter_grid: &LetterGrid) -> Result<usize, SolutionError> {
+        let word_search = WordSearch::new(letter_grid.letters.clone(), letter_grid.row_size);
+
+        Ok(word_search.search())
+    }
 
-    let word_search = WordSearch::new(input.clone(), row_size);
-    let cross_search = CrossSearch::new(input, row_size);
+    fn part2(letter_grid: &LetterGrid) -> Result<usize, SolutionError> {
+        let cross_search = CrossSearch::new(letter_grid.letters.clone(), letter_grid.row_size);
 
-    match options.part {
-        1 => run_part1(word_search),
-        2 => run_part2(cross_search),
-        _ => Err(ApplicationError::UnknownPart)
+        Ok(cross_search.search())
     }
 }
 
-fn run_part1(word_search: WordSearch) -> Result<usize, ApplicationError> {
-    Ok(word_search.search())
+pub struct LetterGrid {
+    letters: String,
+    row_size: usize
 }
 
-fn run_part2(cross_search: CrossSearch) -> Result<usize, ApplicationError> {
-    Ok(cross_search.search())
-}
+impl FromStr for LetterGrid {
+    type Err = Infallible;
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().collect::<Vec<&str>>();
+
+        let row_size = lines.first().map(|line| line.len()).unwrap_or(0);
+        let letters = lines.join("");
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
-}
\ No newline at end of file
+        Ok(LetterGrid { letters, row_size })
+    }
+}
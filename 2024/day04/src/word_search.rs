@@ -3,9 +3,9 @@ pub struct WordSearch {
     row_size: usize
 }
 
-impl<'a> WordSearch {
+impl WordSearch {
     pub fn new(input: String, row_size: usize) -> WordSearch {
-        let padding = std::iter::repeat(".").take(row_size * 4).collect::<String>();
+        let padding = std::iter::repeat_n(".", row_size * 4).collect::<String>();
         let input = padding.clone() + &input + &padding;
         
         WordSearch { input, row_size }
@@ -18,10 +18,10 @@ impl<'a> WordSearch {
 
         let input = self.input.as_bytes();
 
-        const X: u8 = 'X' as u8;
-        const M: u8 = 'M' as u8;
-        const A: u8 = 'A' as u8;
-        const S: u8 = 'S' as u8;
+        const X: u8 = b'X';
+        const M: u8 = b'M';
+        const A: u8 = b'A';
+        const S: u8 = b'S';
 
         let starting_index = self.row_size * 4;
         for i in starting_index..input.len() {
@@ -56,29 +56,25 @@ impl<'a> WordSearch {
             );
             
             if i % self.row_size > 2 {
-                let is_match = match (three_zero, three_one, three_two, three_three) {
+                let is_match = matches!((three_zero, three_one, three_two, three_three),
                     (X, M, A, S)
-                  | (S, A, M, X) => true,
-                  _ => false
-                };
+                  | (S, A, M, X));
     
                 if is_match {
                     row_match_count += 1;
                 }   
             }
 
-            let is_match = match (zero_three, one_three, two_three, three_three) {
+            let is_match = matches!((zero_three, one_three, two_three, three_three),
                   (X, M, A, S)
-                | (S, A, M, X) => true,
-                _ => false
-            };
+                | (S, A, M, X));
 
             if is_match {
                 col_match_count += 1;
             }
 
             if i % self.row_size > 2 {
-                let is_match = match grid {
+                let is_match = matches!(grid,
                       ((X, _, _, _),
                        (_, M, _, _),
                        (_, _, A, _),
@@ -86,15 +82,13 @@ impl<'a> WordSearch {
                     | ((S, _, _, _),
                        (_, A, _, _),
                        (_, _, M, _),
-                       (_, _, _, X)) => true,
-                    _ => false
-                };
+                       (_, _, _, X)));
 
                 if is_match {
                     diag_match_count += 1;
                 }
 
-                let is_match = match grid {
+                let is_match = matches!(grid,
                     ((_, _, _, S),
                      (_, _, A, _),
                      (_, M, _, _),
@@ -102,9 +96,7 @@ impl<'a> WordSearch {
                   | ((_, _, _, X),
                      (_, _, M, _),
                      (_, A, _, _),
-                     (S, _, _, _)) => true,
-                  _ => false
-              };
+                     (S, _, _, _)));
               
               if is_match {
                   diag_match_count += 1;
@@ -1,84 +1,27 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use print_job::{OrderRule, OrderRuleSet, PageList, PrintJob};
-use thiserror::Error;
+use print_job::PrintJob;
 
-mod print_job;
+pub mod print_job;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
-
-enum PrintJobReadState {
-    ExpectingOrderRule,
-    ExpectingPageNumbers
-}
+pub struct Day;
 
-pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
-    let filename = options.filename;
-   
-    let mut state = PrintJobReadState::ExpectingOrderRule;
-    let mut order_rules = vec![];
-    let mut page_number_lists = vec![];
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 5;
 
-    let lines = read_lines(filename)?;
-    for line in lines {
-        let line = line?;
+    type Input = PrintJob;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        state = match state {
-            PrintJobReadState::ExpectingOrderRule => {
-                if line.is_empty() {
-                    PrintJobReadState::ExpectingPageNumbers
-                } else {
-                    let order_rule: OrderRule = line.parse().unwrap();
-                    order_rules.push(order_rule);
-                    
-                    state
-                }
-            },
-            PrintJobReadState::ExpectingPageNumbers => {
-                let page_number_list: PageList = line.parse().unwrap();
-                page_number_lists.push(page_number_list);
-
-                state
-            },
-        }
+    fn part1(print_job: &PrintJob) -> Result<usize, SolutionError> {
+        Ok(print_job.calculate_good_score())
     }
 
-    let rule_set = OrderRuleSet::new(order_rules);
-    let mut print_job = PrintJob::new(rule_set, page_number_lists);
+    // Really not happy that this requires a mutable reference and can only be done once
+    fn part2(print_job: &PrintJob) -> Result<usize, SolutionError> {
+        let mut print_job = print_job.clone();
 
-    match options.part {
-        1 => run_part1(&print_job),
-        2 => run_part2(&mut print_job),
-        _ => Err(ApplicationError::UnknownPart)
+        Ok(print_job.calculate_bad_score())
     }
 }
-
-fn run_part1(print_job: &PrintJob) -> Result<usize, ApplicationError> {
-    Ok(print_job.calculate_good_score())
-}
-
-// Really not happy that this requires a mutable reference and can only be done once
-fn run_part2(print_job: &mut PrintJob) -> Result<usize, ApplicationError> {
-    Ok(print_job.calculate_bad_score())
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
-}
\ No newline at end of file
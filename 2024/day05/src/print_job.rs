@@ -1,5 +1,8 @@
 use std::str::FromStr;
 
+use thiserror::Error;
+
+#[derive(Clone)]
 pub struct PrintJob {
     rule_set: OrderRuleSet,
     page_lists: Vec<PageList>
@@ -36,7 +39,43 @@ impl PrintJob {
     }
 }
 
-#[derive(Debug)]
+impl FromStr for PrintJob {
+    type Err = ParsePrintJobError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        let mut order_rules = vec![];
+        for line in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+
+            let order_rule: OrderRule = line.parse()
+                .map_err(|_| ParsePrintJobError::InvalidOrderRule(line.to_string()))?;
+            order_rules.push(order_rule);
+        }
+
+        let mut page_lists = vec![];
+        for line in lines {
+            let page_list: PageList = line.parse()
+                .map_err(|_| ParsePrintJobError::InvalidPageList(line.to_string()))?;
+            page_lists.push(page_list);
+        }
+
+        Ok(PrintJob::new(OrderRuleSet::new(order_rules), page_lists))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParsePrintJobError {
+    #[error("invalid order rule: {0}")]
+    InvalidOrderRule(String),
+    #[error("invalid page list: {0}")]
+    InvalidPageList(String)
+}
+
+#[derive(Debug, Clone)]
 pub struct OrderRuleSet {
     rules: Vec<OrderRule>
 }
@@ -47,7 +86,7 @@ impl OrderRuleSet {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OrderRule {
     left: usize,
     right: usize
@@ -74,6 +113,7 @@ impl FromStr for OrderRule {
     }
 }
 
+#[derive(Clone)]
 pub struct PageList {
     page_numbers: Vec<usize>
 }
@@ -120,13 +160,12 @@ impl PageList {
         let left_position = self.get_page_position(rule.left);
         let right_position = self.get_page_position(rule.right);
 
-        if let (Some(left_position), Some(right_position)) = (left_position, right_position) {
-            if left_position > right_position {
-                let slice = &mut self.page_numbers[right_position..=left_position];
-                slice.rotate_right(1);
+        if let (Some(left_position), Some(right_position)) = (left_position, right_position)
+            && left_position > right_position {
+            let slice = &mut self.page_numbers[right_position..=left_position];
+            slice.rotate_right(1);
 
-                return true;
-            }
+            return true;
         }
         
         false
@@ -162,7 +201,7 @@ mod tests {
         let page_list = PageList::new(vec![75, 47, 61, 53, 29]);
         let rule = OrderRule::new(47, 53);
 
-        assert_eq!(page_list.check_rule(&rule), true);
+        assert!(page_list.check_rule(&rule));
     }
 
     #[test]
@@ -170,7 +209,7 @@ mod tests {
         let page_list = PageList::new(vec![75, 47, 61, 53, 29]);
         let rule = OrderRule::new(61, 75);
 
-        assert_eq!(page_list.check_rule(&rule), false);
+        assert!(!page_list.check_rule(&rule));
     }
 
     #[test]
@@ -178,7 +217,7 @@ mod tests {
         let page_list = PageList::new(vec![75, 47, 61, 53, 29]);
         let rule = OrderRule::new(47, 99);
 
-        assert_eq!(page_list.check_rule(&rule), true);
+        assert!(page_list.check_rule(&rule));
     }
 
     #[test]
@@ -186,7 +225,7 @@ mod tests {
         let page_list = PageList::new(vec![75, 47, 61, 53, 29]);
         let rule = OrderRule::new(99, 53);
 
-        assert_eq!(page_list.check_rule(&rule), true);
+        assert!(page_list.check_rule(&rule));
     }
 
     #[test]
@@ -1,9 +1,8 @@
-use std::collections::{hash_set, HashSet};
-use std::hash::Hash;
+use std::collections::{hash_set, HashMap, HashSet};
 use std::panic;
 use std::str::FromStr;
 
-use thiserror::Error;
+use aoc_grid::{Direction, Grid, ParseGridError, Point};
 
 #[derive(Clone, Copy)]
 pub enum LabMapTile {
@@ -13,133 +12,56 @@ pub enum LabMapTile {
     MapBoundary
 }
 
+impl TryFrom<char> for LabMapTile {
+    type Error = ParseGridError;
+
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            '.' => Ok(LabMapTile::Empty),
+            '#' => Ok(LabMapTile::Obstacle),
+            '^' => Ok(LabMapTile::GuardStartingPosition),
+            _ => Err(ParseGridError::UnexpectedCharacter(char))
+        }
+    }
+}
+
 pub struct LabMap {
-    rows: Vec<LabMapRow>
+    tiles: Grid<LabMapTile>
 }
 
 impl LabMap {
-    pub fn new(rows: Vec<LabMapRow>) -> LabMap {
-        LabMap { rows }
+    pub fn new(tiles: Grid<LabMapTile>) -> LabMap {
+        LabMap { tiles }
     }
 
     pub fn guard_starting_position(&self) -> Option<(i32, i32)> {
-        for (y, row) in self.rows.iter().enumerate() {
-            for (x, tile) in row.tiles.iter().enumerate() {
-                if let LabMapTile::GuardStartingPosition = tile {
-                    let x: i32 = x.try_into().unwrap();
-                    let y: i32 = y.try_into().unwrap();
-
-                    return Some((x, y));
-                }
-            }
-        }
-
-        None
+        self.tiles.position(|tile| matches!(tile, LabMapTile::GuardStartingPosition))
+            .map(|Point { x, y }| (x, y))
     }
 
     pub fn tile_at(&self, x: i32, y: i32) -> LabMapTile {
-        if x < 0 || y < 0 {
-            LabMapTile::MapBoundary
-        } else {
-            let x: usize = x.try_into().unwrap();
-            let y: usize = y.try_into().unwrap();
-
-            if let Some(row) = self.rows.get(y) {
-                if let Some(tile) = row.tiles.get(x) {
-                    *tile
-                } else {
-                    LabMapTile::MapBoundary
-                }
-            } else {
-                LabMapTile::MapBoundary
-            }
-        }
+        self.tiles.get(Point::new(x, y)).copied().unwrap_or(LabMapTile::MapBoundary)
     }
 
     pub fn width(&self) -> usize {
-        self.rows[0].tiles.len()
+        self.tiles.width()
     }
 
     pub fn height(&self) -> usize {
-        self.rows.len()
-    }
-
-    pub fn iter(&self) -> LabMapIterator<'_> {
-        let next = Some((0, 0, self.tile_at(0, 0)));
-
-        LabMapIterator { map: self, current: None, next }
+        self.tiles.height()
     }
-}
-
-pub struct LabMapIterator<'a> {
-    map: &'a LabMap,
-    current: Option<(i32, i32, LabMapTile)>,
-    next: Option<(i32, i32, LabMapTile)>
-}
-
-impl<'a> Iterator for LabMapIterator<'a> {
-    type Item = (i32, i32, LabMapTile);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.current = self.next;
-
-        if let Some((x, y, _)) = self.current {
-            let mut x = x + 1;
-            let mut y = y;
-            
-            if x >= self.map.width().try_into().unwrap() {
-                x = 0;
-                y += 1;
-            }
-    
-            if y >= self.map.height().try_into().unwrap() {
-                self.next = None;
-            } else {
-                let tile = self.map.tile_at(x, y);
 
-                self.next = Some((x, y, tile));
-            }
-        }
-
-        self.current
+    pub fn iter(&self) -> impl Iterator<Item = (i32, i32, LabMapTile)> {
+        self.tiles.iter().map(|(Point { x, y }, tile)| (x, y, *tile))
     }
 }
 
-pub struct LabMapRow {
-    tiles: Vec<LabMapTile>
-}
+impl FromStr for LabMap {
+    type Err = ParseGridError;
 
-impl LabMapRow {
-    pub fn new(tiles: Vec<LabMapTile>) -> LabMapRow {
-        LabMapRow { tiles }
-    }
-}
-
-impl FromStr for LabMapRow {
-    type Err = LabMapRowParseError;
-    
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tiles = vec![];
-
-        for char in s.chars() {
-            let tile = match char {
-                '.' => LabMapTile::Empty,
-                '#' => LabMapTile::Obstacle,
-                '^' => LabMapTile::GuardStartingPosition,
-                _ => return Err(LabMapRowParseError::UnexpectedCharacter(char))
-            };
-            
-            tiles.push(tile);
-        }
-        
-        Ok(LabMapRow::new(tiles))
-    }    
-}
-
-#[derive(Debug, Error)]
-pub enum LabMapRowParseError {
-    #[error("unexpected character '{0}'")]
-    UnexpectedCharacter(char)
+        Ok(LabMap::new(s.parse()?))
+    }
 }
 
 pub struct GuardSimulation<'a> {
@@ -149,8 +71,9 @@ pub struct GuardSimulation<'a> {
     y: i32,
     heading: Direction,
     locations_visited: HashSet<(i32, i32)>,
-    locations_and_headings_visited: HashSet<(i32, i32, Direction)>,
-    loop_detected: bool
+    locations_and_headings_visited: HashMap<(i32, i32, Direction), usize>,
+    trajectory: Vec<((i32, i32), Direction)>,
+    cycle_start: Option<usize>
 }
 
 impl<'a> GuardSimulation<'a> {
@@ -161,25 +84,33 @@ impl<'a> GuardSimulation<'a> {
             let mut locations_visited = HashSet::new();
             locations_visited.insert((x, y));
 
-            let mut locations_and_headings_visited = HashSet::new();
-            locations_and_headings_visited.insert((x, y, heading));
+            let mut locations_and_headings_visited = HashMap::new();
+            locations_and_headings_visited.insert((x, y, heading), 0);
             
-            GuardSimulation { map, extra_obstacle, x, y, heading, locations_visited, locations_and_headings_visited, loop_detected: false }
+            GuardSimulation {
+                map,
+                extra_obstacle,
+                x,
+                y,
+                heading,
+                locations_visited,
+                locations_and_headings_visited,
+                trajectory: vec![((x, y), heading)],
+                cycle_start: None
+            }
         } else {
             panic!("no guard starting position");
         }
     }
 
+    /// Moves or turns the guard once. Returns false once the guard has left the map or
+    /// started repeating itself.
     pub fn step(&mut self) -> bool {
-        let (x_delta, y_delta) = match self.heading {
-            Direction::North => (0, -1),
-            Direction::East => (1, 0),
-            Direction::South => (0, 1),
-            Direction::West => (-1, 0)
-        };
+        if self.cycle_start.is_some() {
+            return false;
+        }
 
-        let next_x = self.x + x_delta;
-        let next_y = self.y + y_delta;
+        let Point { x: next_x, y: next_y } = Point::new(self.x, self.y).step(self.heading);
 
         let next_tile = self.tile_at(next_x, next_y);
 
@@ -189,40 +120,53 @@ impl<'a> GuardSimulation<'a> {
                 self.y = next_y;
 
                 self.locations_visited.insert((self.x, self.y));
+                self.record_state();
                 
-                if !self.locations_and_headings_visited.insert((self.x, self.y, self.heading)) {
-                    self.loop_detected = true;
-                }
-                
-                return true;
+                true
             },
             LabMapTile::Obstacle => {
-                self.heading = self.heading.rotate_right();
+                self.heading = self.heading.rotate_clockwise();
 
-                if !self.locations_and_headings_visited.insert((self.x, self.y, self.heading)) {
-                    self.loop_detected = true;
-                }
+                self.record_state();
                 
-                return true;
+                true
             },
             LabMapTile::MapBoundary => {
-                return false;
+                false
             }
         }
     }
 
+    fn record_state(&mut self) {
+        let index = self.trajectory.len();
+        self.trajectory.push(((self.x, self.y), self.heading));
+
+        if let Some(&first) = self.locations_and_headings_visited.get(&(self.x, self.y, self.heading)) {
+            self.cycle_start = Some(first);
+        } else {
+            self.locations_and_headings_visited.insert((self.x, self.y, self.heading), index);
+        }
+    }
+
     fn tile_at(&self, x: i32, y: i32) -> LabMapTile {
-        if let Some((obstacle_x, obstacle_y)) = self.extra_obstacle {
-            if obstacle_x == x && obstacle_y == y {
-                return LabMapTile::Obstacle;
-            }
+        if let Some((obstacle_x, obstacle_y)) = self.extra_obstacle
+            && obstacle_x == x && obstacle_y == y {
+            return LabMapTile::Obstacle;
         }
 
         self.map.tile_at(x, y)
     }
 
+    pub fn map(&self) -> &LabMap {
+        self.map
+    }
+
+    pub fn extra_obstacle(&self) -> Option<(i32, i32)> {
+        self.extra_obstacle
+    }
+
     pub fn loop_detected(&self) -> bool {
-        self.loop_detected
+        self.cycle_start.is_some()
     }
 
     pub fn visited_count(&self) -> usize {
@@ -232,26 +176,25 @@ impl<'a> GuardSimulation<'a> {
     pub fn locations_visited(&self) -> hash_set::Iter<'_, (i32, i32)> {
         self.locations_visited.iter()
     }
-}
 
-#[derive(Clone, Copy, PartialEq, Hash)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West
-}
+    /// Every position and heading the guard has had, in order, starting where it began.
+    /// A turn adds an entry with the same position and the new heading.
+    pub fn trajectory(&self) -> &[((i32, i32), Direction)] {
+        &self.trajectory
+    }
 
-impl Eq for Direction {
-}
+    /// The positions where the guard turned, with the heading it turned to. Turning twice
+    /// in a corner gives two entries for the same position.
+    pub fn turn_points(&self) -> Vec<((i32, i32), Direction)> {
+        self.trajectory.windows(2)
+            .filter(|pair| pair[0].1 != pair[1].1)
+            .map(|pair| pair[1])
+            .collect()
+    }
 
-impl Direction {
-    pub fn rotate_right(&self) -> Self {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North
-        }
+    /// The part of the trajectory the guard keeps repeating, once a loop has been detected.
+    /// The repeated state itself is only included once.
+    pub fn cycle(&self) -> Option<&[((i32, i32), Direction)]> {
+        self.cycle_start.map(|start| &self.trajectory[start..self.trajectory.len() - 1])
     }
-}
\ No newline at end of file
+}
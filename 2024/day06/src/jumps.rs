@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use aoc_grid::Direction;
+use rayon::prelude::*;
+
+use crate::guard::{LabMap, LabMapTile};
+
+/// For every tile and heading, the tile where the guard stops in front of the next obstacle,
+/// or `None` if it walks off the map first.
+pub struct JumpTable {
+    width: usize,
+    stops: Vec<Option<(i32, i32)>>
+}
+
+impl JumpTable {
+    pub fn new(map: &LabMap) -> JumpTable {
+        let (width, height) = (map.width(), map.height());
+        let mut stops = vec![None; width * height * 4];
+
+        for heading in Direction::ALL {
+            let offset = heading.offset();
+
+            // Visit tiles so the one ahead of each tile is always filled in first
+            let xs: Vec<i32> = if offset.x > 0 { (0..width as i32).rev().collect() } else { (0..width as i32).collect() };
+            let ys: Vec<i32> = if offset.y > 0 { (0..height as i32).rev().collect() } else { (0..height as i32).collect() };
+
+            for &y in ys.iter() {
+                for &x in xs.iter() {
+                    let (next_x, next_y) = (x + offset.x, y + offset.y);
+
+                    stops[index(width, (x, y), heading)] = match map.tile_at(next_x, next_y) {
+                        LabMapTile::MapBoundary => None,
+                        LabMapTile::Obstacle => Some((x, y)),
+                        LabMapTile::Empty | LabMapTile::GuardStartingPosition => stops[index(width, (next_x, next_y), heading)]
+                    };
+                }
+            }
+        }
+
+        JumpTable { width, stops }
+    }
+
+    /// Where the guard stops walking from `position` towards `heading`. An extra obstacle only
+    /// changes the stops in its own row and column, so rather than patching a copy of the
+    /// table, this cuts the jump short when the obstacle is between the start and the stop.
+    pub fn jump(&self, position: (i32, i32), heading: Direction, extra_obstacle: Option<(i32, i32)>) -> Option<(i32, i32)> {
+        let stop = self.stops[index(self.width, position, heading)];
+
+        let Some((obstacle_x, obstacle_y)) = extra_obstacle else {
+            return stop;
+        };
+
+        let offset = heading.offset();
+        let (x, y) = position;
+
+        let distance_to_obstacle = match offset.x {
+            0 if obstacle_x == x => (obstacle_y - y) * offset.y,
+            0 => return stop,
+            _ if obstacle_y == y => (obstacle_x - x) * offset.x,
+            _ => return stop
+        };
+
+        let distance_to_stop = stop.map_or(i32::MAX, |(stop_x, stop_y)| (stop_x - x).abs() + (stop_y - y).abs());
+
+        if distance_to_obstacle > 0 && distance_to_obstacle <= distance_to_stop {
+            let steps = distance_to_obstacle - 1;
+            Some((x + offset.x * steps, y + offset.y * steps))
+        } else {
+            stop
+        }
+    }
+
+    /// Whether a guard at `position` facing `heading` ends up walking in circles, moving from
+    /// turn point to turn point.
+    pub fn causes_loop(&self, position: (i32, i32), heading: Direction, extra_obstacle: Option<(i32, i32)>) -> bool {
+        let mut turns_seen = HashSet::new();
+        let (mut position, mut heading) = (position, heading);
+
+        while let Some(stop) = self.jump(position, heading, extra_obstacle) {
+            if !turns_seen.insert((stop, heading)) {
+                return true;
+            }
+
+            position = stop;
+            heading = heading.rotate_clockwise();
+        }
+
+        false
+    }
+}
+
+fn index(width: usize, (x, y): (i32, i32), heading: Direction) -> usize {
+    let heading = match heading {
+        Direction::North => 0,
+        Direction::East => 1,
+        Direction::South => 2,
+        Direction::West => 3
+    };
+
+    (y as usize * width + x as usize) * 4 + heading
+}
+
+impl LabMap {
+    /// Every empty tile where one extra obstacle would trap the guard in a loop.
+    ///
+    /// Only tiles on the guard's original path can change anything, and the guard follows
+    /// that path unchanged until it first reaches the new obstacle. Each candidate is checked
+    /// from the state just before that point, in parallel.
+    pub fn loop_obstacles(&self) -> HashSet<(i32, i32)> {
+        let Some(start) = self.guard_starting_position() else {
+            return HashSet::new();
+        };
+
+        let table = JumpTable::new(self);
+
+        let mut candidates = vec![];
+        let mut tiles_seen = HashSet::from([start]);
+        let mut states_seen = HashSet::new();
+        let (mut position, mut heading) = (start, Direction::North);
+
+        // A guard that already walks in circles never reaches anything past its loop
+        while states_seen.insert((position, heading)) {
+            let offset = heading.offset();
+            let next = (position.0 + offset.x, position.1 + offset.y);
+
+            match self.tile_at(next.0, next.1) {
+                LabMapTile::MapBoundary => break,
+                LabMapTile::Obstacle => heading = heading.rotate_clockwise(),
+                LabMapTile::Empty | LabMapTile::GuardStartingPosition => {
+                    if tiles_seen.insert(next) {
+                        candidates.push((next, position, heading));
+                    }
+
+                    position = next;
+                }
+            }
+        }
+
+        candidates.par_iter()
+            .filter(|(obstacle, position, heading)| table.causes_loop(*position, *heading, Some(*obstacle)))
+            .map(|(obstacle, _, _)| *obstacle)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::guard::GuardSimulation;
+
+    use super::*;
+
+    fn brute_force(map: &LabMap) -> HashSet<(i32, i32)> {
+        map.iter()
+            .filter(|(_, _, tile)| matches!(tile, LabMapTile::Empty))
+            .map(|(x, y, _)| (x, y))
+            .filter(|&obstacle| {
+                let mut simulation = GuardSimulation::new(map, Some(obstacle));
+                while simulation.step() && !simulation.loop_detected() { }
+
+                simulation.loop_detected()
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn matches_brute_force() {
+        let maps = [
+            "....#.....\n.........#\n..........\n..#.......\n.......#..\n..........\n.#..^.....\n........#.\n#.........\n......#...",
+            // Turning twice in a corner, and an obstacle right in front of the start
+            ".#...\n..#..\n.....\n.^...\n.....",
+            "..#..\n.#...\n.^...\n....#\n....."
+        ];
+
+        for map in maps {
+            let map: LabMap = map.parse().unwrap();
+            assert_eq!(map.loop_obstacles(), brute_force(&map));
+        }
+
+        let example: LabMap = maps[0].parse().unwrap();
+        assert_eq!(example.loop_obstacles(), HashSet::from([(3, 6), (6, 7), (7, 7), (1, 8), (3, 8), (7, 9)]));
+    }
+}
@@ -1,94 +1,143 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::fs;
+use std::path::PathBuf;
 
 use clap::Parser;
-use guard::{GuardSimulation, LabMap, LabMapRow, LabMapTile};
-use thiserror::Error;
+use clap::Subcommand;
+use aoc_common::{solve, ApplicationError, Part, Solution, SolutionError};
+use aoc_grid::Direction;
+use aoc_grid::frames::FrameFormat;
 
-mod guard;
+use guard::{GuardSimulation, LabMap};
+
+pub mod guard;
+pub mod jumps;
+pub mod trace;
 
 #[derive(Parser)]
 pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
+    #[command(subcommand)]
+    command: Commands
 }
 
-pub fn run(options: CliOptions) -> Result<usize, ApplicationError> {
-    let filename = options.filename;
+#[derive(Subcommand)]
+enum Commands {
+    Part1 {
+        input: PathBuf
+    },
+    Part2 {
+        input: PathBuf
+    },
+    /// Draw the guard's path over the map and list where it turned
+    Trace {
+        input: PathBuf,
+
+        /// Put an extra obstacle here, given as x,y
+        #[arg(long, value_parser = parse_position)]
+        obstacle: Option<(i32, i32)>,
+
+        /// Show the repeating part of a loop in reverse video
+        #[arg(long)]
+        highlight: bool,
+
+        /// Record a frame per turn to this file or directory
+        #[arg(long)]
+        record: Option<PathBuf>,
+
+        /// text, pbm, pgm or cast
+        #[arg(long, default_value = "cast")]
+        record_format: FrameFormat
+    }
+}
 
-    let mut rows = vec![];
+pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
+    match options.command {
+        Commands::Part1 { input } => solve::<Day>(Part::One, &fs::read_to_string(input)?),
+        Commands::Part2 { input } => solve::<Day>(Part::Two, &fs::read_to_string(input)?),
+        Commands::Trace { input, obstacle, highlight, record, record_format } => {
+            trace(input, obstacle, highlight, record.map(|path| (path, record_format)))
+        }
+    }
+}
 
-    let lines = read_lines(filename)?;
-    for line in lines {
-        let line = line?;
-        let map_row: LabMapRow = line.parse()?;
-        rows.push(map_row);
+fn parse_position(s: &str) -> Result<(i32, i32), String> {
+    let (x, y) = s.split_once(',').ok_or_else(|| format!("expected x,y, got '{}'", s))?;
+    let parse = |value: &str| value.trim().parse::<i32>().map_err(|err| err.to_string());
+
+    Ok((parse(x)?, parse(y)?))
+}
+
+fn trace(input: PathBuf, obstacle: Option<(i32, i32)>, highlight: bool, record: Option<(PathBuf, FrameFormat)>) -> Result<String, ApplicationError> {
+    let map = fs::read_to_string(input)?
+        .parse::<LabMap>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
+
+    if map.guard_starting_position().is_none() {
+        return Err(ApplicationError::CouldntParseInput("no guard starting position".to_string()));
     }
 
-    let map = LabMap::new(rows);
+    let mut simulation = GuardSimulation::new(&map, obstacle);
+    while simulation.step() { }
+
+    if let Some((path, format)) = record {
+        let mut sink = format.create_sink(path)?;
+        let trajectory = simulation.trajectory();
 
-    match options.part {
-        1 => run_part1(&map),
-        2 => run_part2(&map),
-        _ => Err(ApplicationError::UnknownPart)
+        let turns = (1..trajectory.len()).filter(|&i| trajectory[i].1 != trajectory[i - 1].1);
+        for (frame, steps) in turns.chain([trajectory.len()]).enumerate() {
+            sink.write_frame(&simulation.frame_after(steps), frame as f64 / 4.0)?;
+        }
     }
-}
 
-fn run_part1(map: &LabMap) -> Result<usize, ApplicationError> {
-    let simulation = run_standard_simulation(map);
+    let turns: Vec<String> = simulation.turn_points().iter()
+        .map(|((x, y), heading)| format!("{},{} {}", x, y, heading_char(*heading)))
+        .collect();
 
-    Ok(simulation.visited_count())
+    let summary = match simulation.cycle() {
+        Some(cycle) => format!(
+            "loops after {} steps, repeating {} steps",
+            simulation.trajectory().len() - 1 - cycle.len(),
+            cycle.len()
+        ),
+        None => format!("leaves after {} steps, visiting {} tiles", simulation.trajectory().len() - 1, simulation.visited_count())
+    };
+
+    Ok(format!("{}\nturns: {}\n{}", summary, turns.join(", "), simulation.render(highlight)))
 }
 
-fn run_part2(map: &LabMap) -> Result<usize, ApplicationError> {
-    let mut total_count = 0;
-    let mut success_count = 0;
+fn heading_char(heading: Direction) -> char {
+    match heading {
+        Direction::North => '^',
+        Direction::East => '>',
+        Direction::South => 'v',
+        Direction::West => '<'
+    }
+}
 
-    let initial_simulation = run_standard_simulation(map);
+pub struct Day;
 
-    for (x, y) in initial_simulation.locations_visited() {
-        let x = *x;
-        let y = *y;
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 6;
 
-        if let LabMapTile::Empty = map.tile_at(x, y) {
-            total_count += 1;
-            println!("running simulation {} for {}, {}", total_count, x, y);
+    type Input = LabMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-            let mut simulation = GuardSimulation::new(&map, Some((x, y)));
+    fn part1(map: &LabMap) -> Result<usize, SolutionError> {
+        let simulation = run_standard_simulation(map);
 
-            while simulation.step() {
-                if simulation.loop_detected() {
-                    success_count += 1;
-                    break;
-                }
-            }
-        }
+        Ok(simulation.visited_count())
     }
 
-    Ok(success_count)
+    fn part2(map: &LabMap) -> Result<usize, SolutionError> {
+        Ok(map.loop_obstacles().len())
+    }
 }
 
-fn run_standard_simulation(map: &LabMap) -> GuardSimulation {
-    let mut simulation = GuardSimulation::new(&map, None);
+fn run_standard_simulation(map: &LabMap) -> GuardSimulation<'_> {
+    let mut simulation = GuardSimulation::new(map, None);
 
     while simulation.step() { }
 
     simulation
 }
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse map row: {0}")]
-    CouldntParseMapRow(#[from] guard::LabMapRowParseError)
-}
\ No newline at end of file
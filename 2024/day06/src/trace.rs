@@ -0,0 +1,185 @@
+use aoc_grid::frames::Frame;
+use aoc_grid::{Direction, Grid, Point};
+
+use crate::guard::{GuardSimulation, LabMapTile};
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Marks {
+    vertical: bool,
+    horizontal: bool,
+    turned: bool,
+    in_cycle: bool
+}
+
+impl GuardSimulation<'_> {
+    /// The map with the guard's path drawn the way the puzzle does: `|` and `-` where it
+    /// walked, `+` where it turned or crossed its own path, and `O` for the extra obstacle.
+    /// With `highlight_cycle`, the repeating part of a loop is shown in reverse video.
+    pub fn render(&self, highlight_cycle: bool) -> String {
+        let marks = self.marks(self.trajectory().len());
+
+        marks.rows()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, marks)| {
+                        let char = self.char_at(Point::new(x as i32, y as i32), marks);
+
+                        if highlight_cycle && marks.in_cycle {
+                            format!("\u{1b}[7m{}\u{1b}[0m", char)
+                        } else {
+                            char.to_string()
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn frame(&self) -> Frame {
+        self.frame_after(self.trajectory().len())
+    }
+
+    /// The path as far as the first `steps` entries of the trajectory. In images, a finished
+    /// loop's cycle is darker than the rest of the path.
+    pub fn frame_after(&self, steps: usize) -> Frame {
+        let marks = self.marks(steps);
+
+        Frame::from_grid(&marks, 3, |point, marks| {
+            let char = self.char_at(point, marks);
+            let level = match char {
+                '#' | 'O' => 3,
+                _ if marks.in_cycle => 2,
+                '.' => 0,
+                _ => 1
+            };
+
+            (char, level)
+        })
+    }
+
+    fn marks(&self, steps: usize) -> Grid<Marks> {
+        let map = self.map();
+        let mut marks = Grid::filled(map.width(), map.height(), Marks::default());
+
+        let trajectory = &self.trajectory()[..steps.min(self.trajectory().len())];
+
+        for pair in trajectory.windows(2) {
+            let ((from, _), (to, heading)) = (pair[0], pair[1]);
+
+            if from == to {
+                marks[Point::from(from)].turned = true;
+                continue;
+            }
+
+            for position in [from, to] {
+                let marks = &mut marks[Point::from(position)];
+
+                match heading {
+                    Direction::North | Direction::South => marks.vertical = true,
+                    Direction::East | Direction::West => marks.horizontal = true
+                }
+            }
+        }
+
+        // The cycle is only known once the whole run has been drawn
+        if let Some(cycle) = self.cycle() && steps >= self.trajectory().len() {
+            for (position, _) in cycle.iter() {
+                marks[Point::from(*position)].in_cycle = true;
+            }
+        }
+
+        marks
+    }
+
+    fn char_at(&self, point: Point, marks: &Marks) -> char {
+        if self.extra_obstacle() == Some((point.x, point.y)) {
+            return 'O';
+        }
+
+        match self.map().tile_at(point.x, point.y) {
+            LabMapTile::Obstacle => '#',
+            LabMapTile::GuardStartingPosition => '^',
+            _ if marks.turned || (marks.vertical && marks.horizontal) => '+',
+            _ if marks.vertical => '|',
+            _ if marks.horizontal => '-',
+            _ => '.'
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::guard::LabMap;
+
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    fn run(simulation: &mut GuardSimulation) {
+        while simulation.step() { }
+    }
+
+    #[test]
+    pub fn loop_trace() {
+        let map: LabMap = EXAMPLE.parse().unwrap();
+        let mut simulation = GuardSimulation::new(&map, Some((3, 6)));
+        run(&mut simulation);
+
+        assert!(simulation.loop_detected());
+        assert_eq!(simulation.render(false), "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...");
+
+        let cycle = simulation.cycle().unwrap();
+        assert_eq!(cycle.first(), Some(&((4, 6), Direction::North)));
+        assert_eq!(simulation.turn_points()[..4], [
+            ((4, 1), Direction::East),
+            ((8, 1), Direction::South),
+            ((8, 6), Direction::West),
+            ((4, 6), Direction::North)
+        ]);
+
+        // Here the guard walks part of its original path before it gets stuck
+        let mut later = GuardSimulation::new(&map, Some((7, 9)));
+        run(&mut later);
+
+        assert!(later.cycle().unwrap().len() < later.trajectory().len() - 1);
+
+        let highlighted = later.render(true);
+        let rows: Vec<&str> = highlighted.lines().collect();
+        assert!(!rows[5].contains('\u{1b}'));
+        assert!(rows[8].contains('\u{1b}'));
+    }
+
+    #[test]
+    pub fn turning_twice_in_a_corner() {
+        let map: LabMap = ".#...\n..#..\n.....\n.^...\n.....".parse().unwrap();
+        let mut simulation = GuardSimulation::new(&map, None);
+        run(&mut simulation);
+
+        assert!(!simulation.loop_detected());
+        assert_eq!(simulation.turn_points(), vec![((1, 1), Direction::East), ((1, 1), Direction::South)]);
+        assert_eq!(simulation.render(false), ".#...\n.+#..\n.|...\n.^...\n.|...");
+    }
+}
@@ -1,8 +1,6 @@
-use std::iter;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use itertools::{repeat_n, Itertools};
 use thiserror::Error;
 
 pub struct UnsolvedCalibrationSet {
@@ -27,6 +25,18 @@ impl UnsolvedCalibrationSet {
     }
 }
 
+impl FromStr for UnsolvedCalibrationSet {
+    type Err = UnsolvedCalibrationParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let calibrations = s.lines()
+            .map(|line| line.parse::<UnsolvedCalibration>())
+            .collect::<Result<Vec<UnsolvedCalibration>, _>>()?;
+
+        Ok(UnsolvedCalibrationSet::new(calibrations))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct UnsolvedCalibration {
     test_value: u64,
@@ -58,7 +68,7 @@ impl UnsolvedCalibration {
                 Operator::Multiply => accum * operand,
                 Operator::Concatenate => {
                     let operand_digits = operand.checked_ilog10().unwrap_or(0) + 1;
-                    accum * (10 as u64).pow(operand_digits) + operand
+                    accum * 10_u64.pow(operand_digits) + operand
                 }
             };
 
@@ -69,26 +79,6 @@ impl UnsolvedCalibration {
         
         false
     }
-
-    fn apply_operators(&self, operators: &Vec<&Operator>) -> u64 {
-        let mut accum = 0;
-        for (i, operand) in self.operands.iter().enumerate() {
-            if i == 0 {
-                accum = *operand;
-            } else {
-                match operators[i - 1] {
-                    Operator::Add => accum += operand,
-                    Operator::Multiply => accum *= operand,
-                    Operator::Concatenate => {
-                        let operand_digits = operand.checked_ilog10().unwrap_or(0) + 1;
-                        accum = accum * (10 as u64).pow(operand_digits) + operand;
-                    },
-                }
-            }            
-        }
-
-        accum
-    }
 }
 
 impl FromStr for UnsolvedCalibration {
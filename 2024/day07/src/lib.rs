@@ -1,67 +1,30 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use aoc_common::{Solution, SolutionError};
 
-use calibration::{Operator, UnsolvedCalibration, UnsolvedCalibrationParseError, UnsolvedCalibrationSet};
-use clap::Parser;
-use thiserror::Error;
+use calibration::{Operator, UnsolvedCalibrationSet};
 
-mod calibration;
+pub mod calibration;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+pub struct Day;
 
-pub fn run(options: CliOptions) -> Result<u64, ApplicationError> {
-    let filename = options.filename;
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 7;
 
-    let mut calibrations = vec![];
+    type Input = UnsolvedCalibrationSet;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    let lines = read_lines(filename)?;
-    for line in lines {
-        let line = line?;
-        let calibration: UnsolvedCalibration = line.parse()?;
+    fn part1(calibration_set: &UnsolvedCalibrationSet) -> Result<u64, SolutionError> {
+        let part1_operators = vec![Operator::Add, Operator::Multiply];
+        let score = calibration_set.calculate_score(&part1_operators);
 
-        calibrations.push(calibration);
+        Ok(score)
     }
 
-    let calibration_set = UnsolvedCalibrationSet::new(calibrations);
+    fn part2(calibration_set: &UnsolvedCalibrationSet) -> Result<u64, SolutionError> {
+        let part2_operators = vec![Operator::Add, Operator::Multiply, Operator::Concatenate];
+        let score = calibration_set.calculate_score(&part2_operators);
 
-    match options.part {
-        1 => run_part1(&calibration_set),
-        2 => run_part2(&calibration_set),
-        _ => Err(ApplicationError::UnknownPart)
+        Ok(score)
     }
 }
-
-fn run_part1(calibration_set: &UnsolvedCalibrationSet) -> Result<u64, ApplicationError> {
-    let part1_operators = vec![Operator::Add, Operator::Multiply];
-    let score = calibration_set.calculate_score(&part1_operators);
-
-    Ok(score)
-}
-
-fn run_part2(calibration_set: &UnsolvedCalibrationSet) -> Result<u64, ApplicationError> {
-    let part2_operators = vec![Operator::Add, Operator::Multiply, Operator::Concatenate];
-    let score = calibration_set.calculate_score(&part2_operators);
-
-    Ok(score)
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse puzzle input line: {0}")]
-    CouldntParseLine(#[from] UnsolvedCalibrationParseError)
-}
\ No newline at end of file
@@ -1,65 +1,34 @@
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
-use itertools::Itertools;
+use aoc_grid::{Grid, ParseGridError, Point};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-pub struct Point(i32, i32);
+impl FromStr for CityMap {
+    type Err = ParseGridError;
 
-impl Point {
-    pub fn subtract(&self, other: &Point) -> Point {
-        Point(self.0 - other.0, self.1 - other.1)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CityMap::new(s.parse()?))
     }
 }
 
-pub struct CityMapBuilder {
-    next_row: usize,
-    width: usize,
+pub struct CityMap {
+    map: Grid<char>,
     antennas_by_symbol: HashMap<char, Vec<Point>>
 }
 
-impl CityMapBuilder {
-    pub fn new() -> CityMapBuilder {
-        let next_row = 0;
-        let width = 0;
-        let antennas_by_symbol = HashMap::new();
-        
-        CityMapBuilder { next_row, width, antennas_by_symbol }
-    }
-
-    pub fn add_line(&mut self, line: &String) {
-        self.width = line.len();
-
-        let row = i32::try_from(self.next_row).unwrap();
-        self.next_row += 1;
+impl CityMap {
+    pub fn new(map: Grid<char>) -> CityMap {
+        let mut antennas_by_symbol: HashMap<char, Vec<Point>> = HashMap::new();
 
-        for (col, char) in line.chars().enumerate() {
-            let col = i32::try_from(col).unwrap();
-            
+        for (point, &char) in map.iter() {
             if char == '.' || char == '#' {
                 continue;
             }
-            
-            let entry = self.antennas_by_symbol.entry(char).or_insert_with(|| vec![]);
-            entry.push(Point(col, row))
-        }
-    }
-
-    pub fn into_city_map(self) -> CityMap {
-        let height = self.next_row;
-
-        CityMap::new(self.width, height, self.antennas_by_symbol)
-    }
-}
 
-pub struct CityMap {
-    width: usize,
-    height: usize,
-    antennas_by_symbol: HashMap<char, Vec<Point>>
-}
+            antennas_by_symbol.entry(char).or_default().push(point);
+        }
 
-impl CityMap {
-    pub fn new(width: usize, height: usize, antennas_by_symbol: HashMap<char, Vec<Point>>) -> CityMap {
-        CityMap { width, height, antennas_by_symbol }
+        CityMap { map, antennas_by_symbol }
     }
 
     pub fn count_antinodes_within_map_part1(&self) -> usize {
@@ -80,15 +49,15 @@ impl CityMap {
         for (_, points) in self.antennas_by_symbol.iter() {
             for (i, point) in points.iter().enumerate() {
                 for other in points.iter().skip(i + 1) {
-                    let Point(x, y) = other.subtract(point);
+                    let delta = *other - *point;
                     
-                    let before_antinode = Point(point.0 - x, point.1 - y);
-                    if self.is_point_in_map(before_antinode) {
+                    let before_antinode = *point - delta;
+                    if self.map.contains(before_antinode) {
                         antinodes.insert(before_antinode);
                     }
                     
-                    let after_antinode = Point(other.0 + x, other.1 + y);
-                    if self.is_point_in_map(after_antinode) {
+                    let after_antinode = *other + delta;
+                    if self.map.contains(after_antinode) {
                         antinodes.insert(after_antinode);
                     }
                 }
@@ -104,7 +73,7 @@ impl CityMap {
         for (_, points) in self.antennas_by_symbol.iter() {
             for (i, point) in points.iter().enumerate() {
                 for other in points.iter().skip(i + 1) {
-                    let Point(x, y) = other.subtract(point);
+                    let delta = *other - *point;
                     
                     antinodes.insert(*point);
                     antinodes.insert(*other);
@@ -112,8 +81,8 @@ impl CityMap {
                     for n in 1..=usize::MAX {
                         let n: i32 = n.try_into().unwrap();
 
-                        let antinode = Point(point.0 - x * n, point.1 - y * n);
-                        if self.is_point_in_map(antinode) {
+                        let antinode = *point - delta * n;
+                        if self.map.contains(antinode) {
                             antinodes.insert(antinode);
                         } else {
                             break;
@@ -123,8 +92,8 @@ impl CityMap {
                     for n in 1..=usize::MAX {
                         let n: i32 = n.try_into().unwrap();
 
-                        let antinode = Point(other.0 + x * n, other.1 + y * n);
-                        if self.is_point_in_map(antinode) {
+                        let antinode = *other + delta * n;
+                        if self.map.contains(antinode) {
                             antinodes.insert(antinode);
                         } else {
                             break;
@@ -136,13 +105,4 @@ impl CityMap {
 
         antinodes
     }
-
-    fn is_point_in_map(&self, point: Point) -> bool {
-        let width: i32 = self.width.try_into().unwrap();
-        let height: i32 = self.height.try_into().unwrap();
-
-        let Point(x, y) = point;
-
-        x >= 0 && x < width && y >= 0 && y < height
-    }
 }
\ No newline at end of file
@@ -3,6 +3,7 @@ use std::str::FromStr;
 
 use thiserror::Error;
 
+#[derive(Clone)]
 pub struct DiskMap {
     blocks: Vec<Option<usize>>
 }
@@ -15,7 +16,7 @@ impl DiskMap {
     pub fn compact(&mut self) {
         let mut empty_blocks = VecDeque::new();
         for (i, block) in self.blocks.iter().enumerate() {
-            if let None = block {
+            if block.is_none() {
                 empty_blocks.push_back(i);
             }
         }
@@ -33,7 +34,7 @@ impl DiskMap {
                     self.blocks[i] = None;
                 }
 
-                if empty_blocks.len() == 0 {
+                if empty_blocks.is_empty() {
                     break;
                 }
             }
@@ -112,24 +113,22 @@ impl FromStr for DiskMap {
         let chars: Vec<char> = s.chars().collect();
 
         for i in (0..chars.len()).step_by(2) {
-            if let Some(char) = chars.get(i) {
-                if let Some(block_count) = char.to_digit(10) {
-                    let block_count = block_count as usize;
+            if let Some(char) = chars.get(i)
+                && let Some(block_count) = char.to_digit(10) {
+                let block_count = block_count as usize;
 
-                    let id = next_id;
-                    next_id += 1;
+                let id = next_id;
+                next_id += 1;
 
-                    for _ in 0..block_count {
-                        blocks.push(Some(id));
-                    }
+                for _ in 0..block_count {
+                    blocks.push(Some(id));
                 }
             }
 
-            if let Some(char) = chars.get(i + 1) {
-                if let Some(block_count) = char.to_digit(10) {
-                    for _ in 0..block_count {
-                        blocks.push(None);
-                    }
+            if let Some(char) = chars.get(i + 1)
+                && let Some(block_count) = char.to_digit(10) {
+                for _ in 0..block_count {
+                    blocks.push(None);
                 }
             }
         }
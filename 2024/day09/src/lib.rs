@@ -1,53 +1,30 @@
-use std::fs::{self, File};
-use std::io::{self, BufRead};
-use std::path::Path;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use disk_map::{DiskMap, DiskMapParseError};
-use thiserror::Error;
+use disk_map::DiskMap;
 
-mod disk_map;
+pub mod disk_map;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
-
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-
-    let line = fs::read_to_string(filename)?;
+pub struct Day;
 
-    let mut disk_map: DiskMap = line.parse()?;
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 9;
 
-    let result = match options.part {
-        1 => run_part1(&mut disk_map),
-        2 => run_part2(&mut disk_map),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+    type Input = DiskMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn run_part1(disk_map: &mut DiskMap) -> Result<usize, ApplicationError> {
-    disk_map.compact();
+    fn part1(disk_map: &DiskMap) -> Result<usize, SolutionError> {
+        let mut disk_map = disk_map.clone();
+        disk_map.compact();
 
-    Ok(disk_map.checksum())
-}
+        Ok(disk_map.checksum())
+    }
 
-fn run_part2(disk_map: &mut DiskMap) -> Result<usize, ApplicationError> {
-    disk_map.compact_without_fragmenting();
-    
-    Ok(disk_map.checksum())
+    fn part2(disk_map: &DiskMap) -> Result<usize, SolutionError> {
+        let mut disk_map = disk_map.clone();
+        disk_map.compact_without_fragmenting();
+        
+        Ok(disk_map.checksum())
+    }
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse puzzle input: {0}")]
-    CouldntParseInput(#[from] DiskMapParseError)
-}
\ No newline at end of file
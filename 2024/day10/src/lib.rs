@@ -1,59 +1,28 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use thiserror::Error;
+use topographic_map::TopographicMap;
 
-mod topographic_map;
+pub mod topographic_map;
 
-use topographic_map::{TopographicMap};
+pub struct Day;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
-
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    
-    let lines = read_lines(filename)?;
-    let lines: Vec<String> = lines.map(|l| l.unwrap()).collect();
-
-    let map = TopographicMap::from_lines(&lines);
-
-    let result = match options.part {
-        1 => run_part1(&map),
-        2 => run_part2(&map),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 10;
 
-fn run_part1(map: &TopographicMap) -> Result<usize, ApplicationError> {
-    let score = map.score();
+    type Input = TopographicMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Ok(score)
-}
+    fn part1(map: &TopographicMap) -> Result<usize, SolutionError> {
+        let score = map.score();
 
-fn run_part2(map: &TopographicMap) -> Result<usize, ApplicationError> {
-    let rating = map.rating();
+        Ok(score)
+    }
 
-    Ok(rating)
-}
+    fn part2(map: &TopographicMap) -> Result<usize, SolutionError> {
+        let rating = map.rating();
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+        Ok(rating)
+    }
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
-}
\ No newline at end of file
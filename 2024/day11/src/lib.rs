@@ -1,55 +1,34 @@
-use std::fs::{self, File};
-use std::io::{self, BufRead};
-use std::path::Path;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
 use stones::StoneLine;
-use thiserror::Error;
 
-mod stones;
+pub mod stones;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
-
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
+pub struct Day;
 
-    let contents = fs::read_to_string(filename)?;
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 11;
 
-    let mut stone_line: StoneLine = contents.parse().unwrap();
+    type Input = StoneLine;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let result = match options.part {
-        1 => run_part1(&mut stone_line),
-        2 => run_part2(&mut stone_line),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+    fn part1(stone_line: &StoneLine) -> Result<usize, SolutionError> {
+        let mut stone_line = stone_line.clone();
+        for _ in 0..25 {
+            stone_line.blink();
+        }
 
-fn run_part1(stone_line: &mut StoneLine) -> Result<usize, ApplicationError> {
-    for _ in 0..25 {
-        stone_line.blink();
+        Ok(stone_line.score())
     }
 
-    Ok(stone_line.score())
-}
+    fn part2(stone_line: &StoneLine) -> Result<usize, SolutionError> {
+        let mut stone_line = stone_line.clone();
+        for _ in 0..75 {
+            stone_line.blink();
+        }
 
-fn run_part2(stone_line: &mut StoneLine) -> Result<usize, ApplicationError> {
-    for _ in 0..75 {
-        stone_line.blink();
+        Ok(stone_line.score())
     }
-
-    Ok(stone_line.score())
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
-}
\ No newline at end of file
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::num::ParseIntError;
 use std::str::FromStr;
 
+#[derive(Clone)]
 pub struct StoneLine {
     pub stones: HashMap<u64, usize>
 }
@@ -11,7 +13,7 @@ impl StoneLine {
     }
 
     pub fn score(&self) -> usize {
-        self.stones.iter().map(|(_, count)| *count).sum()
+        self.stones.values().copied().sum()
     }
 
     pub fn blink(&mut self) {
@@ -40,7 +42,7 @@ impl StoneLine {
         // If the stone is engraved with a number that has an even number of digits, it is replaced by two stones. The left half of the digits are engraved on the new left stone, and the right half of the digits are engraved on the new right stone. (The new numbers don't keep extra leading zeroes: 1000 would become stones 10 and 0.)
         let stone_string = stone.to_string();
         let len = stone_string.len();
-        if len % 2 == 0 {
+        if len.is_multiple_of(2) {
             let (left, right) = stone_string.split_at(len / 2);
 
             let left: u64 = left.parse().unwrap();
@@ -55,13 +57,13 @@ impl StoneLine {
 }
 
 impl FromStr for StoneLine {
-    type Err = ();
+    type Err = ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut stones = HashMap::new();
 
         for segment in s.split_whitespace() {
-            let stone = segment.parse::<u64>().unwrap();
+            let stone = segment.parse::<u64>()?;
 
             stones.entry(stone)
                 .and_modify(|count| *count += 1)
@@ -74,8 +76,6 @@ impl FromStr for StoneLine {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     // #[test]
     // pub fn blink() {
     //     let mut line: StoneLine = "0 1 10 99 999".parse().unwrap();
@@ -1,53 +1,31 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+
+use aoc_grid::{Grid, ParseGridError, Point};
 
 pub struct GardenMap {
-    width: i32,
-    height: i32,
-    plots: Vec<char>
+    plots: Grid<char>
 }
 
 impl GardenMap {
-    pub fn from_lines(lines: &Vec<String>) -> GardenMap {
-        let mut width = 0;
-        let mut plots = vec![];
-
-        for line in lines.iter() {
-            width = line.len();
-            
-            for char in line.chars() {
-                plots.push(char);
-            }
-        }
-
-        let width: i32 = width.try_into().unwrap();
-        let height: i32 = lines.len().try_into().unwrap();
-
-        GardenMap { width, height, plots }
-    }
-
     pub fn width(&self) -> i32 {
-        self.width
+        self.plots.width().try_into().unwrap()
     }
 
     pub fn height(&self) -> i32 {
-        self.height
+        self.plots.height().try_into().unwrap()
     }
 
     pub fn plot_at(&self, x: i32, y: i32) -> Option<char> {
-        if x < 0 || x >= self.width || y < 0 || y >= self.height {
-            None
-        } else {
-            let i = y * self.width + x;
-            let i: usize = i.try_into().unwrap();
-
-            self.plots.get(i).copied()
-        }
+        self.plots.get(Point::new(x, y)).copied()
     }
+}
 
-    pub fn find_regions(&self) {
-
+impl FromStr for GardenMap {
+    type Err = ParseGridError;
 
-        todo!()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(GardenMap { plots: s.parse()? })
     }
 }
 
@@ -56,11 +34,11 @@ pub struct RegionFinder<'a> {
 }
 
 impl<'a> RegionFinder<'a> {
-    pub fn new(garden_map: &GardenMap) -> RegionFinder {
+    pub fn new(garden_map: &GardenMap) -> RegionFinder<'_> {
         RegionFinder { garden_map }
     }
 
-    pub fn calculate_fence_prices(mut self) -> RegionFencePrice {
+    pub fn calculate_fence_prices(self) -> RegionFencePrice {
         let mut regions: HashMap<usize, Region> = HashMap::new();
         let mut visited = HashSet::new();
         let mut walkers = VecDeque::new();
@@ -77,85 +55,81 @@ impl<'a> RegionFinder<'a> {
             }
         }
 
-        loop {
-            if let Some((walker_id, x, y, plot)) = walkers.pop_front() {
-                if visited.insert((x, y)) {
-                    let region = regions.entry(walker_id).or_insert_with(|| Region::new());
-                    region.add_plot(x, y);
+        while let Some((walker_id, x, y, plot)) = walkers.pop_front() {
+            if visited.insert((x, y)) {
+                let region = regions.entry(walker_id).or_insert_with(Region::new);
+                region.add_plot(x, y);
                     
-                    let mut neighbors = vec![];
-                    for (next_x, next_y) in vec![(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
-                        let neighbor = self.garden_map.plot_at(next_x, next_y);
-                        neighbors.push(neighbor);
+                let mut neighbors = vec![];
+                for (next_x, next_y) in [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
+                    let neighbor = self.garden_map.plot_at(next_x, next_y);
+                    neighbors.push(neighbor);
                         
-                        if let Some(next_plot) = neighbor {
-                            if next_plot == plot {
-                                walkers.push_front((walker_id, next_x, next_y, next_plot))
-                            } else {
-                                region.add_edge();
-                            }
+                    if let Some(next_plot) = neighbor {
+                        if next_plot == plot {
+                            walkers.push_front((walker_id, next_x, next_y, next_plot))
                         } else {
                             region.add_edge();
                         }
+                    } else {
+                        region.add_edge();
                     }
+                }
 
-                    let north_neighbor = neighbors[0];
-                    let east_neighbor = neighbors[1];
-                    let south_neighbor = neighbors[2];
-                    let west_neighbor = neighbors[3];
+                let north_neighbor = neighbors[0];
+                let east_neighbor = neighbors[1];
+                let south_neighbor = neighbors[2];
+                let west_neighbor = neighbors[3];
                     
-                    let plot = Some(plot);
+                let plot = Some(plot);
                     
-                    // northeast corner check
-                    if plot != north_neighbor && plot != east_neighbor {
+                // northeast corner check
+                if plot != north_neighbor && plot != east_neighbor {
+                    region.add_corner();
+                }
+
+                if plot == north_neighbor && plot == east_neighbor {
+                    let northeast_neighbor = self.garden_map.plot_at(x + 1, y - 1);
+                    if plot != northeast_neighbor {
                         region.add_corner();
                     }
+                }
 
-                    if plot == north_neighbor && plot == east_neighbor {
-                        let northeast_neighbor = self.garden_map.plot_at(x + 1, y - 1);
-                        if plot != northeast_neighbor {
-                            region.add_corner();
-                        }
-                    }
+                // southeast corner check
+                if plot != south_neighbor && plot != east_neighbor {
+                    region.add_corner();
+                }
 
-                    // southeast corner check
-                    if plot != south_neighbor && plot != east_neighbor {
+                if plot == south_neighbor && plot == east_neighbor {
+                    let southeast_neighbor = self.garden_map.plot_at(x + 1, y + 1);
+                    if plot != southeast_neighbor {
                         region.add_corner();
                     }
+                }
 
-                    if plot == south_neighbor && plot == east_neighbor {
-                        let southeast_neighbor = self.garden_map.plot_at(x + 1, y + 1);
-                        if plot != southeast_neighbor {
-                            region.add_corner();
-                        }
-                    }
+                // southwest corner check
+                if plot != south_neighbor && plot != west_neighbor {
+                    region.add_corner();
+                }
 
-                    // southwest corner check
-                    if plot != south_neighbor && plot != west_neighbor {
+                if plot == south_neighbor && plot == west_neighbor {
+                    let southwest_neighbor = self.garden_map.plot_at(x - 1, y + 1);
+                    if plot != southwest_neighbor {
                         region.add_corner();
                     }
+                }
 
-                    if plot == south_neighbor && plot == west_neighbor {
-                        let southwest_neighbor = self.garden_map.plot_at(x - 1, y + 1);
-                        if plot != southwest_neighbor {
-                            region.add_corner();
-                        }
-                    }
+                // northwest corner check
+                if plot != north_neighbor && plot != west_neighbor {
+                    region.add_corner();
+                }
 
-                    // northwest corner check
-                    if plot != north_neighbor && plot != west_neighbor {
+                if plot == north_neighbor && plot == west_neighbor {
+                    let northwest_neighbor = self.garden_map.plot_at(x - 1, y - 1);
+                    if plot != northwest_neighbor {
                         region.add_corner();
                     }
-
-                    if plot == north_neighbor && plot == west_neighbor {
-                        let northwest_neighbor = self.garden_map.plot_at(x - 1, y - 1);
-                        if plot != northwest_neighbor {
-                            region.add_corner();
-                        }
-                    }
                 }
-            } else {
-                break;
             }
         }
 
@@ -232,4 +206,4 @@ impl Region {
     pub fn add_corner(&mut self) {
         self.corners += 1;
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,4 @@
+use std::convert::Infallible;
 use std::str::FromStr;
 
 use regex::Regex;
@@ -13,13 +14,9 @@ impl CraneGameList {
 
     pub fn solve(&self, adjustment: i64) -> i64 {
         let mut total_tokens: i64 = 0;
-        for (n, crane_game) in self.crane_games.iter().enumerate() {
-            println!("solving {:?}", crane_game);
+        for crane_game in self.crane_games.iter() {
             if let Some((a, b)) = crane_game.solve(adjustment) {
-                println!("found solution A: {}, B: {}", a, b);
                 total_tokens += a * 3 + b;
-            } else {
-                println!("no solution");
             }
         }
 
@@ -28,7 +25,7 @@ impl CraneGameList {
 }
 
 impl FromStr for CraneGameList {
-    type Err = ();
+    type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let pattern = Regex::new(r"Button A: X\+(\d+), Y\+(\d+)\nButton B: X\+(\d+), Y\+(\d+)\nPrize: X=(\d+), Y=(\d+)").unwrap();
@@ -0,0 +1,228 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::robots::RobotSimulation;
+
+impl RobotSimulation {
+    /// Every robot's x repeats after `width` seconds and y after `height`, so the whole
+    /// arena repeats after their lcm.
+    pub fn period(&self) -> i64 {
+        let (arena_width, arena_height) = self.arena_size();
+
+        arena_width / gcd(arena_width, arena_height) * arena_height
+    }
+
+    /// The seconds within one x-period and one y-period where the robots are most tightly
+    /// bunched along that axis, measured by variance.
+    pub fn axis_minima(&self) -> (i64, i64) {
+        let (arena_width, arena_height) = self.arena_size();
+
+        let x_minimum = (0..arena_width)
+            .min_by_key(|&seconds| variance(self.positions_after(seconds).iter().map(|(x, _)| *x)))
+            .unwrap_or(0);
+
+        let y_minimum = (0..arena_height)
+            .min_by_key(|&seconds| variance(self.positions_after(seconds).iter().map(|(_, y)| *y)))
+            .unwrap_or(0);
+
+        (x_minimum, y_minimum)
+    }
+
+    /// The first second where the robots are bunched along both axes at once, combining
+    /// [`RobotSimulation::axis_minima`] with the Chinese remainder theorem.
+    pub fn clustered_second(&self) -> Option<i64> {
+        let (arena_width, arena_height) = self.arena_size();
+        let (x_minimum, y_minimum) = self.axis_minima();
+
+        crt((x_minimum, arena_width), (y_minimum, arena_height))
+    }
+
+    /// Scores every second in one period and returns the `count` best, best first. Ties go
+    /// to the earlier second.
+    pub fn rank_seconds(&self, scorer: &dyn Scorer, count: usize) -> Vec<(i64, f64)> {
+        let mut scores: Vec<(i64, f64)> = (0..self.period())
+            .map(|seconds| (seconds, scorer.score(&self.positions_after(seconds), self.arena_size())))
+            .collect();
+
+        scores.sort_by(|(a_seconds, a_score), (b_seconds, b_score)| {
+            b_score.total_cmp(a_score).then(a_seconds.cmp(b_seconds))
+        });
+        scores.truncate(count);
+
+        scores
+    }
+}
+
+/// Rates how much a set of robot positions looks like a picture. Higher is more ordered.
+pub trait Scorer {
+    fn score(&self, positions: &[(i64, i64)], arena_size: (i64, i64)) -> f64;
+}
+
+/// Negated Shannon entropy of how the robots spread over square blocks of the arena.
+pub struct Entropy {
+    pub block_size: i64
+}
+
+impl Scorer for Entropy {
+    fn score(&self, positions: &[(i64, i64)], _arena_size: (i64, i64)) -> f64 {
+        let mut blocks: HashMap<(i64, i64), usize> = HashMap::new();
+        for (x, y) in positions.iter() {
+            *blocks.entry((x / self.block_size, y / self.block_size)).or_default() += 1;
+        }
+
+        let total = positions.len() as f64;
+        let entropy: f64 = blocks.values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum();
+
+        -entropy
+    }
+}
+
+/// Size of the largest group of robots touching horizontally or vertically.
+pub struct LargestComponent;
+
+impl Scorer for LargestComponent {
+    fn score(&self, positions: &[(i64, i64)], _arena_size: (i64, i64)) -> f64 {
+        let mut unvisited: HashSet<(i64, i64)> = positions.iter().copied().collect();
+        let mut largest = 0;
+
+        while let Some(&start) = unvisited.iter().next() {
+            unvisited.remove(&start);
+
+            let mut stack = vec![start];
+            let mut size = 0;
+
+            while let Some((x, y)) = stack.pop() {
+                size += 1;
+
+                for neighbor in [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
+                    if unvisited.remove(&neighbor) {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+
+            largest = largest.max(size);
+        }
+
+        largest as f64
+    }
+}
+
+/// How unevenly the robots are split between the quadrants used for the safety factor, as
+/// the gap between the fullest and emptiest quadrant over the number of robots.
+pub struct QuadrantImbalance;
+
+impl Scorer for QuadrantImbalance {
+    fn score(&self, positions: &[(i64, i64)], arena_size: (i64, i64)) -> f64 {
+        let (mid_x, mid_y) = (arena_size.0 / 2, arena_size.1 / 2);
+        let mut quadrants = [0usize; 4];
+
+        for &(x, y) in positions.iter() {
+            if x == mid_x || y == mid_y {
+                continue;
+            }
+
+            let quadrant = usize::from(x > mid_x) + 2 * usize::from(y > mid_y);
+            quadrants[quadrant] += 1;
+        }
+
+        let fullest = quadrants.iter().max().copied().unwrap_or(0);
+        let emptiest = quadrants.iter().min().copied().unwrap_or(0);
+
+        (fullest - emptiest) as f64 / positions.len().max(1) as f64
+    }
+}
+
+/// Variance scaled by n², which keeps it an integer without changing the ordering.
+fn variance(values: impl Iterator<Item = i64>) -> i64 {
+    let (n, sum, sum_of_squares) = values.fold((0, 0, 0), |(n, sum, sum_of_squares), value| {
+        (n + 1, sum + value, sum_of_squares + value * value)
+    });
+
+    n * sum_of_squares - sum * sum
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+/// Returns `(gcd, x, y)` with `a * x + b * y == gcd`.
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// The smallest non-negative `t` with `t ≡ a (mod m)` and `t ≡ b (mod n)`, if there is one.
+/// The moduli don't have to be coprime.
+fn crt((a, m): (i64, i64), (b, n): (i64, i64)) -> Option<i64> {
+    let (g, p, _) = extended_gcd(m, n);
+    if (b - a) % g != 0 {
+        return None;
+    }
+
+    let lcm = m / g * n;
+    let k = ((b - a) / g * p).rem_euclid(n / g);
+
+    Some((a + m * k).rem_euclid(lcm))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::robots::Robot;
+
+    use super::*;
+
+    /// Robots that all land on a 4x4 block at `seconds`, scattered by their differing
+    /// velocities the rest of the time.
+    fn converging(seconds: i64) -> RobotSimulation {
+        let arena_size = (101, 103);
+
+        let robots = (0..16)
+            .map(|i| {
+                let target = (40 + i % 4, 60 + i / 4);
+                let velocity = (i * 7 - 53, 31 - i * 5);
+                let start = (
+                    (target.0 - velocity.0 * seconds).rem_euclid(arena_size.0),
+                    (target.1 - velocity.1 * seconds).rem_euclid(arena_size.1)
+                );
+
+                Robot::new(start, velocity)
+            })
+            .collect();
+
+        RobotSimulation::new(robots, arena_size)
+    }
+
+    #[test]
+    pub fn chinese_remainder() {
+        assert_eq!(crt((2, 3), (3, 5)), Some(8));
+        assert_eq!(crt((1, 4), (3, 6)), Some(9));
+        assert_eq!(crt((1, 4), (2, 6)), None);
+    }
+
+    #[test]
+    pub fn clustering() {
+        let simulation = converging(6512);
+
+        assert_eq!(simulation.period(), 101 * 103);
+        assert_eq!(simulation.axis_minima(), (6512 % 101, 6512 % 103));
+        assert_eq!(simulation.clustered_second(), Some(6512));
+    }
+
+    #[test]
+    pub fn scorers() {
+        let simulation = converging(1234);
+
+        assert_eq!(simulation.rank_seconds(&LargestComponent, 1), vec![(1234, 16.0)]);
+        assert_eq!(simulation.rank_seconds(&Entropy { block_size: 4 }, 1)[0].0, 1234);
+        assert_eq!(simulation.rank_seconds(&QuadrantImbalance, 1)[0], (1234, 1.0));
+    }
+}
@@ -1,57 +1,128 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use clap::{Parser, ValueEnum};
-use thiserror::Error;
 
-mod robots;
-use robots::{ParseRobotError, Robot, RobotSimulation};
+use aoc_common::params::{Param, ParamError, ParamOptions, Params};
+use aoc_common::{ApplicationError, Part, Solution, SolutionError};
+use aoc_grid::frames::FrameFormat;
+
+pub mod analysis;
+pub mod robots;
+use analysis::{Entropy, LargestComponent, QuadrantImbalance, Scorer};
+use robots::RobotList;
 
 #[derive(Parser)]
 pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf,
-    arena_type: ArenaType
+    part: Part,
+    filename: PathBuf,
+
+    #[command(flatten)]
+    params: ParamOptions,
+
+    /// Instead of solving, list the seconds in one period that look most like a picture
+    #[arg(long, value_enum)]
+    rank: Option<ScorerType>,
+
+    /// How many seconds to list with --rank
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+
+    /// Instead of solving, record a frame per second to this file or directory
+    #[arg(long)]
+    record: Option<PathBuf>,
+
+    /// text, pbm, pgm or cast
+    #[arg(long, default_value = "cast")]
+    record_format: FrameFormat,
+
+    #[arg(long, default_value_t = 100)]
+    record_seconds: i64
 }
 
-#[derive(ValueEnum, Debug, Clone)]
+#[derive(ValueEnum, Debug, Clone, Copy)]
 #[clap(rename_all = "kebab_case")]
-enum ArenaType {
-    Example,
-    Full
+enum ScorerType {
+    Entropy,
+    Components,
+    Quadrants
 }
 
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
+pub const PARAMS: &[Param] = &[
+    Param { name: "width", description: "width of the arena", default: "101", example: "11" },
+    Param { name: "height", description: "height of the arena", default: "103", example: "7" }
+];
 
-    let mut robots = vec![];
+pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
+    let contents = fs::read_to_string(options.filename)?;
+    let robot_list = contents.parse::<RobotList>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
 
-    let lines = read_lines(filename)?;
-    for line in lines {
-        let line = line?;
+    let arena_size = arena_size(&robot_list, &options.params.resolve(PARAMS)?)?;
 
-        let robot: Robot = line.parse()?;
-        robots.push(robot);
+    if let Some(path) = options.record {
+        return record(&robot_list, arena_size, &path, options.record_format, options.record_seconds);
     }
 
-    let arena_size: (i64, i64) = match options.arena_type {
-        ArenaType::Example => (11, 7),
-        ArenaType::Full => (101, 103)
-    };
-
-    let mut simulation = RobotSimulation::new(robots, arena_size);
+    if let Some(scorer_type) = options.rank {
+        return Ok(rank_seconds(&robot_list, arena_size, scorer_type, options.top));
+    }
 
     let result = match options.part {
-        1 => run_part1(&mut simulation),
-        2 => run_part2(&mut simulation),
-        _ => Err(ApplicationError::UnknownPart)
+        Part::One => safety_factor(&robot_list, arena_size),
+        Part::Two => find_christmas_tree(&robot_list, arena_size)
     }?;
     
     Ok(result.to_string())
 }
 
-fn run_part1(simulation: &mut RobotSimulation) -> Result<u64, ApplicationError> {
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 14;
+
+    type Input = RobotList;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const PARAMS: &'static [Param] = PARAMS;
+
+    fn part1(robot_list: &RobotList) -> Result<u64, SolutionError> {
+        Self::part1_with(robot_list, &Params::defaults(PARAMS))
+    }
+
+    fn part2(robot_list: &RobotList) -> Result<u64, SolutionError> {
+        Self::part2_with(robot_list, &Params::defaults(PARAMS))
+    }
+
+    fn part1_with(robot_list: &RobotList, params: &Params) -> Result<u64, SolutionError> {
+        safety_factor(robot_list, arena_size(robot_list, params)?)
+    }
+
+    fn part2_with(robot_list: &RobotList, params: &Params) -> Result<u64, SolutionError> {
+        find_christmas_tree(robot_list, arena_size(robot_list, params)?)
+    }
+}
+
+fn arena_size(robot_list: &RobotList, params: &Params) -> Result<(i64, i64), ParamError> {
+    let arena_size = (params.get("width")?, params.get("height")?);
+
+    if arena_size.0 <= 0 || arena_size.1 <= 0 {
+        return Err(ParamError::DoesntFitInput(format!("arena size {}x{} is empty", arena_size.0, arena_size.1)));
+    }
+
+    if let Some((x, y)) = robot_list.first_outside(arena_size) {
+        return Err(ParamError::DoesntFitInput(
+            format!("robot at {},{} is outside the {}x{} arena", x, y, arena_size.0, arena_size.1)
+        ));
+    }
+
+    Ok(arena_size)
+}
+
+fn safety_factor(robot_list: &RobotList, arena_size: (i64, i64)) -> Result<u64, SolutionError> {
+    let mut simulation = robot_list.to_simulation(arena_size);
     simulation.simulate(100);
 
     let safety_factor = simulation.safety_factor();
@@ -59,35 +130,40 @@ fn run_part1(simulation: &mut RobotSimulation) -> Result<u64, ApplicationError>
     Ok(safety_factor)
 }
 
-fn run_part2(simulation: &mut RobotSimulation) -> Result<u64, ApplicationError> {
-    for n in 1..=100000 {
-        simulation.simulate(1);
+fn find_christmas_tree(robot_list: &RobotList, arena_size: (i64, i64)) -> Result<u64, SolutionError> {
+    let simulation = robot_list.to_simulation(arena_size);
 
-        if simulation.detect_christmas_tree() {
-            println!("possible christmas tree detected! {}", n);
-            simulation.print_map();
-            
-            return Ok(n);
-        }
-    }
+    let seconds = simulation.clustered_second().ok_or(SolutionError::NoSolution)?;
 
-    Err(ApplicationError::CouldntFindChristmasTree)
+    Ok(seconds as u64)
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+fn rank_seconds(robot_list: &RobotList, arena_size: (i64, i64), scorer_type: ScorerType, count: usize) -> String {
+    let simulation = robot_list.to_simulation(arena_size);
+
+    let scorer: Box<dyn Scorer> = match scorer_type {
+        ScorerType::Entropy => Box::new(Entropy { block_size: 5 }),
+        ScorerType::Components => Box::new(LargestComponent),
+        ScorerType::Quadrants => Box::new(QuadrantImbalance)
+    };
+
+    let lines: Vec<String> = simulation.rank_seconds(scorer.as_ref(), count)
+        .into_iter()
+        .map(|(seconds, score)| format!("{}: {:.3}", seconds, score))
+        .collect();
+
+    lines.join("\n")
 }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse robot robot: {0}")]
-    CouldntParseRobot(#[from] ParseRobotError),
-    #[error("couldn't find Christmas tree :(")]
-    CouldntFindChristmasTree
-}
\ No newline at end of file
+/// Frames are a tenth of a second apart in recordings that keep time.
+fn record(robot_list: &RobotList, arena_size: (i64, i64), path: &Path, format: FrameFormat, seconds: i64) -> Result<String, ApplicationError> {
+    let mut simulation = robot_list.to_simulation(arena_size);
+    let mut sink = format.create_sink(path)?;
+
+    for n in 0..=seconds {
+        sink.write_frame(&simulation.frame(), n as f64 / 10.0)?;
+        simulation.simulate(1);
+    }
+
+    Ok(format!("recorded {} frames to {}", seconds + 1, path.display()))
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
+use aoc_grid::frames::{Frame, FrameSink, TextSink};
 use thiserror::Error;
 
 pub struct RobotSimulation {
@@ -15,24 +16,20 @@ impl RobotSimulation {
     }
 
     pub fn simulate(&mut self, seconds: i64) {
-        let (arena_width, arena_height) = self.arena_size;
-
         for robot in self.robots.iter_mut() {
-            let (pos_x, pos_y) = robot.position;
-            let (vel_x, vel_y) = robot.velocity;
-
-            let mut new_x = (pos_x + vel_x * seconds) % arena_width;
-            if new_x < 0 {
-                new_x = arena_width + new_x;
-            }
+            *robot = robot.with_position(robot.position_after(seconds, self.arena_size));
+        }
+    }
 
-            let mut new_y = (pos_y + vel_y * seconds) % arena_height;
-            if new_y < 0 {
-                new_y = arena_height + new_y;
-            }
+    /// Where every robot will be after `seconds`, without moving them.
+    pub fn positions_after(&self, seconds: i64) -> Vec<(i64, i64)> {
+        self.robots.iter()
+            .map(|robot| robot.position_after(seconds, self.arena_size))
+            .collect()
+    }
 
-            *robot = robot.with_position((new_x, new_y));
-        }
+    pub fn arena_size(&self) -> (i64, i64) {
+        self.arena_size
     }
 
     pub fn safety_factor(&self) -> u64 {
@@ -71,9 +68,14 @@ impl RobotSimulation {
     }
 
     pub fn print_map(&self) {
+        TextSink::stdout().write_frame(&self.frame(), 0.0).expect("couldn't write to stdout");
+    }
+
+    /// Robot counts per tile, capped at 9.
+    pub fn frame(&self) -> Frame {
         let (arena_width, arena_height) = self.arena_size;
 
-        let mut arena = HashMap::new();
+        let mut arena: HashMap<(i64, i64), u32> = HashMap::new();
 
         for robot in self.robots.iter() {
             arena.entry(robot.position)
@@ -81,51 +83,53 @@ impl RobotSimulation {
                 .or_insert(1);
         }
 
-        for y in 0..arena_height {
-            for x in 0..arena_width {
-                if let Some(count) = arena.get(&(x, y)) {
-                    print!("{}", count);
-                } else {
-                    print!(".");
-                }
-            }
+        let width = usize::try_from(arena_width).unwrap();
+        let height = usize::try_from(arena_height).unwrap();
 
-            println!();
-        }
+        Frame::from_fn(width, height, 9, |point| {
+            match arena.get(&(i64::from(point.x), i64::from(point.y))) {
+                Some(&count) => {
+                    let count = count.min(9);
+                    (char::from_digit(count, 10).unwrap(), count as u8)
+                },
+                None => ('.', 0)
+            }
+        })
     }
+}
 
-    pub fn detect_christmas_tree(&self) -> bool {
-        let (arena_width, arena_height) = self.arena_size;
+pub struct RobotList {
+    robots: Vec<Robot>
+}
 
-        let mut arena = HashMap::new();
+impl RobotList {
+    /// The starting position of the first robot that doesn't fit in the arena, if any.
+    pub fn first_outside(&self, arena_size: (i64, i64)) -> Option<(i64, i64)> {
+        let (arena_width, arena_height) = arena_size;
 
-        for robot in self.robots.iter() {
-            arena.entry(robot.position)
-                .and_modify(|c| *c += 1)
-                .or_insert(1);
-        }
+        self.robots.iter()
+            .map(|robot| robot.position)
+            .find(|&(x, y)| x < 0 || x >= arena_width || y < 0 || y >= arena_height)
+    }
 
-        let mut robots_in_a_row = 0;
+    pub fn to_simulation(&self, arena_size: (i64, i64)) -> RobotSimulation {
+        RobotSimulation::new(self.robots.clone(), arena_size)
+    }
+}
 
-        for y in 0..arena_height {
-            robots_in_a_row = 0;
-            for x in 0..arena_width {
-                if let Some(count) = arena.get(&(x, y)) {
-                    robots_in_a_row += 1;
+impl FromStr for RobotList {
+    type Err = ParseRobotError;
 
-                    if robots_in_a_row > 10 {
-                        return true;
-                    }
-                } else {
-                    robots_in_a_row = 0;
-                }
-            }
-        }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let robots = s.lines()
+            .map(|line| line.parse::<Robot>())
+            .collect::<Result<Vec<Robot>, _>>()?;
 
-        false
+        Ok(RobotList { robots })
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct Robot {
     position: (i64, i64),
     velocity: (i64, i64)
@@ -136,6 +140,17 @@ impl Robot {
         Robot { position, velocity }
     }
 
+    pub fn position_after(&self, seconds: i64, arena_size: (i64, i64)) -> (i64, i64) {
+        let (arena_width, arena_height) = arena_size;
+        let (pos_x, pos_y) = self.position;
+        let (vel_x, vel_y) = self.velocity;
+
+        let new_x = (pos_x + vel_x * seconds).rem_euclid(arena_width);
+        let new_y = (pos_y + vel_y * seconds).rem_euclid(arena_height);
+
+        (new_x, new_y)
+    }
+
     pub fn with_position(&self, new_position: (i64, i64)) -> Robot {
         Robot {
             position: new_position,
@@ -152,8 +167,7 @@ impl FromStr for Robot {
         let velocity;
         
         if let Some((position_s, velocity_s)) = s.split_once(' ') {
-            if position_s.starts_with("p=") {
-                let position_s = &position_s[2..];
+            if let Some(position_s) = position_s.strip_prefix("p=") {
                 if let Some((x, y)) = position_s.split_once(',') {
                     let x: i64 = x.parse()?;
                     let y: i64 = y.parse()?;
@@ -166,8 +180,7 @@ impl FromStr for Robot {
                 return Err(ParseRobotError::InvalidPositionString);
             }
 
-            if velocity_s.starts_with("v=") {
-                let velocity_s = &velocity_s[2..];
+            if let Some(velocity_s) = velocity_s.strip_prefix("v=") {
                 if let Some((x, y)) = velocity_s.split_once(',') {
                     let x: i64 = x.parse()?;
                     let y: i64 = y.parse()?;
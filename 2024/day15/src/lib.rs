@@ -1,77 +1,211 @@
-use std::fs::{read_to_string, File};
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::fs;
+use std::path::PathBuf;
 
 use clap::Parser;
-use thiserror::Error;
-use warehouse::{ParseWarehouseSimulationError, WarehouseSimulation, WarehouseSimulationSpec};
+use clap::Subcommand;
+use aoc_common::{solve, ApplicationError, Part, Solution, SolutionError};
+use aoc_grid::frames::FrameFormat;
 
-mod warehouse;
+use replay::Replay;
+use solver::Goal;
+use warehouse::{WarehouseSimulation, WarehouseSimulationSpec};
+
+pub mod replay;
+pub mod solver;
+pub mod warehouse;
 
 #[derive(Parser)]
 pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
+    #[command(subcommand)]
+    command: Commands
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Part1 {
+        input: PathBuf,
+
+        /// Also record a frame per instruction to this file or directory
+        #[arg(long)]
+        record: Option<PathBuf>,
+
+        /// text, pbm, pgm or cast
+        #[arg(long, default_value = "cast")]
+        record_format: FrameFormat
+    },
+    Part2 {
+        input: PathBuf,
+
+        /// Also record a frame per instruction to this file or directory
+        #[arg(long)]
+        record: Option<PathBuf>,
+
+        /// text, pbm, pgm or cast
+        #[arg(long, default_value = "cast")]
+        record_format: FrameFormat
+    },
+    /// Run the instructions up to a step and print the map there
+    Replay {
+        input: PathBuf,
+
+        /// Stretch the warehouse this many times horizontally; part 2 uses 2
+        #[arg(long, default_value_t = 1)]
+        scale: usize,
+
+        /// Number of instructions to apply; defaults to all of them
+        #[arg(long)]
+        step: Option<usize>,
+
+        /// Write a line per applied step to this file
+        #[arg(long)]
+        export: Option<PathBuf>,
+
+        /// Record a frame per step up to --step to this file or directory
+        #[arg(long)]
+        record: Option<PathBuf>,
+
+        /// text, pbm, pgm or cast
+        #[arg(long, default_value = "cast")]
+        record_format: FrameFormat
+    },
+    /// Find the shortest instructions moving the boxes to a target layout or score; the
+    /// input's own instructions are ignored
+    Solve {
+        input: PathBuf,
+
+        #[arg(long, default_value_t = 1)]
+        scale: usize,
+
+        /// A map in the same format as the input, with the boxes where they should end up
+        #[arg(long, conflicts_with = "score", required_unless_present = "score")]
+        target: Option<PathBuf>,
+
+        #[arg(long)]
+        score: Option<i32>,
+
+        #[arg(long, default_value_t = 1_000_000)]
+        max_states: usize
+    }
 }
 
 pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    
-    let file_contents = read_to_string(filename)?;
-
-    let spec: WarehouseSimulationSpec = file_contents.parse()?;
-
-    let result = match options.part {
-        1 => run_part1(&spec),
-        2 => run_part2(&spec),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
+    match options.command {
+        Commands::Part1 { input, record, record_format } => {
+            solve_part(input, Part::One, record.map(|path| (path, record_format)))
+        },
+        Commands::Part2 { input, record, record_format } => {
+            solve_part(input, Part::Two, record.map(|path| (path, record_format)))
+        },
+        Commands::Replay { input, scale, step, export, record, record_format } => {
+            replay(input, scale, step, export, record.map(|path| (path, record_format)))
+        },
+        Commands::Solve { input, scale, target, score, max_states } => solve_layout(input, scale, target, score, max_states)
+    }
 }
 
-fn run_part1(spec: &WarehouseSimulationSpec) -> Result<i32, ApplicationError> {
-    let mut simulation = WarehouseSimulation::from_spec(spec);
+fn read_spec(input: PathBuf) -> Result<WarehouseSimulationSpec, ApplicationError> {
+    fs::read_to_string(input)?
+        .parse::<WarehouseSimulationSpec>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))
+}
 
-    println!("start:");
-    simulation.print();
+fn solve_part(input: PathBuf, part: Part, record: Option<(PathBuf, FrameFormat)>) -> Result<String, ApplicationError> {
+    let input = fs::read_to_string(input)?;
 
-    for instruction in spec.instructions().iter() {
-        simulation.process_instruction(*instruction);
+    if let Some((path, format)) = record {
+        let spec = input.parse::<WarehouseSimulationSpec>()
+            .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
+        let scale = match part {
+            Part::One => 1,
+            Part::Two => 2
+        };
+
+        let mut replay = Replay::new(WarehouseSimulation::from_spec_scaled(&spec, scale), spec.instructions().clone());
+        let steps = replay.len();
+        record_replay(&mut replay, steps, path, format)?;
     }
 
-    println!("end:");
-    simulation.print();
+    solve::<Day>(part, &input)
+}
+
+/// Applies instructions up to `step`, writing a frame before the first and after each one.
+fn record_replay(replay: &mut Replay, step: usize, path: PathBuf, format: FrameFormat) -> Result<(), ApplicationError> {
+    let mut sink = format.create_sink(path)?;
+    sink.write_frame(&replay.simulation().frame(), 0.0)?;
 
-    let score = simulation.score();
+    while replay.step() < step {
+        replay.redo();
+        sink.write_frame(&replay.simulation().frame(), replay.step() as f64 / 10.0)?;
+    }
 
-    Ok(score)
+    Ok(())
 }
 
-fn run_part2(spec: &WarehouseSimulationSpec) -> Result<i32, ApplicationError> {
-    let mut simulation = WarehouseSimulation::from_spec_doubled(spec);
+fn replay(input: PathBuf, scale: usize, step: Option<usize>, export: Option<PathBuf>, record: Option<(PathBuf, FrameFormat)>) -> Result<String, ApplicationError> {
+    let spec = read_spec(input)?;
+    let simulation = WarehouseSimulation::from_spec_scaled(&spec, scale);
 
-    println!("start:");
-    simulation.print();
+    let mut replay = Replay::new(simulation, spec.instructions().clone());
+    let step = step.unwrap_or(usize::MAX).min(replay.len());
 
-    for (n, instruction) in spec.instructions().iter().enumerate() {
-        simulation.process_instruction(*instruction);
+    if let Some((path, format)) = record {
+        record_replay(&mut replay, step, path, format)?;
     }
 
-    println!("end:");
-    simulation.print();
+    replay.jump_to(step);
 
-    let score = simulation.score();
+    if let Some(export) = export {
+        fs::write(export, replay.export())?;
+    }
 
-    Ok(score)
+    Ok(format!(
+        "step {}/{}, score {}\n{}",
+        replay.step(),
+        replay.len(),
+        replay.simulation().score(),
+        replay.simulation().render()
+    ))
 }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse warehouse simulation spec: {0}")]
-    CoulndParseInput(#[from] ParseWarehouseSimulationError)
-}
\ No newline at end of file
+fn solve_layout(input: PathBuf, scale: usize, target: Option<PathBuf>, score: Option<i32>, max_states: usize) -> Result<String, ApplicationError> {
+    let simulation = WarehouseSimulation::from_spec_scaled(&read_spec(input)?, scale);
+
+    let goal = match (target, score) {
+        (Some(target), _) => Goal::layout_of(&WarehouseSimulation::from_spec_scaled(&read_spec(target)?, scale)),
+        (None, Some(score)) => Goal::Score(score),
+        (None, None) => unreachable!("clap requires a target or a score")
+    };
+
+    let instructions = solver::solve(&simulation, &goal, max_states).map_err(SolutionError::from)?;
+
+    let instructions: String = instructions.iter().map(|instruction| instruction.to_string()).collect();
+
+    Ok(format!("{} instructions\n{}", instructions.len(), instructions))
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 15;
+
+    type Input = WarehouseSimulationSpec;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn part1(spec: &WarehouseSimulationSpec) -> Result<i32, SolutionError> {
+        Ok(run_instructions(WarehouseSimulation::from_spec(spec), spec))
+    }
+
+    fn part2(spec: &WarehouseSimulationSpec) -> Result<i32, SolutionError> {
+        Ok(run_instructions(WarehouseSimulation::from_spec_doubled(spec), spec))
+    }
+}
+
+fn run_instructions(mut simulation: WarehouseSimulation, spec: &WarehouseSimulationSpec) -> i32 {
+    for instruction in spec.instructions().iter() {
+        simulation.process_instruction(*instruction);
+    }
+
+    simulation.score()
+}
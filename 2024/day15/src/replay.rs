@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use crate::warehouse::{Instruction, MoveRecord, WarehouseSimulation};
+
+/// Steps through a list of instructions, keeping the record of every move so any of them can
+/// be undone and redone.
+pub struct Replay {
+    simulation: WarehouseSimulation,
+    instructions: Vec<Instruction>,
+    records: Vec<MoveRecord>,
+    step: usize
+}
+
+impl Replay {
+    pub fn new(simulation: WarehouseSimulation, instructions: Vec<Instruction>) -> Replay {
+        Replay { simulation, instructions, records: vec![], step: 0 }
+    }
+
+    pub fn simulation(&self) -> &WarehouseSimulation {
+        &self.simulation
+    }
+
+    /// How many instructions have been applied.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Records for the instructions applied so far.
+    pub fn records(&self) -> &[MoveRecord] {
+        &self.records[..self.step]
+    }
+
+    /// Applies the next instruction, reusing its record if it has been undone before.
+    pub fn redo(&mut self) -> Option<&MoveRecord> {
+        if self.step < self.records.len() {
+            self.simulation.apply(&self.records[self.step]);
+        } else {
+            let instruction = *self.instructions.get(self.step)?;
+            let record = self.simulation.process_instruction(instruction);
+            self.records.push(record);
+        }
+
+        self.step += 1;
+
+        Some(&self.records[self.step - 1])
+    }
+
+    pub fn undo(&mut self) -> Option<&MoveRecord> {
+        if self.step == 0 {
+            return None;
+        }
+
+        self.step -= 1;
+
+        let record = &self.records[self.step];
+        self.simulation.revert(record);
+
+        Some(record)
+    }
+
+    /// Moves to the state after `step` instructions, clamped to the number of instructions.
+    pub fn jump_to(&mut self, step: usize) {
+        let step = step.min(self.instructions.len());
+
+        while self.step < step {
+            self.redo();
+        }
+
+        while self.step > step {
+            self.undo();
+        }
+    }
+
+    /// One line per applied step, so two runs can be compared with `diff`.
+    pub fn export(&self) -> String {
+        let mut output = String::new();
+
+        for (i, record) in self.records().iter().enumerate() {
+            writeln!(output, "{} {}", i + 1, record).unwrap();
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::warehouse::WarehouseSimulationSpec;
+
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^";
+
+    fn example(doubled: bool) -> Replay {
+        let spec: WarehouseSimulationSpec = EXAMPLE.parse().unwrap();
+        let simulation = if doubled {
+            WarehouseSimulation::from_spec_doubled(&spec)
+        } else {
+            WarehouseSimulation::from_spec(&spec)
+        };
+
+        Replay::new(simulation, spec.instructions().clone())
+    }
+
+    #[test]
+    pub fn undo_redo() {
+        let mut replay = example(true);
+        let start = replay.simulation().render();
+
+        replay.jump_to(usize::MAX);
+        let end = replay.simulation().render();
+        assert_eq!(replay.step(), 11);
+        assert_eq!(replay.simulation().score(), 105 + 207 + 306);
+
+        replay.jump_to(0);
+        assert_eq!(replay.simulation().render(), start);
+
+        replay.jump_to(5);
+        assert!(replay.undo().is_some());
+        assert!(replay.redo().is_some());
+        replay.jump_to(11);
+        assert_eq!(replay.simulation().render(), end);
+        assert!(replay.redo().is_none());
+    }
+
+    #[test]
+    pub fn export() {
+        let mut replay = example(true);
+        replay.jump_to(4);
+
+        assert_eq!(replay.export(), "\
+1 < 10,3 -> 9,3 pushed [6,3 ]7,3 [8,3 ]9,3
+2 v 9,3 -> 9,4
+3 v 9,4 -> 9,5
+4 < 9,5 -> 8,5
+");
+    }
+}
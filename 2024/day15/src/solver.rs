@@ -0,0 +1,233 @@
+use std::collections::{HashMap, VecDeque};
+
+use aoc_common::SolutionError;
+use thiserror::Error;
+
+use crate::warehouse::{Instruction, WarehouseSimulation};
+
+const INSTRUCTIONS: [Instruction; 4] = [Instruction::Up, Instruction::Right, Instruction::Down, Instruction::Left];
+
+pub enum Goal {
+    /// Any layout with this [`WarehouseSimulation::score`].
+    Score(i32),
+    /// Boxes with their left edges at exactly these positions.
+    Boxes(Vec<(i32, i32)>)
+}
+
+impl Goal {
+    /// The box layout of another simulation, such as one built from a target map.
+    pub fn layout_of(simulation: &WarehouseSimulation) -> Goal {
+        Goal::Boxes(simulation.box_positions())
+    }
+
+    fn is_reached(&self, simulation: &WarehouseSimulation) -> bool {
+        match self {
+            Goal::Score(score) => simulation.score() == *score,
+            Goal::Boxes(positions) => simulation.box_positions() == *positions
+        }
+    }
+
+    /// Whether the goal can still be reached now that the boxes in `newly_frozen` can never
+    /// move again. A layout needs each of them on a goal position. A score needs the frozen
+    /// boxes' fixed share plus what the movable boxes could still add up to, each somewhere
+    /// in `box_scores`, to be able to hit the target.
+    fn allows_frozen(&self, simulation: &WarehouseSimulation, newly_frozen: &[(i32, i32)], box_scores: (i32, i32)) -> bool {
+        match self {
+            Goal::Score(score) => {
+                let (frozen, movable): (Vec<_>, Vec<_>) = simulation.box_positions()
+                    .into_iter()
+                    .partition(|&(x, y)| simulation.is_box_frozen(x, y));
+
+                let remaining = score - frozen.iter().map(|(x, y)| y * 100 + x).sum::<i32>();
+                let movable = movable.len() as i32;
+
+                (movable * box_scores.0..=movable * box_scores.1).contains(&remaining)
+            },
+            Goal::Boxes(positions) => newly_frozen.iter().all(|box_pos| positions.binary_search(box_pos).is_ok())
+        }
+    }
+}
+
+type State = ((i32, i32), Vec<(i32, i32)>);
+
+/// Breadth-first search over robot and box positions for the shortest list of instructions
+/// reaching `goal`. Moves that freeze a box against the walls where the goal can no longer
+/// be reached are pruned. Gives up after visiting `max_states` states.
+pub fn solve(simulation: &WarehouseSimulation, goal: &Goal, max_states: usize) -> Result<Vec<Instruction>, SolverError> {
+    let goal = match goal {
+        Goal::Boxes(positions) => {
+            let mut positions = positions.clone();
+            positions.sort();
+
+            if positions.len() != simulation.box_positions().len() {
+                return Err(SolverError::BoxCountMismatch);
+            }
+
+            Goal::Boxes(positions)
+        },
+        Goal::Score(score) => Goal::Score(*score)
+    };
+
+    let box_scores = simulation.box_score_range().unwrap_or((0, 0));
+
+    let start = state_of(simulation);
+    let mut parents: HashMap<State, Option<(State, Instruction)>> = HashMap::new();
+    parents.insert(start.clone(), None);
+
+    let mut queue = VecDeque::from([(simulation.clone(), start)]);
+
+    while let Some((current, state)) = queue.pop_front() {
+        if goal.is_reached(&current) {
+            return Ok(path_to(&parents, state));
+        }
+
+        for instruction in INSTRUCTIONS {
+            let mut next = current.clone();
+            let record = next.process_instruction(instruction);
+            if record.is_blocked() {
+                continue;
+            }
+
+            let newly_frozen: Vec<_> = record.pushed_boxes()
+                .filter(|&(x, y)| next.is_box_frozen(x, y))
+                .collect();
+
+            if !newly_frozen.is_empty() && !goal.allows_frozen(&next, &newly_frozen, box_scores) {
+                continue;
+            }
+
+            let next_state = state_of(&next);
+            if parents.contains_key(&next_state) {
+                continue;
+            }
+
+            if parents.len() >= max_states {
+                return Err(SolverError::TooManyStates(max_states));
+            }
+
+            parents.insert(next_state.clone(), Some((state.clone(), instruction)));
+            queue.push_back((next, next_state));
+        }
+    }
+
+    Err(SolverError::Unreachable)
+}
+
+fn state_of(simulation: &WarehouseSimulation) -> State {
+    (simulation.robot_pos(), simulation.box_positions())
+}
+
+fn path_to(parents: &HashMap<State, Option<(State, Instruction)>>, mut state: State) -> Vec<Instruction> {
+    let mut path = vec![];
+
+    while let Some(Some((parent, instruction))) = parents.get(&state) {
+        path.push(*instruction);
+        state = parent.clone();
+    }
+
+    path.reverse();
+    path
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum SolverError {
+    #[error("the goal can't be reached")]
+    Unreachable,
+    #[error("gave up after {0} states")]
+    TooManyStates(usize),
+    #[error("the goal doesn't have the same number of boxes as the warehouse")]
+    BoxCountMismatch
+}
+
+impl From<SolverError> for SolutionError {
+    fn from(err: SolverError) -> SolutionError {
+        SolutionError::Unsolvable(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::warehouse::WarehouseSimulationSpec;
+
+    use super::*;
+
+    fn simulation(map: &str, scale: usize) -> WarehouseSimulation {
+        let spec: WarehouseSimulationSpec = format!("{}\n\n", map).parse().unwrap();
+
+        WarehouseSimulation::from_spec_scaled(&spec, scale)
+    }
+
+    #[test]
+    pub fn shortest_path() {
+        let start = simulation("\
+######
+#....#
+#.O@.#
+#....#
+######", 1);
+
+        let target = simulation("\
+######
+#....#
+#....#
+#.O..#
+######", 1);
+
+        let instructions = solve(&start, &Goal::layout_of(&target), 10_000).unwrap();
+        assert_eq!(instructions.len(), 3);
+
+        let mut check = start.clone();
+        for instruction in instructions {
+            check.process_instruction(instruction);
+        }
+        assert_eq!(check.box_positions(), vec![(2, 3)]);
+
+        let by_score = solve(&start, &Goal::Score(302), 10_000).unwrap();
+        assert_eq!(by_score.len(), 3);
+    }
+
+    #[test]
+    pub fn deadlocks() {
+        let open = simulation("\
+#####
+#...#
+#.O.#
+#.@.#
+#####", 2);
+
+        assert!(!open.is_box_frozen(4, 2));
+        assert_eq!(solve(&open, &Goal::Boxes(vec![(4, 1)]), 10_000).unwrap().len(), 1);
+        assert_eq!(solve(&open, &Goal::Boxes(vec![]), 10_000), Err(SolverError::BoxCountMismatch));
+
+        let cornered = simulation("\
+#####
+#O..#
+#...#
+#.@.#
+#####", 2);
+
+        assert!(cornered.is_box_frozen(2, 1));
+        assert_eq!(solve(&cornered, &Goal::Boxes(vec![(4, 2)]), 10_000), Err(SolverError::Unreachable));
+    }
+
+    #[test]
+    pub fn score_deadlocks() {
+        // The only push corners the box at a score of 101
+        let corridor = simulation("\
+#####
+#.O@#
+#####", 1);
+
+        assert_eq!(corridor.box_score_range(), Some((101, 103)));
+        assert_eq!(solve(&corridor, &Goal::Score(101), 10), Ok(vec![Instruction::Left]));
+
+        // Pruned before the state limit is reached, rather than stored and searched
+        assert_eq!(solve(&corridor, &Goal::Score(103), 1), Err(SolverError::Unreachable));
+
+        let mut cornered = corridor.clone();
+        cornered.process_instruction(Instruction::Left);
+        assert!(cornered.is_box_frozen(1, 1));
+        assert!(Goal::Score(101).allows_frozen(&cornered, &[(1, 1)], (101, 103)));
+        assert!(!Goal::Score(103).allows_frozen(&cornered, &[(1, 1)], (101, 103)));
+    }
+}
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
-use std::iter;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::str::FromStr;
+use aoc_grid::frames::Frame;
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -29,7 +30,7 @@ impl FromStr for WarehouseSimulationSpec {
         if let Some(line) = lines.next() {
             first_line = line;
 
-            let first_row: Vec<MapTile> = iter::repeat(MapTile::Wall).take(first_line.len()).collect();
+            let first_row: Vec<MapTile> = std::iter::repeat_n(MapTile::Wall, first_line.len()).collect();
             rows.push(first_row);
         } else {
             return Err(ParseWarehouseSimulationError::UnexpectedEndOfString);
@@ -39,7 +40,7 @@ impl FromStr for WarehouseSimulationSpec {
         loop {
             if let Some(line) = lines.next() {
                 if line == first_line {
-                    let last_row: Vec<MapTile> = iter::repeat(MapTile::Wall).take(first_line.len()).collect();
+                    let last_row: Vec<MapTile> = std::iter::repeat_n(MapTile::Wall, first_line.len()).collect();
                     rows.push(last_row);
                     
                     break;
@@ -65,25 +66,21 @@ impl FromStr for WarehouseSimulationSpec {
         }
 
         // Skip a line
-        if let None = lines.next() {
+        if lines.next().is_none() {
             return Err(ParseWarehouseSimulationError::UnexpectedEndOfString);
         }
 
-        loop {
-            if let Some(line) = lines.next() {
-                for char in line.chars() {
-                    let instruction = match char {
-                        '^' => Instruction::Up,
-                        '>' => Instruction::Right,
-                        'v' => Instruction::Down,
-                        '<' => Instruction::Left,
-                        _ => return Err(ParseWarehouseSimulationError::UnrecognizedInstruction(char))
-                    };
-
-                    instructions.push(instruction);
-                }
-            } else {
-                break;
+        for line in lines {
+            for char in line.chars() {
+                let instruction = match char {
+                    '^' => Instruction::Up,
+                    '>' => Instruction::Right,
+                    'v' => Instruction::Down,
+                    '<' => Instruction::Left,
+                    _ => return Err(ParseWarehouseSimulationError::UnrecognizedInstruction(char))
+                };
+
+                instructions.push(instruction);
             }
         }
 
@@ -109,7 +106,7 @@ enum MapTile {
     Robot
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Instruction {
     Up,
     Right,
@@ -117,7 +114,20 @@ pub enum Instruction {
     Left
 }
 
-#[derive(Debug)]
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let char = match self {
+            Instruction::Up => '^',
+            Instruction::Right => '>',
+            Instruction::Down => 'v',
+            Instruction::Left => '<'
+        };
+
+        write!(f, "{}", char)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct WarehouseSimulation {
     robot_pos: (i32, i32),
     entities: HashMap<(i32, i32), Entity>,
@@ -127,6 +137,18 @@ pub struct WarehouseSimulation {
 
 impl WarehouseSimulation {
     pub fn from_spec(spec: &WarehouseSimulationSpec) -> WarehouseSimulation {
+        WarehouseSimulation::from_spec_scaled(spec, 1)
+    }
+
+    pub fn from_spec_doubled(spec: &WarehouseSimulationSpec) -> WarehouseSimulation {
+        WarehouseSimulation::from_spec_scaled(spec, 2)
+    }
+
+    /// Stretches every tile `scale` times horizontally, so boxes end up `scale` wide. The robot
+    /// stays a single tile, on the left of its stretched tile.
+    pub fn from_spec_scaled(spec: &WarehouseSimulationSpec, scale: usize) -> WarehouseSimulation {
+        let scale: i32 = scale.max(1).try_into().unwrap();
+
         let mut robot_pos = (0, 0);
         let mut entities = HashMap::new();
 
@@ -139,12 +161,21 @@ impl WarehouseSimulation {
 
             for (x, tile) in row.iter().enumerate() {
                 let x: i32 = x.try_into().unwrap();
-                width = x + 1;
+                let x = x * scale;
+                width = x + scale;
 
                 match tile {
                     MapTile::Floor => { },
-                    MapTile::Wall => { entities.insert((x, y), Entity::Wall); },
-                    MapTile::Box => { entities.insert((x, y), Entity::Box); },
+                    MapTile::Wall => {
+                        for offset in 0..scale {
+                            entities.insert((x + offset, y), Entity::Wall);
+                        }
+                    },
+                    MapTile::Box => {
+                        for offset in 0..scale {
+                            entities.insert((x + offset, y), Entity::Box { offset, width: scale });
+                        }
+                    },
                     MapTile::Robot => { robot_pos = (x, y); }
                 }
             }
@@ -153,56 +184,61 @@ impl WarehouseSimulation {
         WarehouseSimulation { robot_pos, entities, width, height }
     }
 
-    pub fn from_spec_doubled(spec: &WarehouseSimulationSpec) -> WarehouseSimulation {
-        let mut robot_pos = (0, 0);
-        let mut entities = HashMap::new();
+    /// Moves the robot, pushing any boxes in its way, and returns a record of what moved.
+    pub fn process_instruction(&mut self, instruction: Instruction) -> MoveRecord {
+        let robot_from = self.robot_pos;
+        let (next_x, next_y) = robot_from.apply_instruction(instruction);
 
-        let mut width: i32 = 0;
-        let mut height: i32 = 0;
+        let pushed = match self.entity_at(next_x, next_y) {
+            Some(Entity::Wall) => None,
+            Some(Entity::Box { .. }) => self.try_push_box(next_x, next_y, instruction),
+            None => Some(vec![])
+        };
 
-        for (y, row) in spec.rows.iter().enumerate() {
-            let y: i32 = y.try_into().unwrap();
-            height = y + 1;
+        let record = match pushed {
+            Some(mut positions) => {
+                positions.sort();
+                positions.dedup();
 
-            for (x, tile) in row.iter().enumerate() {
-                let x: i32 = (x * 2).try_into().unwrap();
-                width = x + 2;
+                let moved = positions.into_iter()
+                    .map(|pos| (pos, self.entities[&pos]))
+                    .collect();
 
-                match tile {
-                    MapTile::Floor => { },
-                    MapTile::Wall => { 
-                        entities.insert((x, y), Entity::Wall);
-                        entities.insert((x + 1, y), Entity::Wall);
-                    },
-                    MapTile::Box => {
-                        entities.insert((x, y), Entity::LargeBoxLeft);
-                        entities.insert((x + 1, y), Entity::LargeBoxRight);
-                    },
-                    MapTile::Robot => { robot_pos = (x, y); }
-                }
-            }
+                MoveRecord { instruction, robot_from, robot_to: (next_x, next_y), moved }
+            },
+            None => MoveRecord { instruction, robot_from, robot_to: robot_from, moved: vec![] }
+        };
+
+        self.apply(&record);
+
+        record
+    }
+
+    /// Replays a record produced by [`WarehouseSimulation::process_instruction`] from the
+    /// state it was recorded in.
+    pub fn apply(&mut self, record: &MoveRecord) {
+        for (pos, _) in record.moved.iter() {
+            self.entities.remove(pos);
         }
 
-        WarehouseSimulation { robot_pos, entities, width, height }
+        for (pos, entity) in record.moved.iter() {
+            self.entities.insert(pos.apply_instruction(record.instruction), *entity);
+        }
+
+        self.robot_pos = record.robot_to;
     }
 
-    pub fn process_instruction(&mut self, instruction: Instruction) {
-        let current_pos = self.robot_pos;
-        let (next_x, next_y) = current_pos.apply_instruction(instruction);
+    /// Reverts a record, which must be the last one applied.
+    pub fn revert(&mut self, record: &MoveRecord) {
+        for (pos, _) in record.moved.iter() {
+            self.entities.remove(&pos.apply_instruction(record.instruction));
+        }
 
-        match self.entity_at(next_x, next_y) {
-            Some(Entity::Wall) => { 
-                // Do nothing
-            },
-            Some(Entity::Box | Entity::LargeBoxLeft | Entity::LargeBoxRight) => {
-                if self.try_push_box(next_x, next_y, instruction) {
-                    self.robot_pos = (next_x, next_y);
-                }
-            },
-            None => {
-                self.robot_pos = (next_x, next_y);
-            }
+        for (pos, entity) in record.moved.iter() {
+            self.entities.insert(*pos, *entity);
         }
+
+        self.robot_pos = record.robot_from;
     }
 
     fn entity_at(&self, x: i32, y: i32) -> Option<Entity> {
@@ -213,122 +249,104 @@ impl WarehouseSimulation {
         }
     }
 
-    fn try_push_box(&mut self, x: i32, y: i32, instruction: Instruction) -> bool {
-        match instruction {
-            Instruction::Left | Instruction::Right => self.try_push_box_left_right(x, y, instruction),
-            Instruction::Up | Instruction::Down => self.try_push_box_up_down(x, y, instruction)
-        }
-    }
+    /// Finds every tile of every box that would move if the box at `(x, y)` were pushed, or
+    /// `None` if any of them runs into a wall.
+    fn try_push_box(&self, x: i32, y: i32, instruction: Instruction) -> Option<Vec<(i32, i32)>> {
+        let mut entities_to_move = vec![];
+        let mut seen_boxes = HashSet::new();
+        let mut boxes_to_check = vec![(x, y)];
+
+        while let Some(pos) = boxes_to_check.pop() {
+            let Some(Entity::Box { offset, width }) = self.entity_at(pos.0, pos.1) else {
+                continue;
+            };
+
+            let origin = (pos.0 - offset, pos.1);
+            if !seen_boxes.insert(origin) {
+                continue;
+            }
 
-    fn try_push_box_left_right(&mut self, x: i32, y: i32, instruction: Instruction) -> bool {
-        let mut current_pos = (x, y);
-        let mut entities_to_move = vec![current_pos];
+            for offset in 0..width {
+                let tile = (origin.0 + offset, origin.1);
+                entities_to_move.push(tile);
 
-        loop {
-            let (next_x, next_y) = current_pos.apply_instruction(instruction);
-            match self.entity_at(next_x, next_y) {
-                Some(Entity::Wall) => { 
-                    return false;
-                },
-                Some(Entity::Box) => { 
-                    entities_to_move.push((next_x, next_y));
-                    current_pos = (next_x, next_y);
-                },
-                Some(Entity::LargeBoxLeft) => {
-                    entities_to_move.push((next_x, next_y));
-                    current_pos = (next_x, next_y);
-                },
-                Some(Entity::LargeBoxRight) => {
-                    entities_to_move.push((next_x, next_y));
-                    current_pos = (next_x, next_y);
-                },
-                None => {
-                    while let Some(old_pos) = entities_to_move.pop() {
-                        let new_pos = old_pos.apply_instruction(instruction);
-                        if let Some(entity) = self.entities.remove(&old_pos) {
-                            self.entities.insert(new_pos, entity);
-                        }
-                    }
-                    return true;
+                // Every tile of the box pushes on whatever is in front of it, unless that's
+                // another tile of the same box
+                let (next_x, next_y) = tile.apply_instruction(instruction);
+                if next_y == origin.1 && next_x >= origin.0 && next_x < origin.0 + width {
+                    continue;
+                }
+
+                match self.entity_at(next_x, next_y) {
+                    Some(Entity::Wall) => return None,
+                    Some(Entity::Box { .. }) => boxes_to_check.push((next_x, next_y)),
+                    None => { }
                 }
             }
         }
+
+        Some(entities_to_move)
     }
 
-    fn try_push_box_up_down(&mut self, x: i32, y: i32, instruction: Instruction) -> bool {
-        let mut front_edge = match self.entity_at(x, y) {
-            Some(Entity::Wall) => {
-                return false;
-            },
-            Some(Entity::Box) => {
-                vec![(x, y)]
-            },
-            Some(Entity::LargeBoxLeft) => {
-                vec![(x, y), (x + 1, y)]
-            },
-            Some(Entity::LargeBoxRight) => {
-                vec![(x - 1, y), (x, y)]
-            },
-            None => {
-                return true;
-            }
-        };
-        
-        let mut entities_to_move= front_edge.clone();
+    pub fn robot_pos(&self) -> (i32, i32) {
+        self.robot_pos
+    }
 
-        let mut entity_detected = true;
-        while entity_detected {
-            entity_detected = false;
+    /// The left edge of every box, sorted.
+    pub fn box_positions(&self) -> Vec<(i32, i32)> {
+        let mut positions: Vec<(i32, i32)> = self.entities.iter()
+            .filter(|(_, entity)| matches!(entity, Entity::Box { offset: 0, .. }))
+            .map(|(pos, _)| *pos)
+            .collect();
 
-            let mut next_front_edge = vec![];
+        positions.sort();
+        positions
+    }
 
-            for pos in front_edge {
-                let (next_x, next_y) = pos.apply_instruction(instruction);
-                match self.entity_at(next_x, next_y) {
-                    Some(Entity::Wall) => { 
-                        // Bail out on first wall encountered
-                        return false;
-                    },
-                    Some(Entity::Box) => { 
-                        entity_detected = true;
+    /// Whether walls stop the box with its left edge at `(x, y)` from ever moving again:
+    /// every direction is either walled off in front of it or has a wall where the robot
+    /// would need to stand.
+    pub fn is_box_frozen(&self, x: i32, y: i32) -> bool {
+        let Some(Entity::Box { width, .. }) = self.entity_at(x, y) else {
+            return false;
+        };
 
-                        entities_to_move.push((next_x, next_y));
-                        next_front_edge.push((next_x, next_y));
-                    },
-                    Some(Entity::LargeBoxLeft) => {
-                        entity_detected = true;
-
-                        entities_to_move.push((next_x, next_y));
-                        entities_to_move.push((next_x + 1, next_y));
-                        
-                        next_front_edge.push((next_x, next_y));
-                        next_front_edge.push((next_x + 1, next_y));
-                    },
-                    Some(Entity::LargeBoxRight) => {
-                        entity_detected = true;
-
-                        entities_to_move.push((next_x - 1, next_y));
-                        entities_to_move.push((next_x, next_y));
-                        
-                        next_front_edge.push((next_x - 1, next_y));
-                        next_front_edge.push((next_x, next_y));
-                    },
-                    None => { }
-                }
-            }
-            
-            front_edge = next_front_edge;
-        }
+        let is_wall = |x: i32, y: i32| matches!(self.entity_at(x, y), Some(Entity::Wall));
+        let tiles = || x..x + width;
 
-        // No more entities in the way
-        while let Some(old_pos) = entities_to_move.pop() {
-            let new_pos = old_pos.apply_instruction(instruction);
-            if let Some(entity) = self.entities.remove(&old_pos) {
-                self.entities.insert(new_pos, entity);
-            }
-        }
+        let wall_above = tiles().any(|x| is_wall(x, y - 1));
+        let wall_below = tiles().any(|x| is_wall(x, y + 1));
+        let open_above = tiles().any(|x| !is_wall(x, y - 1));
+        let open_below = tiles().any(|x| !is_wall(x, y + 1));
+        let wall_left = is_wall(x - 1, y);
+        let wall_right = is_wall(x + width, y);
+
+        let can_move_up = !wall_above && open_below;
+        let can_move_down = !wall_below && open_above;
+        // Pushing sideways needs space on one side and the robot on the other
+        let can_move_sideways = !wall_left && !wall_right;
 
-        true
+        !(can_move_up || can_move_down || can_move_sideways)
+    }
+
+    /// The lowest and highest score a single box could have anywhere it fits between the
+    /// walls, ignoring the other boxes. `None` when there are no boxes.
+    pub fn box_score_range(&self) -> Option<(i32, i32)> {
+        let width = self.entities.values().find_map(|entity| match entity {
+            Entity::Box { width, .. } => Some(*width),
+            Entity::Wall => None
+        })?;
+
+        let is_wall = |x: i32, y: i32| matches!(self.entity_at(x, y), Some(Entity::Wall));
+
+        (0..self.height)
+            .flat_map(|y| (0..=self.width - width).map(move |x| (x, y)))
+            .filter(|&(x, y)| (x..x + width).all(|x| !is_wall(x, y)))
+            .map(|(x, y)| y * 100 + x)
+            .fold(None, |range, score| match range {
+                Some((min, max)) => Some((score.min(min), score.max(max))),
+                None => Some((score, score))
+            })
     }
 
     pub fn score(&self) -> i32 {
@@ -337,7 +355,7 @@ impl WarehouseSimulation {
         for x in 0..self.width {
             for y in 0..self.height {
                 score += match self.entity_at(x, y) {
-                    Some(Entity::Box | Entity::LargeBoxLeft) => y * 100 + x,
+                    Some(Entity::Box { offset: 0, .. }) => y * 100 + x,
                     _ => 0
                 }
             }
@@ -346,33 +364,90 @@ impl WarehouseSimulation {
         score
     }
 
-    pub fn print(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if (x, y) == self.robot_pos {
-                    print!("@");
-                } else {
-                    match self.entity_at(x, y) {
-                        Some(Entity::Wall) => { 
-                            print!("#");
-                        },
-                        Some(Entity::Box) => { 
-                            print!("O");
-                        },
-                        Some(Entity::LargeBoxLeft) => {
-                            print!("[");
-                        },
-                        Some(Entity::LargeBoxRight) => {
-                            print!("]");
-                        },
-                        None => {
-                            print!(".");
-                        }
-                    }
-                }                
+    pub fn render(&self) -> String {
+        self.frame().to_string()
+    }
+
+    /// Walls are drawn darkest, then boxes, then the robot.
+    pub fn frame(&self) -> Frame {
+        let width = usize::try_from(self.width).unwrap();
+        let height = usize::try_from(self.height).unwrap();
+
+        Frame::from_fn(width, height, 3, |point| {
+            let (x, y) = (point.x, point.y);
+
+            if (x, y) == self.robot_pos {
+                ('@', 1)
+            } else {
+                match self.entity_at(x, y) {
+                    Some(entity @ Entity::Wall) => (entity.to_char(), 3),
+                    Some(entity @ Entity::Box { .. }) => (entity.to_char(), 2),
+                    None => ('.', 0)
+                }
+            }
+        })
+    }
+}
+
+/// What a single instruction did: where the robot went and which boxes it shifted. Every
+/// tile of a wide box is listed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MoveRecord {
+    instruction: Instruction,
+    robot_from: (i32, i32),
+    robot_to: (i32, i32),
+    moved: Vec<((i32, i32), Entity)>
+}
+
+impl MoveRecord {
+    pub fn instruction(&self) -> Instruction {
+        self.instruction
+    }
+
+    pub fn robot_from(&self) -> (i32, i32) {
+        self.robot_from
+    }
+
+    pub fn robot_to(&self) -> (i32, i32) {
+        self.robot_to
+    }
+
+    /// Box positions before the move, with what was there.
+    pub fn moved(&self) -> &[((i32, i32), Entity)] {
+        &self.moved
+    }
+
+    /// Left edges of the pushed boxes after the move.
+    pub fn pushed_boxes(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.moved.iter()
+            .filter(|(_, entity)| matches!(entity, Entity::Box { offset: 0, .. }))
+            .map(|(pos, _)| pos.apply_instruction(self.instruction))
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        self.robot_from == self.robot_to
+    }
+}
+
+impl Display for MoveRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (from_x, from_y) = self.robot_from;
+        let (to_x, to_y) = self.robot_to;
+
+        if self.is_blocked() {
+            return write!(f, "{} {},{} blocked", self.instruction, from_x, from_y);
+        }
+
+        write!(f, "{} {},{} -> {},{}", self.instruction, from_x, from_y, to_x, to_y)?;
+
+        if !self.moved.is_empty() {
+            write!(f, " pushed")?;
+            for ((x, y), entity) in self.moved.iter() {
+                write!(f, " {}{},{}", entity.to_char(), x, y)?;
             }
-            println!();
         }
+
+        Ok(())
     }
 }
 
@@ -415,10 +490,53 @@ impl Position for (i32, i32) {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Entity {
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Entity {
     Wall,
-    Box,
-    LargeBoxLeft,
-    LargeBoxRight
-}
\ No newline at end of file
+    /// One tile of a box `width` tiles wide, `offset` tiles from its left edge.
+    Box { offset: i32, width: i32 }
+}
+
+impl Entity {
+    fn to_char(self) -> char {
+        match self {
+            Entity::Wall => '#',
+            Entity::Box { width: 1, .. } => 'O',
+            Entity::Box { offset: 0, .. } => '[',
+            Entity::Box { offset, width } if offset == width - 1 => ']',
+            Entity::Box { .. } => '='
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn scaled_boxes() {
+        let spec: WarehouseSimulationSpec = "\
+#######
+#.....#
+#..O..#
+#.OO..#
+#..@..#
+#######
+
+^^<"
+            .parse()
+            .unwrap();
+
+        let mut simulation = WarehouseSimulation::from_spec_scaled(&spec, 3);
+
+        let record = simulation.process_instruction(Instruction::Up);
+        assert_eq!(record.moved().len(), 6);
+        assert!(simulation.process_instruction(Instruction::Up).is_blocked());
+
+        let record = simulation.process_instruction(Instruction::Left);
+        assert_eq!(record.to_string(), "< 9,3 -> 8,3 pushed [6,3 =7,3 ]8,3");
+
+        assert_eq!(simulation.render().lines().nth(3), Some("###..[=]@.........###"));
+        assert_eq!(simulation.score(), 109 + 209 + 305);
+    }
+}
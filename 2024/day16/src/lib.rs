@@ -1,56 +1,30 @@
-use std::fs;
-use std::io;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use maze::MazeSimulation;
-use thiserror::Error;
+use maze::{Maze, MazeSimulation};
 
-mod maze;
-use maze::Maze;
+pub mod maze;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+pub struct Day;
 
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    let puzzle_input = fs::read_to_string(filename)?;
-
-    let maze: Maze = puzzle_input.parse().unwrap();
-
-    maze.print();
-    
-    let result = match options.part {
-        1 => run_part1(&maze),
-        2 => run_part2(&maze),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 16;
 
-fn run_part1(maze: &Maze) -> Result<usize, ApplicationError> {
-    let mut simulation = MazeSimulation::new(&maze);
-    let solution = simulation.simulate().ok_or(ApplicationError::CouldntFindSolution)?;
+    type Input = Maze;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Ok(solution.best_score().try_into().unwrap())
-}
+    fn part1(maze: &Maze) -> Result<usize, SolutionError> {
+        let mut simulation = MazeSimulation::new(maze);
+        let solution = simulation.simulate().ok_or(SolutionError::NoSolution)?;
 
-fn run_part2(maze: &Maze) -> Result<usize, ApplicationError> {
-    let mut simulation = MazeSimulation::new(&maze);
-    let solution = simulation.simulate().ok_or(ApplicationError::CouldntFindSolution)?;
-    
-    Ok(solution.best_path_tile_count())
-}
+        Ok(solution.best_score().try_into().unwrap())
+    }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't find solution")]
-    CouldntFindSolution
-}
\ No newline at end of file
+    fn part2(maze: &Maze) -> Result<usize, SolutionError> {
+        let mut simulation = MazeSimulation::new(maze);
+        let solution = simulation.simulate().ok_or(SolutionError::NoSolution)?;
+        
+        Ok(solution.best_path_tile_count())
+    }
+}
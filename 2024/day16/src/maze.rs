@@ -1,46 +1,39 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::str::FromStr;
 
+use aoc_grid::frames::{Frame, FrameSink, TextSink};
+use aoc_grid::{Direction, Grid, ParseGridError, Point};
+use aoc_search::dijkstra;
 use thiserror::Error;
 
 pub struct Maze {
-    start_pos: Position,
-    end_pos: Position,
-    width: i32,
-    height: i32,
-    tiles: HashMap<Position, Tile>
+    start_pos: Point,
+    end_pos: Point,
+    tiles: Grid<Tile>
 }
 
 impl Maze {
-    pub fn tile_at(&self, position: Position) -> Tile {
-        if let Some(tile) = self.tiles.get(&position) {
-            *tile
-        } else {
-            Tile::Wall
-        }
+    pub fn tile_at(&self, position: Point) -> Tile {
+        self.tiles.get(position).copied().unwrap_or(Tile::Wall)
     }
 
     pub fn print(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let pos = Position(x, y);
-                let char =
-                    if pos == self.start_pos {
-                        'S'
-                    } else if pos == self.end_pos {
-                        'E'
-                    } else {
-                        match self.tile_at(Position(x, y)) {
-                            Tile::Wall => '#',
-                            Tile::Floor => '.',
-                        }
-                    };
-                
-                print!("{}", char);
-            }
+        TextSink::stdout().write_frame(&self.frame(), 0.0).expect("couldn't write to stdout");
+    }
 
-            println!();
-        }
+    pub fn frame(&self) -> Frame {
+        Frame::from_grid(&self.tiles, 2, |pos, tile| {
+            if pos == self.start_pos {
+                ('S', 1)
+            } else if pos == self.end_pos {
+                ('E', 1)
+            } else {
+                match tile {
+                    Tile::Wall => ('#', 2),
+                    Tile::Floor => ('.', 0),
+                }
+            }
+        })
     }
 }
 
@@ -48,52 +41,18 @@ impl FromStr for Maze {
     type Err = ParseMazeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut start_pos = None;
-        let mut end_pos = None;
+        let chars: Grid<char> = s.parse()?;
 
-        let mut width = 0;
-        let mut height = 0;
+        let start_pos = chars.position(|&char| char == 'S').ok_or(ParseMazeError::MissingStartPoint)?;
+        let end_pos = chars.position(|&char| char == 'E').ok_or(ParseMazeError::MissingEndPoint)?;
 
-        let mut tiles = HashMap::new();
-
-        for (y, line) in s.lines().enumerate() {
-            let y: i32 = y.try_into().unwrap();
-            height = y + 1;
-
-            for (x, char) in line.chars().enumerate() {
-                let x: i32 = x.try_into().unwrap();
-                width = x + 1;
-
-                let pos = Position(x, y);
-                let tile = match char {
-                    '#' => Tile::Wall,
-                    '.' => Tile::Floor,
-                    'S' => {
-                        start_pos = Some(pos);
-                        Tile::Floor
-                    },
-                    'E' => {
-                        end_pos = Some(pos);
-                        Tile::Floor
-                    },
-                    _ => { 
-                        return Err(ParseMazeError::UnrecognizedCharacter(char));
-                    }
-                };
-
-                tiles.insert(pos, tile);
-            }
+        if let Some(&char) = chars.values().find(|char| !matches!(char, '#' | '.' | 'S' | 'E')) {
+            return Err(ParseMazeError::UnrecognizedCharacter(char));
         }
 
-        if let Some(start_pos) = start_pos {
-            if let Some(end_pos) = end_pos {
-                Ok(Maze { start_pos, end_pos, width, height, tiles })            
-            } else {
-                Err(ParseMazeError::MissingEndPosition)
-            }    
-        } else {
-            Err(ParseMazeError::MissingStartPosition)
-        }
+        let tiles = chars.map(|&char| if char == '#' { Tile::Wall } else { Tile::Floor });
+
+        Ok(Maze { start_pos, end_pos, tiles })
     }
 }
 
@@ -102,122 +61,57 @@ pub enum ParseMazeError {
     #[error("unrecognized character: {0}")]
     UnrecognizedCharacter(char),
     #[error("missing start position")]
-    MissingStartPosition,
+    MissingStartPoint,
     #[error("missing end position")]
-    MissingEndPosition
+    MissingEndPoint,
+    #[error("invalid maze: {0}")]
+    InvalidGrid(#[from] ParseGridError)
 }
 
 pub struct MazeSimulation<'a> {
-    maze: &'a Maze,
-    best_scores: HashMap<Position, u32>
+    maze: &'a Maze
 }
 
 impl<'a> MazeSimulation<'a> {
     pub fn new(maze: &'a Maze) -> Self {
-        let best_scores = HashMap::new();
-
-        MazeSimulation { maze, best_scores }
+        MazeSimulation { maze }
     }
 
     pub fn simulate(&mut self) -> Option<MazeSolution> {
-        let mut completed_walkers = vec![];
-        let mut walkers = vec![(self.maze.start_pos, Direction::East, 0, vec![self.maze.start_pos])];
-        
-        let mut best_score = u32::MAX;
-
-        while walkers.len() > 0 {
-            let mut next_walkers = vec![];
-
-            for (pos, facing, score, path) in walkers {
-                if pos == self.maze.end_pos {
-                    if score < best_score {
-                        best_score = score;
-                    }
-
-                    completed_walkers.push((pos, facing, score, path));
-                    continue;
-                }
-
-                for &direction in DIRECTIONS {
-                    if direction != facing.opposite() {
-                        let next_pos = pos.move_one(direction);
-                        
-                        let cost = if direction == facing {
-                            1
-                        } else {
-                            1001
-                        };
-                        let next_score = score + cost;
-
-                        if let Tile::Floor = self.maze.tile_at(next_pos) {
-                            let best_score_at_next_pos = self.best_scores.get(&next_pos)
-                                .cloned().unwrap_or(u32::MAX);
+        let start = (self.maze.start_pos, Direction::East);
+        let tree = dijkstra(start, |&(pos, facing)| {
+            let mut moves = vec![
+                ((pos, facing.rotate_clockwise()), 1000),
+                ((pos, facing.rotate_counterclockwise()), 1000)
+            ];
+
+            let next_pos = pos.step(facing);
+            if let Tile::Floor = self.maze.tile_at(next_pos) {
+                moves.push(((next_pos, facing), 1));
+            }
 
-                            let should_continue = if next_score < best_score_at_next_pos {
-                                self.best_scores.insert(next_pos, next_score);
-                                true
-                            } else if next_score < best_score_at_next_pos.saturating_add(1001) {
-                                true
-                            } else {
-                                false
-                            };
+            moves
+        });
 
-                            if should_continue {
-                                let mut next_path = path.clone();
-                                next_path.push(next_pos);
+        let end_states: Vec<_> = Direction::ALL.iter()
+            .map(|&facing| (self.maze.end_pos, facing))
+            .filter_map(|state| tree.cost(&state).map(|cost| (state, cost)))
+            .collect();
 
-                                next_walkers.push((next_pos, direction, next_score, next_path));
-                            }
-                        }
-                    }
-                }
-            }
+        let best_score = end_states.iter().map(|&(_, cost)| cost).min()?;
 
-            walkers = next_walkers;
-        }
+        let best_end_states = end_states.into_iter()
+            .filter(|&(_, cost)| cost == best_score)
+            .map(|(state, _)| state);
 
-        if let Some(best_score) = self.best_scores.get(&self.maze.end_pos).cloned() {
-            let mut all_best_path_tiles = HashSet::new();
-            
-            for (_, _, score, path) in completed_walkers {
-                if score == best_score {
-                    for pos in path {
-                        all_best_path_tiles.insert(pos);
-                    }
-                }
-            }
-        
-            let best_path_tile_count = all_best_path_tiles.len();
-            
-            Some(MazeSolution { best_score, best_path_tile_count })
-        } else {
-            None
-        }
-    }
+        let best_path_tiles: HashSet<_> = tree.states_on_paths_to(best_end_states)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect();
 
-    fn print(&self, all_best_paths: &HashSet<Position>) {
-        for y in 0..self.maze.height {
-            for x in 0..self.maze.width {
-                let pos = Position(x, y);
-                let char =
-                    if all_best_paths.contains(&pos) {
-                        'O'
-                    } else if pos == self.maze.start_pos {
-                        'S'
-                    } else if pos == self.maze.end_pos {
-                        'E'
-                    } else {
-                        match self.maze.tile_at(Position(x, y)) {
-                            Tile::Wall => '#',
-                            Tile::Floor => '.',
-                        }                        
-                    };
-                
-                print!("{}", char);
-            }
+        let best_path_tile_count = best_path_tiles.len();
 
-            println!();
-        }
+        Some(MazeSolution { best_score, best_path_tile_count })
     }
 }
 
@@ -241,59 +135,3 @@ pub enum Tile {
     Wall,
     Floor
 }
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West
-}
-
-static DIRECTIONS: &[Direction] = &[Direction::North, Direction::East, Direction::South, Direction::West];
-
-impl Direction {
-    pub fn opposite(&self) -> Direction {
-        match self {
-            Direction::North => Direction::South,
-            Direction::East => Direction::West,
-            Direction::South => Direction::North,
-            Direction::West => Direction::East
-        }
-    }
-
-    pub fn rotate_clockwise(&self) -> Direction {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North
-        }
-    }
-
-    pub fn rotate_counterclockwise(&self) -> Direction {
-        match self {
-            Direction::North => Direction::West,
-            Direction::East => Direction::North,
-            Direction::South => Direction::East,
-            Direction::West => Direction::South
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-pub struct Position(i32, i32);
-
-impl Position {
-    pub fn move_one(&self, direction: Direction) -> Position {
-        let x = self.0;
-        let y = self.1;
-
-        match direction {
-            Direction::North => Position(x, y - 1),
-            Direction::East => Position(x + 1, y),
-            Direction::South => Position(x, y + 1),
-            Direction::West => Position(x - 1, y)
-        }
-    }
-}
\ No newline at end of file
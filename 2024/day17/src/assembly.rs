@@ -0,0 +1,254 @@
+use std::num::ParseIntError;
+
+use thiserror::Error;
+
+use crate::computer::{ComboOperand, Instruction, ParseDebuggerInfoError};
+
+/// Lists the program one instruction per line, with its address and effect.
+pub fn disassemble(program: &[Instruction]) -> String {
+    let mut output = String::new();
+
+    for (i, instruction) in program.iter().enumerate() {
+        let line = format!("{:04}: {:<8} ; {}\n", i * 2, instruction.to_string(), instruction.describe());
+        output.push_str(&line);
+    }
+
+    output
+}
+
+/// Parses a listing in the format produced by [`disassemble`]. Addresses and `;` comments
+/// are optional.
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, AssembleError> {
+    let mut program = vec![];
+
+    for (i, line) in source.lines().enumerate() {
+        let line = line.split(';').next().unwrap_or_default();
+        let line = match line.split_once(':') {
+            Some((_, rest)) => rest,
+            None => line
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let instruction = assemble_instruction(line)
+            .map_err(|err| AssembleError::InvalidLine(i + 1, Box::new(err)))?;
+
+        program.push(instruction);
+    }
+
+    Ok(program)
+}
+
+fn assemble_instruction(line: &str) -> Result<Instruction, AssembleError> {
+    let mut words = line.split_whitespace();
+    let mnemonic = words.next().unwrap_or_default();
+    let mut operand = || words.next().ok_or(AssembleError::MissingOperand);
+
+    let instruction = match mnemonic {
+        "adv" => Instruction::Adv(parse_combo_operand(operand()?)?),
+        "bxl" => Instruction::Bxl(parse_literal_operand(operand()?)?),
+        "bst" => Instruction::Bst(parse_combo_operand(operand()?)?),
+        "jnz" => Instruction::Jnz(parse_literal_operand(operand()?)?),
+        "bxc" => Instruction::Bxc(parse_literal_operand(operand()?)?),
+        "out" => Instruction::Out(parse_combo_operand(operand()?)?),
+        "bdv" => Instruction::Bdv(parse_combo_operand(operand()?)?),
+        "cdv" => Instruction::Cdv(parse_combo_operand(operand()?)?),
+        _ => return Err(AssembleError::UnknownMnemonic(mnemonic.to_string()))
+    };
+
+    if let Some(extra) = words.next() {
+        return Err(AssembleError::UnexpectedOperand(extra.to_string()));
+    }
+
+    Ok(instruction)
+}
+
+fn parse_combo_operand(operand: &str) -> Result<ComboOperand, AssembleError> {
+    match operand {
+        "A" => Ok(ComboOperand::RegisterA),
+        "B" => Ok(ComboOperand::RegisterB),
+        "C" => Ok(ComboOperand::RegisterC),
+        _ => Ok(ComboOperand::decode(operand.parse()?)?)
+    }
+}
+
+fn parse_literal_operand(operand: &str) -> Result<usize, AssembleError> {
+    let value: usize = operand.parse()?;
+
+    if value < 8 {
+        Ok(value)
+    } else {
+        Err(AssembleError::OperandOutOfRange(value))
+    }
+}
+
+/// Writes the program in the comma-separated format used by the puzzle's `Program:` line.
+pub fn encode(program: &[Instruction]) -> String {
+    let values: Vec<String> = program.iter()
+        .flat_map(|instruction| {
+            let (opcode, operand) = instruction.encode();
+            [opcode.to_string(), operand.to_string()]
+        })
+        .collect();
+
+    values.join(",")
+}
+
+#[derive(Debug, Error)]
+pub enum AssembleError {
+    #[error("line {0}: {1}")]
+    InvalidLine(usize, Box<AssembleError>),
+    #[error("unknown mnemonic '{0}'")]
+    UnknownMnemonic(String),
+    #[error("missing operand")]
+    MissingOperand,
+    #[error("unexpected operand '{0}'")]
+    UnexpectedOperand(String),
+    #[error("operand {0} doesn't fit in 3 bits")]
+    OperandOutOfRange(usize),
+    #[error("couldn't parse operand: {0}")]
+    ParseIntError(#[from] ParseIntError),
+    #[error("{0}")]
+    InvalidOperand(#[from] ParseDebuggerInfoError)
+}
+
+/// The structure shared by most puzzle inputs: a single loop that ends in `jnz 0`, shifting A
+/// right by a constant amount once per iteration.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LoopShape {
+    shift: usize,
+    outputs_per_iteration: usize
+}
+
+impl LoopShape {
+    pub fn detect(program: &[Instruction]) -> Option<LoopShape> {
+        let (last, body) = program.split_last()?;
+        if !matches!(last, Instruction::Jnz(0)) {
+            return None;
+        }
+
+        if body.iter().any(|instruction| matches!(instruction, Instruction::Jnz(_))) {
+            return None;
+        }
+
+        let mut shifts = body.iter().filter_map(|instruction| match instruction {
+            Instruction::Adv(operand) => Some(*operand),
+            _ => None
+        });
+
+        let shift = match (shifts.next(), shifts.next()) {
+            (Some(ComboOperand::Literal(shift)), None) if shift > 0 => shift,
+            _ => return None
+        };
+
+        let outputs_per_iteration = body.iter()
+            .filter(|instruction| matches!(instruction, Instruction::Out(_)))
+            .count();
+
+        Some(LoopShape { shift, outputs_per_iteration })
+    }
+
+    /// Bits of A consumed per iteration.
+    pub fn shift(&self) -> usize {
+        self.shift
+    }
+
+    pub fn outputs_per_iteration(&self) -> usize {
+        self.outputs_per_iteration
+    }
+}
+
+/// Renders the program as pseudo-code. Programs with a recognised [`LoopShape`] become a
+/// `do`/`while` loop; anything else is listed statement by statement with `goto`s.
+pub fn decompile(program: &[Instruction]) -> String {
+    let mut output = String::new();
+
+    if let Some(shape) = LoopShape::detect(program) {
+        output.push_str(&format!(
+            "// shifts A right by {} bits and outputs {} value(s) per iteration\n",
+            shape.shift(),
+            shape.outputs_per_iteration()
+        ));
+        output.push_str("do {\n");
+
+        for instruction in &program[..program.len() - 1] {
+            output.push_str(&format!("    {}\n", instruction.describe()));
+        }
+
+        output.push_str("} while A != 0\n");
+    } else {
+        for (i, instruction) in program.iter().enumerate() {
+            output.push_str(&format!("{:04}: {}\n", i * 2, instruction.describe()));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::computer::DebuggerInfo;
+
+    use super::*;
+
+    fn parse_program(program: &str) -> Vec<Instruction> {
+        let debugger_info: DebuggerInfo = format!("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {}", program)
+            .parse()
+            .unwrap();
+
+        debugger_info.program().clone()
+    }
+
+    #[test]
+    pub fn round_trip() {
+        let original = "2,4,1,3,7,5,0,3,1,5,4,4,5,5,3,0";
+        let program = parse_program(original);
+
+        let listing = disassemble(&program);
+        assert!(listing.starts_with("0000: bst A    ; B = A % 8\n0002: bxl 3    ; B = B ^ 3\n"));
+
+        let reassembled = assemble(&listing).unwrap();
+        assert_eq!(encode(&reassembled), original);
+    }
+
+    #[test]
+    pub fn assemble_errors() {
+        assert!(matches!(assemble("adv A\nfoo 1"), Err(AssembleError::InvalidLine(2, _))));
+        assert!(matches!(assemble_instruction("bxl 8"), Err(AssembleError::OperandOutOfRange(8))));
+        assert!(matches!(assemble_instruction("out 7"), Err(AssembleError::InvalidOperand(_))));
+        assert!(matches!(assemble_instruction("out"), Err(AssembleError::MissingOperand)));
+    }
+
+    #[test]
+    pub fn decompile_loop() {
+        let program = parse_program("2,4,1,3,7,5,0,3,1,5,4,4,5,5,3,0");
+
+        assert_eq!(
+            LoopShape::detect(&program),
+            Some(LoopShape { shift: 3, outputs_per_iteration: 1 })
+        );
+        assert_eq!(decompile(&program), "\
+// shifts A right by 3 bits and outputs 1 value(s) per iteration
+do {
+    B = A % 8
+    B = B ^ 3
+    C = A >> B
+    A = A >> 3
+    B = B ^ 5
+    B = B ^ C
+    output(B % 8)
+} while A != 0
+");
+    }
+
+    #[test]
+    pub fn decompile_without_loop() {
+        let program = parse_program("5,4,0,4,3,0");
+
+        assert_eq!(LoopShape::detect(&program), None);
+        assert_eq!(decompile(&program), "0000: output(A % 8)\n0002: A = A >> A\n0004: if A != 0 goto 0000\n");
+    }
+}
@@ -1,61 +1,9 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 use thiserror::Error;
 
-pub fn search_for_quine(debugger_info: &DebuggerInfo) -> Option<usize> {
-    let original_program = debugger_info.original_program();
-    let len = original_program.len();
-
-    let mut solutions = vec![];
-
-    let mut walkers = vec![
-        Walker { i: 0, base_guess: 0 }
-    ];
-
-    while let Some(Walker { i, base_guess }) = walkers.pop() {
-        println!("{}, {}", i, base_guess);
-
-        if i < original_program.len() {
-            for n in 0..8 {
-                let guess = base_guess * 8 + n;
-    
-                let mut emulator = Emulator::from_debugger_info(&debugger_info);
-                *emulator.register_a_mut() = guess;
-    
-                while emulator.step() { }
-    
-                let output = emulator.output_buffer();
-                if output.eq(original_program) {
-                    solutions.push(guess);
-                } else {
-                    if output[0] == original_program[len - i - 1] {
-                        println!("{} for {:?}", guess, output);
-                        walkers.push(Walker { i: i + 1, base_guess: guess })
-                    }
-                }   
-            }
-        }
-    }
-    
-    println!("solutions found: {:?}", solutions);
-
-    if solutions.is_empty() {
-        None
-    } else {
-        solutions.sort();
-
-        Some(solutions[0])
-    }
-
-}
-
-struct Walker {
-    i: usize,
-    base_guess: usize
-}
-
 #[derive(Debug)]
 pub struct DebuggerInfo {
     register_a: usize,
@@ -110,30 +58,6 @@ impl FromStr for DebuggerInfo {
             }
         }
 
-        fn parse_combo_operand(operand: usize) -> Result<ComboOperand, ParseDebuggerInfoError> {
-            match operand {
-                0..=3 => Ok(ComboOperand::Literal(operand)),
-                4 => Ok(ComboOperand::RegisterA),
-                5 => Ok(ComboOperand::RegisterB),
-                6 => Ok(ComboOperand::RegisterC),
-                _ => Err(ParseDebuggerInfoError::UnrecognizedComboOperand(operand))
-            }
-        }
-
-        fn parse_instruction(opcode: usize, operand: usize) -> Result<Instruction, ParseDebuggerInfoError> {
-            match opcode {
-                0 => Ok(Instruction::Adv(parse_combo_operand(operand)?)),
-                1 => Ok(Instruction::Bxl(operand)),
-                2 => Ok(Instruction::Bst(parse_combo_operand(operand)?)),
-                3 => Ok(Instruction::Jnz(operand)),
-                4 => Ok(Instruction::Bxc),
-                5 => Ok(Instruction::Out(parse_combo_operand(operand)?)),
-                6 => Ok(Instruction::Bdv(parse_combo_operand(operand)?)),
-                7 => Ok(Instruction::Cdv(parse_combo_operand(operand)?)),
-                _ => Err(ParseDebuggerInfoError::UnrecognizedOpcode(opcode))
-            }
-        }
-
         let mut lines = s.lines();
         
         let register_a = parse_register_line("A", lines.next())?;
@@ -148,7 +72,7 @@ impl FromStr for DebuggerInfo {
         let mut program = vec![];
         for chunk in original_program.chunks_exact(2) {
             if let [opcode, operand] = chunk {
-                let instruction = parse_instruction(*opcode, *operand)?;
+                let instruction = Instruction::decode(*opcode, *operand)?;
                 program.push(instruction);
             }
         }
@@ -177,12 +101,74 @@ pub enum Instruction {
     Bxl(usize),
     Bst(ComboOperand),
     Jnz(usize),
-    Bxc,
+    /// The operand is read but ignored; it's kept so the program can be re-encoded exactly.
+    Bxc(usize),
     Out(ComboOperand),
     Bdv(ComboOperand),
     Cdv(ComboOperand)
 }
 
+impl Instruction {
+    pub fn decode(opcode: usize, operand: usize) -> Result<Instruction, ParseDebuggerInfoError> {
+        match opcode {
+            0 => Ok(Instruction::Adv(ComboOperand::decode(operand)?)),
+            1 => Ok(Instruction::Bxl(operand)),
+            2 => Ok(Instruction::Bst(ComboOperand::decode(operand)?)),
+            3 => Ok(Instruction::Jnz(operand)),
+            4 => Ok(Instruction::Bxc(operand)),
+            5 => Ok(Instruction::Out(ComboOperand::decode(operand)?)),
+            6 => Ok(Instruction::Bdv(ComboOperand::decode(operand)?)),
+            7 => Ok(Instruction::Cdv(ComboOperand::decode(operand)?)),
+            _ => Err(ParseDebuggerInfoError::UnrecognizedOpcode(opcode))
+        }
+    }
+
+    /// The opcode and operand pair this instruction was decoded from.
+    pub fn encode(&self) -> (usize, usize) {
+        match self {
+            Instruction::Adv(operand) => (0, operand.encode()),
+            Instruction::Bxl(operand) => (1, *operand),
+            Instruction::Bst(operand) => (2, operand.encode()),
+            Instruction::Jnz(operand) => (3, *operand),
+            Instruction::Bxc(operand) => (4, *operand),
+            Instruction::Out(operand) => (5, operand.encode()),
+            Instruction::Bdv(operand) => (6, operand.encode()),
+            Instruction::Cdv(operand) => (7, operand.encode())
+        }
+    }
+
+    /// The instruction's effect written as a pseudo-code statement.
+    pub fn describe(&self) -> String {
+        match self {
+            Instruction::Adv(operand) => format!("A = A >> {}", operand),
+            Instruction::Bxl(operand) => format!("B = B ^ {}", operand),
+            Instruction::Bst(ComboOperand::Literal(literal)) => format!("B = {}", literal),
+            Instruction::Bst(operand) => format!("B = {} % 8", operand),
+            Instruction::Jnz(operand) => format!("if A != 0 goto {:04}", operand),
+            Instruction::Bxc(_) => "B = B ^ C".to_string(),
+            Instruction::Out(ComboOperand::Literal(literal)) => format!("output({})", literal),
+            Instruction::Out(operand) => format!("output({} % 8)", operand),
+            Instruction::Bdv(operand) => format!("B = A >> {}", operand),
+            Instruction::Cdv(operand) => format!("C = A >> {}", operand)
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Adv(operand) => write!(f, "adv {}", operand),
+            Instruction::Bxl(operand) => write!(f, "bxl {}", operand),
+            Instruction::Bst(operand) => write!(f, "bst {}", operand),
+            Instruction::Jnz(operand) => write!(f, "jnz {}", operand),
+            Instruction::Bxc(operand) => write!(f, "bxc {}", operand),
+            Instruction::Out(operand) => write!(f, "out {}", operand),
+            Instruction::Bdv(operand) => write!(f, "bdv {}", operand),
+            Instruction::Cdv(operand) => write!(f, "cdv {}", operand)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ComboOperand {
     Literal(usize),
@@ -191,6 +177,88 @@ pub enum ComboOperand {
     RegisterC
 }
 
+impl ComboOperand {
+    pub fn decode(operand: usize) -> Result<ComboOperand, ParseDebuggerInfoError> {
+        match operand {
+            0..=3 => Ok(ComboOperand::Literal(operand)),
+            4 => Ok(ComboOperand::RegisterA),
+            5 => Ok(ComboOperand::RegisterB),
+            6 => Ok(ComboOperand::RegisterC),
+            _ => Err(ParseDebuggerInfoError::UnrecognizedComboOperand(operand))
+        }
+    }
+
+    pub fn encode(&self) -> usize {
+        match self {
+            ComboOperand::Literal(literal) => *literal,
+            ComboOperand::RegisterA => 4,
+            ComboOperand::RegisterB => 5,
+            ComboOperand::RegisterC => 6
+        }
+    }
+}
+
+impl Display for ComboOperand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComboOperand::Literal(literal) => write!(f, "{}", literal),
+            ComboOperand::RegisterA => write!(f, "{}", Register::A),
+            ComboOperand::RegisterB => write!(f, "{}", Register::B),
+            ComboOperand::RegisterC => write!(f, "{}", Register::C)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Register {
+    A,
+    B,
+    C
+}
+
+impl Register {
+    pub const ALL: [Register; 3] = [Register::A, Register::B, Register::C];
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Register::A => write!(f, "A"),
+            Register::B => write!(f, "B"),
+            Register::C => write!(f, "C")
+        }
+    }
+}
+
+impl FromStr for Register {
+    type Err = ParseRegisterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "A" => Ok(Register::A),
+            "B" => Ok(Register::B),
+            "C" => Ok(Register::C),
+            _ => Err(ParseRegisterError::UnknownRegister(s.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParseRegisterError {
+    #[error("unknown register '{0}'")]
+    UnknownRegister(String)
+}
+
+/// Parses a register value, accepting octal with a `0o` prefix.
+pub fn parse_value(s: &str) -> Result<usize, ParseIntError> {
+    if let Some(rest) = s.strip_prefix("0o") {
+        usize::from_str_radix(rest, 8)
+    } else {
+        s.parse()
+    }
+}
+
+#[derive(Clone)]
 pub struct Emulator {
     register_a: usize,
     register_b: usize,
@@ -221,10 +289,42 @@ impl Emulator {
         &mut self.register_a
     }
 
+    pub fn register(&self, register: Register) -> usize {
+        match register {
+            Register::A => self.register_a,
+            Register::B => self.register_b,
+            Register::C => self.register_c
+        }
+    }
+
+    pub fn register_mut(&mut self, register: Register) -> &mut usize {
+        match register {
+            Register::A => &mut self.register_a,
+            Register::B => &mut self.register_b,
+            Register::C => &mut self.register_c
+        }
+    }
+
     pub fn output_buffer(&self) -> &Vec<usize> {
         &self.output_buffer
     }
 
+    pub fn program(&self) -> &Vec<Instruction> {
+        &self.program
+    }
+
+    pub fn instruction_counter(&self) -> usize {
+        self.instruction_counter
+    }
+
+    pub fn current_instruction(&self) -> Option<Instruction> {
+        self.instruction_at(self.instruction_counter)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.current_instruction().is_none()
+    }
+
     pub fn step(&mut self) -> bool {
         if let Some(instruction) = self.instruction_at(self.instruction_counter) {
             match instruction {
@@ -232,7 +332,7 @@ impl Emulator {
                 Instruction::Bxl(operand) => self.bxl(operand),
                 Instruction::Bst(operand) => self.bst(operand),
                 Instruction::Jnz(operand) => self.jnz(operand),
-                Instruction::Bxc => self.bxc(),
+                Instruction::Bxc(_) => self.bxc(),
                 Instruction::Out(operand) => self.out(operand),
                 Instruction::Bdv(operand) => self.bdv(operand),
                 Instruction::Cdv(operand) => self.cdv(operand)
@@ -324,19 +424,19 @@ impl Emulator {
     }
 }
 
-fn run_program(program: &str) -> Result<Emulator, ParseDebuggerInfoError> {
-    let debugger_info: DebuggerInfo = program.trim().parse()?;
-    let mut emulator = Emulator::from_debugger_info(&debugger_info);
-
-    while emulator.step() { }
-
-    Ok(emulator)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn run_program(program: &str) -> Result<Emulator, ParseDebuggerInfoError> {
+        let debugger_info: DebuggerInfo = program.trim().parse()?;
+        let mut emulator = Emulator::from_debugger_info(&debugger_info);
+
+        while emulator.step() { }
+
+        Ok(emulator)
+    }
+
     #[test]
     pub fn example1() {
         let result = run_program("
@@ -0,0 +1,342 @@
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::computer::{parse_value, Emulator, ParseRegisterError, Register};
+
+const HELP: &str = "\
+commands:
+  step [n]          (s)  execute n instructions (default 1)
+  continue          (c)  run until a breakpoint, a watched register changes, or the program halts
+  break <address>   (b)  stop before executing the instruction at address
+  delete <address>  (d)  remove a breakpoint
+  watch <register>  (w)  stop whenever the register changes
+  unwatch <register>     stop watching a register
+  set <register> <value> change a register (0o prefix for octal)
+  registers         (r)  show all registers
+  output            (o)  show the output so far
+  list              (l)  disassemble the whole program
+  reset                  restart the program with the original registers
+  help              (h)  show this message
+  quit              (q)  leave the debugger
+an empty line repeats the previous command";
+
+pub struct Debugger {
+    initial_state: Emulator,
+    emulator: Emulator,
+    breakpoints: BTreeSet<usize>,
+    watches: BTreeSet<Register>
+}
+
+impl Debugger {
+    pub fn new(emulator: Emulator) -> Debugger {
+        let initial_state = emulator.clone();
+        let breakpoints = BTreeSet::new();
+        let watches = BTreeSet::new();
+
+        Debugger { initial_state, emulator, breakpoints, watches }
+    }
+
+    pub fn emulator(&self) -> &Emulator {
+        &self.emulator
+    }
+
+    /// Reads commands from `input` until it's exhausted or `quit` is entered.
+    pub fn repl(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        let mut last_command = None;
+
+        self.print_location(&mut output)?;
+        prompt(&mut output)?;
+
+        for line in input.lines() {
+            let line = line?;
+            let command = if line.trim().is_empty() {
+                match last_command {
+                    Some(command) => Ok(command),
+                    None => { prompt(&mut output)?; continue; }
+                }
+            } else {
+                line.parse::<DebuggerCommand>()
+            };
+
+            match command {
+                Ok(DebuggerCommand::Quit) => break,
+                Ok(command) => {
+                    self.execute(command, &mut output)?;
+                    last_command = Some(command);
+                },
+                Err(err) => writeln!(output, "{}", err)?
+            }
+
+            prompt(&mut output)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn execute(&mut self, command: DebuggerCommand, output: &mut impl Write) -> io::Result<()> {
+        match command {
+            DebuggerCommand::Step(count) => {
+                for _ in 0..count {
+                    if self.step(output)? != StepResult::Running {
+                        break;
+                    }
+                }
+
+                self.print_location(output)?;
+            },
+            DebuggerCommand::Continue => {
+                while self.step(output)? == StepResult::Running { }
+
+                self.print_location(output)?;
+            },
+            DebuggerCommand::Break(address) => {
+                self.breakpoints.insert(address);
+                writeln!(output, "breakpoint set at {:04}", address)?;
+            },
+            DebuggerCommand::Delete(address) => {
+                if self.breakpoints.remove(&address) {
+                    writeln!(output, "breakpoint at {:04} removed", address)?;
+                } else {
+                    writeln!(output, "no breakpoint at {:04}", address)?;
+                }
+            },
+            DebuggerCommand::Watch(register) => {
+                self.watches.insert(register);
+                writeln!(output, "watching {}", register)?;
+            },
+            DebuggerCommand::Unwatch(register) => {
+                self.watches.remove(&register);
+                writeln!(output, "no longer watching {}", register)?;
+            },
+            DebuggerCommand::Set(register, value) => {
+                *self.emulator.register_mut(register) = value;
+                self.print_register(register, output)?;
+            },
+            DebuggerCommand::Registers => {
+                for register in Register::ALL {
+                    self.print_register(register, output)?;
+                }
+            },
+            DebuggerCommand::Output => {
+                writeln!(output, "output: {}", self.output_string())?;
+            },
+            DebuggerCommand::List => {
+                let instruction_counter = self.emulator.instruction_counter();
+
+                for (i, instruction) in self.emulator.program().iter().enumerate() {
+                    let address = i * 2;
+                    let current = if address == instruction_counter { '>' } else { ' ' };
+                    let breakpoint = if self.breakpoints.contains(&address) { '*' } else { ' ' };
+
+                    writeln!(output, "{}{} {:04}: {}", current, breakpoint, address, instruction)?;
+                }
+            },
+            DebuggerCommand::Reset => {
+                self.emulator = self.initial_state.clone();
+                self.print_location(output)?;
+            },
+            DebuggerCommand::Help => {
+                writeln!(output, "{}", HELP)?;
+            },
+            DebuggerCommand::Quit => { }
+        }
+
+        Ok(())
+    }
+
+    fn step(&mut self, output: &mut impl Write) -> io::Result<StepResult> {
+        let watched_before: Vec<_> = self.watches.iter()
+            .map(|&register| (register, self.emulator.register(register)))
+            .collect();
+        let output_len = self.emulator.output_buffer().len();
+
+        if !self.emulator.step() {
+            return Ok(StepResult::Halted);
+        }
+
+        for value in self.emulator.output_buffer().iter().skip(output_len) {
+            writeln!(output, "out: {}", value)?;
+        }
+
+        let mut result = StepResult::Running;
+
+        for (register, before) in watched_before {
+            let after = self.emulator.register(register);
+            if after != before {
+                writeln!(output, "{} changed: {} -> {}", register, before, after)?;
+                result = StepResult::Stopped;
+            }
+        }
+
+        if self.breakpoints.contains(&self.emulator.instruction_counter()) {
+            writeln!(output, "breakpoint at {:04}", self.emulator.instruction_counter())?;
+            result = StepResult::Stopped;
+        }
+
+        if self.emulator.is_halted() {
+            result = StepResult::Halted;
+        }
+
+        Ok(result)
+    }
+
+    fn print_location(&self, output: &mut impl Write) -> io::Result<()> {
+        match self.emulator.current_instruction() {
+            Some(instruction) => {
+                writeln!(output, "{:04}: {}", self.emulator.instruction_counter(), instruction)?;
+            },
+            None => {
+                writeln!(output, "halted, output: {}", self.output_string())?;
+            }
+        }
+
+        for &register in self.watches.iter() {
+            self.print_register(register, output)?;
+        }
+
+        Ok(())
+    }
+
+    fn print_register(&self, register: Register, output: &mut impl Write) -> io::Result<()> {
+        let value = self.emulator.register(register);
+
+        writeln!(output, "  {} = {} (0o{:o})", register, value, value)
+    }
+
+    fn output_string(&self) -> String {
+        let output = self.emulator.output_buffer();
+        let output = output.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+
+        output.join(",")
+    }
+}
+
+fn prompt(output: &mut impl Write) -> io::Result<()> {
+    write!(output, "(debug) ")?;
+    output.flush()
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum StepResult {
+    Running,
+    Stopped,
+    Halted
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DebuggerCommand {
+    Step(usize),
+    Continue,
+    Break(usize),
+    Delete(usize),
+    Watch(Register),
+    Unwatch(Register),
+    Set(Register, usize),
+    Registers,
+    Output,
+    List,
+    Reset,
+    Help,
+    Quit
+}
+
+impl FromStr for DebuggerCommand {
+    type Err = ParseDebuggerCommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let command = words.next().ok_or(ParseDebuggerCommandError::Empty)?;
+        let mut argument = || words.next().ok_or(ParseDebuggerCommandError::MissingArgument(command.to_string()));
+
+        let command = match command {
+            "s" | "step" => {
+                let count = argument().map(parse_value).unwrap_or(Ok(1))?;
+                DebuggerCommand::Step(count)
+            },
+            "c" | "continue" => DebuggerCommand::Continue,
+            "b" | "break" => DebuggerCommand::Break(parse_value(argument()?)?),
+            "d" | "delete" => DebuggerCommand::Delete(parse_value(argument()?)?),
+            "w" | "watch" => DebuggerCommand::Watch(argument()?.parse()?),
+            "unwatch" => DebuggerCommand::Unwatch(argument()?.parse()?),
+            "set" => {
+                let register = argument()?.parse()?;
+                let value = parse_value(argument()?)?;
+
+                DebuggerCommand::Set(register, value)
+            },
+            "r" | "registers" => DebuggerCommand::Registers,
+            "o" | "output" => DebuggerCommand::Output,
+            "l" | "list" => DebuggerCommand::List,
+            "reset" => DebuggerCommand::Reset,
+            "h" | "help" => DebuggerCommand::Help,
+            "q" | "quit" => DebuggerCommand::Quit,
+            _ => return Err(ParseDebuggerCommandError::UnknownCommand(command.to_string()))
+        };
+
+        Ok(command)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParseDebuggerCommandError {
+    #[error("empty command")]
+    Empty,
+    #[error("unknown command '{0}' (try 'help')")]
+    UnknownCommand(String),
+    #[error("'{0}' needs an argument")]
+    MissingArgument(String),
+    #[error("couldn't parse number: {0}")]
+    ParseIntError(#[from] ParseIntError),
+    #[error("{0}")]
+    ParseRegisterError(#[from] ParseRegisterError)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::computer::DebuggerInfo;
+
+    use super::*;
+
+    fn debug(commands: &str) -> (Debugger, String) {
+        let debugger_info: DebuggerInfo = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0"
+            .parse()
+            .unwrap();
+        let mut debugger = Debugger::new(Emulator::from_debugger_info(&debugger_info));
+
+        let mut output = vec![];
+        debugger.repl(commands.as_bytes(), &mut output).unwrap();
+
+        (debugger, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    pub fn step_and_repeat() {
+        let (debugger, output) = debug("step\n\nstep 2\n");
+
+        assert!(output.starts_with("0000: adv 1\n"));
+        assert!(output.contains("out: 4\n"));
+        assert_eq!(debugger.emulator().instruction_counter(), 2);
+        assert_eq!(debugger.emulator().output_buffer(), &vec![4]);
+    }
+
+    #[test]
+    pub fn breakpoints_and_watches() {
+        let (debugger, output) = debug("break 4\ncontinue\ncontinue\ndelete 4\nwatch a\ncontinue\n");
+
+        assert!(output.contains("breakpoint at 0004\n"));
+        assert!(output.contains("A changed: 182 -> 91\n"));
+        assert_eq!(debugger.emulator().register(Register::A), 91);
+        assert_eq!(debugger.emulator().output_buffer(), &vec![4, 6]);
+    }
+
+    #[test]
+    pub fn continue_to_halt() {
+        let (_, output) = debug("set a 0o1\nc\nq\nstep\n");
+
+        assert!(output.ends_with("halted, output: 0\n(debug) "));
+    }
+}
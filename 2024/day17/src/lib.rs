@@ -4,13 +4,19 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use clap::Subcommand;
-use computer::search_for_quine;
+use assembly::{assemble, decompile, disassemble, encode};
+use computer::parse_value;
 use computer::DebuggerInfo;
 use computer::Emulator;
-use computer::ParseDebuggerInfoError;
-use thiserror::Error;
+use debugger::Debugger;
+use quine::search_for_quine;
 
-mod computer;
+use aoc_common::{solve, ApplicationError, Part, Solution, SolutionError};
+
+pub mod assembly;
+pub mod computer;
+pub mod debugger;
+pub mod quine;
 
 #[derive(Parser)]
 pub struct CliOptions {
@@ -28,53 +34,74 @@ enum Commands {
     },
     RunProgram {
         input: PathBuf,
-        register_a: String
+        #[arg(value_parser = parse_value)]
+        register_a: usize
+    },
+    /// Step through the program interactively
+    Debug {
+        input: PathBuf,
+
+        /// Override the initial value of register A (0o prefix for octal)
+        #[arg(long, value_parser = parse_value)]
+        register_a: Option<usize>
+    },
+    /// List the program with mnemonics
+    Disasm {
+        input: PathBuf
+    },
+    /// Convert a listing back into the comma-separated program format
+    Assemble {
+        source: PathBuf
+    },
+    /// Show the program as pseudo-code
+    Decompile {
+        input: PathBuf
     }
 }
 
-pub fn run(options: CliOptions) -> Result<(), ApplicationError> {
-    match options.command {
-        Commands::Part1 { input } => run_part1(input),
-        Commands::Part2 { input } => run_part2(input),
-        Commands::RunProgram { input, register_a } => run_program(input, register_a)
+pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
+    let result = match options.command {
+        Commands::Part1 { input } => solve::<Day>(Part::One, &fs::read_to_string(input)?),
+        Commands::Part2 { input } => solve::<Day>(Part::Two, &fs::read_to_string(input)?),
+        Commands::RunProgram { input, register_a } => run_program(input, register_a),
+        Commands::Debug { input, register_a } => debug(input, register_a),
+        Commands::Disasm { input } => Ok(disassemble(read_debugger_info(input)?.program())),
+        Commands::Assemble { source } => assemble_file(source),
+        Commands::Decompile { input } => Ok(decompile(read_debugger_info(input)?.program()))
     }?;
     
-    Ok(())
+    Ok(result)
 }
 
-fn run_part1(input: PathBuf) -> Result<(), ApplicationError> {
-    let puzzle_input = fs::read_to_string(input)?;
-    let debugger_info: DebuggerInfo = puzzle_input.parse()?;
+pub struct Day;
 
-    let mut emulator = Emulator::from_debugger_info(&debugger_info);
-    while emulator.step() { }
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 17;
 
-    let output = emulator.output_buffer();
-    let output = output.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+    type Input = DebuggerInfo;
+    type Answer1 = String;
+    type Answer2 = usize;
 
-    println!("{}", output.join(","));
+    fn part1(debugger_info: &DebuggerInfo) -> Result<String, SolutionError> {
+        let mut emulator = Emulator::from_debugger_info(debugger_info);
+        while emulator.step() { }
 
-    Ok(())
-}
+        let output = emulator.output_buffer();
+        let output = output.iter().map(|v| v.to_string()).collect::<Vec<String>>();
 
-fn run_part2(input: PathBuf) -> Result<(), ApplicationError> {
-    let puzzle_input = fs::read_to_string(input)?;
-    let debugger_info: DebuggerInfo = puzzle_input.parse()?;
-
-    if let Some(solution) = search_for_quine(&debugger_info) {
-        println!("{}", solution);
-        
-        Ok(())
-    } else {
-        Err(ApplicationError::CouldntFindSolution)
+        Ok(output.join(","))
     }
-}
 
-fn run_program(input: PathBuf, register_a: String) -> Result<(), ApplicationError> {
-    let puzzle_input = fs::read_to_string(input)?;
-    let debugger_info: DebuggerInfo = puzzle_input.parse()?;
+    fn part2(debugger_info: &DebuggerInfo) -> Result<usize, SolutionError> {
+        search_for_quine(debugger_info)?
+            .next()
+            .ok_or(SolutionError::NoSolution)
+    }
+}
 
-    let register_a = permissive_parse(register_a);
+fn run_program(input: PathBuf, register_a: usize) -> Result<String, ApplicationError> {
+    let debugger_info = read_debugger_info(input)?;
 
     let mut emulator = Emulator::from_debugger_info(&debugger_info);
     *emulator.register_a_mut() = register_a;
@@ -84,27 +111,35 @@ fn run_program(input: PathBuf, register_a: String) -> Result<(), ApplicationErro
     let output = emulator.output_buffer();
     let output = output.iter().map(|v| v.to_string()).collect::<Vec<String>>();
 
-    println!("{}", output.join(","));
-
-    Ok(())
+    Ok(output.join(","))
 }
 
-fn permissive_parse(n: String) -> usize {    
-    if let Some(rest) = n.strip_prefix("0o") {
-        usize::from_str_radix(&rest, 8).unwrap()
-    } else {
-        usize::from_str_radix(&n, 10).unwrap()
+fn debug(input: PathBuf, register_a: Option<usize>) -> Result<String, ApplicationError> {
+    let debugger_info = read_debugger_info(input)?;
+
+    let mut emulator = Emulator::from_debugger_info(&debugger_info);
+    if let Some(register_a) = register_a {
+        *emulator.register_a_mut() = register_a;
     }
+
+    let mut debugger = Debugger::new(emulator);
+    debugger.repl(io::stdin().lock(), io::stdout())?;
+
+    Ok("".to_string())
+}
+
+fn assemble_file(source: PathBuf) -> Result<String, ApplicationError> {
+    let source = fs::read_to_string(source)?;
+    let program = assemble(&source)
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
+
+    Ok(encode(&program))
 }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse debugger info: {0}")]
-    CouldntParsePuzzleInput(#[from] ParseDebuggerInfoError),
-    #[error("couldn't find solution")]
-    CouldntFindSolution
-}
\ No newline at end of file
+fn read_debugger_info(input: PathBuf) -> Result<DebuggerInfo, ApplicationError> {
+    let puzzle_input = fs::read_to_string(input)?;
+    let debugger_info = puzzle_input.parse::<DebuggerInfo>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
+
+    Ok(debugger_info)
+}
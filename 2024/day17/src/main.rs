@@ -11,4 +11,6 @@ fn main() {
         println!("Application error: {}", err);
         process::exit(1);
     });
+    
+    println!("{}", result);
 }
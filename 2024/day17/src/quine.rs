@@ -0,0 +1,413 @@
+use thiserror::Error;
+
+use aoc_common::SolutionError;
+
+use crate::assembly::LoopShape;
+use crate::computer::{ComboOperand, DebuggerInfo, Emulator, Instruction};
+
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// Finds every value of register A that makes the program output a copy of itself.
+pub fn search_for_quine(debugger_info: &DebuggerInfo) -> Result<QuineSolutions<'_>, QuineError> {
+    search_for_output(debugger_info, debugger_info.original_program())
+}
+
+/// Finds every value of register A that makes the program output `expected`.
+///
+/// The program is executed symbolically first to check that each iteration's output only
+/// depends on the low [`QuineAnalysis::window`] bits of A at the start of that iteration.
+/// That lets A be built from its most significant end, one iteration's shift at a time:
+/// the bits above each guess are already fixed, so only the iteration that reads the new
+/// bits has to be run to check it against `expected`.
+pub fn search_for_output<'a>(debugger_info: &'a DebuggerInfo, expected: &'a [usize]) -> Result<QuineSolutions<'a>, QuineError> {
+    let analysis = QuineAnalysis::analyze(debugger_info.program())?;
+
+    QuineSolutions::new(debugger_info, analysis, expected)
+}
+
+/// What a single iteration of the program's loop reads and writes.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct QuineAnalysis {
+    shift: usize,
+    output_dependencies: Vec<u64>
+}
+
+impl QuineAnalysis {
+    pub fn analyze(program: &[Instruction]) -> Result<QuineAnalysis, QuineError> {
+        let shape = LoopShape::detect(program).ok_or(QuineError::UnsupportedLoop)?;
+        if shape.outputs_per_iteration() == 0 {
+            return Err(QuineError::NoOutput);
+        }
+
+        let mut state = SymbolicState::new();
+        let mut output_dependencies = vec![];
+
+        for instruction in &program[..program.len() - 1] {
+            if let Some(output) = state.execute(instruction) {
+                if output.carried {
+                    return Err(QuineError::DependsOnPreviousIteration);
+                }
+
+                output_dependencies.push(output.a_bits);
+            }
+        }
+
+        Ok(QuineAnalysis { shift: shape.shift(), output_dependencies })
+    }
+
+    /// Bits of A consumed per iteration, and so the number of bits guessed at each step.
+    pub fn shift(&self) -> usize {
+        self.shift
+    }
+
+    /// For each `out` in the loop, a mask of the bits of A (as it was at the start of the
+    /// iteration) that the output value depends on.
+    pub fn output_dependencies(&self) -> &[u64] {
+        &self.output_dependencies
+    }
+
+    /// Number of low bits of A that an iteration looks at.
+    pub fn window(&self) -> usize {
+        let mask = self.output_dependencies.iter().fold(0, |mask, dependencies| mask | dependencies);
+
+        (u64::BITS - mask.leading_zeros()) as usize
+    }
+}
+
+/// Values of A in ascending order, found by a depth-first search over its bits.
+pub struct QuineSolutions<'a> {
+    debugger_info: &'a DebuggerInfo,
+    expected: &'a [usize],
+    shift: usize,
+    window_mask: usize,
+    outputs_per_iteration: usize,
+    iterations: usize,
+    stack: Vec<(usize, usize)>
+}
+
+impl<'a> QuineSolutions<'a> {
+    fn new(debugger_info: &'a DebuggerInfo, analysis: QuineAnalysis, expected: &'a [usize]) -> Result<QuineSolutions<'a>, QuineError> {
+        let shift = analysis.shift();
+        let window = analysis.window();
+        let window_mask = if window >= WORD_BITS { usize::MAX } else { (1 << window) - 1 };
+        let outputs_per_iteration = analysis.output_dependencies().len();
+        let iterations = expected.len() / outputs_per_iteration;
+
+        if shift * iterations > WORD_BITS {
+            return Err(QuineError::TooManyBits(shift * iterations));
+        }
+
+        // Each iteration outputs the same number of values, so other lengths can't be matched
+        let stack = if expected.len().is_multiple_of(outputs_per_iteration) { vec![(0, 0)] } else { vec![] };
+
+        Ok(QuineSolutions { debugger_info, expected, shift, window_mask, outputs_per_iteration, iterations, stack })
+    }
+
+    /// Whether the iteration that starts with A = `guess` outputs the values that come
+    /// `iterations` iterations from the end. The analysis says nothing above the window
+    /// matters, so only those bits are passed to the emulator.
+    fn iteration_matches(&self, guess: usize, iterations: usize) -> bool {
+        let mut emulator = Emulator::from_debugger_info(self.debugger_info);
+        *emulator.register_a_mut() = guess & self.window_mask;
+
+        while emulator.output_buffer().len() < self.outputs_per_iteration && emulator.step() { }
+
+        let start = self.expected.len() - iterations * self.outputs_per_iteration;
+
+        emulator.output_buffer()[..] == self.expected[start..start + self.outputs_per_iteration]
+    }
+}
+
+impl Iterator for QuineSolutions<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some((iterations, prefix)) = self.stack.pop() {
+            if iterations == self.iterations {
+                return Some(prefix);
+            }
+
+            // The top bits can't all be zero, or the loop would finish an iteration early
+            let first = if iterations == 0 && self.iterations > 1 { 1 } else { 0 };
+
+            // Pushed in reverse so the smallest guess is explored first, which keeps the
+            // solutions sorted
+            for n in (first..1 << self.shift).rev() {
+                let guess = (prefix << self.shift) | n;
+
+                if self.iteration_matches(guess, iterations + 1) {
+                    self.stack.push((iterations + 1, guess));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum QuineError {
+    #[error("the program isn't a single loop that shifts A by a constant amount")]
+    UnsupportedLoop,
+    #[error("the program never outputs anything")]
+    NoOutput,
+    #[error("the output depends on B or C from the previous iteration")]
+    DependsOnPreviousIteration,
+    #[error("A would need {0} bits")]
+    TooManyBits(usize)
+}
+
+impl From<QuineError> for SolutionError {
+    fn from(err: QuineError) -> SolutionError {
+        SolutionError::Unsolvable(err.to_string())
+    }
+}
+
+/// What a bit of a register is known about partway through an iteration.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum SymbolicBit {
+    Known(bool),
+    Unknown {
+        a_bits: u64,
+        carried: bool
+    }
+}
+
+impl SymbolicBit {
+    fn xor(self, other: SymbolicBit) -> SymbolicBit {
+        match (self, other) {
+            (SymbolicBit::Known(a), SymbolicBit::Known(b)) => SymbolicBit::Known(a ^ b),
+            (SymbolicBit::Known(_), unknown) | (unknown, SymbolicBit::Known(_)) => unknown,
+            (a, b) => a.merge(b)
+        }
+    }
+
+    /// A bit that could be either of these, e.g. depending on a shift amount.
+    fn merge(self, other: SymbolicBit) -> SymbolicBit {
+        match (self, other) {
+            (SymbolicBit::Known(a), SymbolicBit::Known(b)) if a == b => self,
+            _ => {
+                let (a_bits, carried) = self.dependencies();
+                let (other_a_bits, other_carried) = other.dependencies();
+
+                SymbolicBit::Unknown { a_bits: a_bits | other_a_bits, carried: carried || other_carried }
+            }
+        }
+    }
+
+    fn dependencies(self) -> (u64, bool) {
+        match self {
+            SymbolicBit::Known(_) => (0, false),
+            SymbolicBit::Unknown { a_bits, carried } => (a_bits, carried)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SymbolicValue([SymbolicBit; WORD_BITS]);
+
+impl SymbolicValue {
+    fn constant(value: usize) -> SymbolicValue {
+        SymbolicValue(std::array::from_fn(|i| SymbolicBit::Known(value >> i & 1 == 1)))
+    }
+
+    fn register_a() -> SymbolicValue {
+        SymbolicValue(std::array::from_fn(|i| SymbolicBit::Unknown { a_bits: 1 << i, carried: false }))
+    }
+
+    fn carried() -> SymbolicValue {
+        SymbolicValue([SymbolicBit::Unknown { a_bits: 0, carried: true }; WORD_BITS])
+    }
+
+    fn bit(&self, i: usize) -> SymbolicBit {
+        self.0.get(i).copied().unwrap_or(SymbolicBit::Known(false))
+    }
+
+    fn xor(&self, other: &SymbolicValue) -> SymbolicValue {
+        SymbolicValue(std::array::from_fn(|i| self.0[i].xor(other.0[i])))
+    }
+
+    fn low_bits(&self, count: usize) -> SymbolicValue {
+        SymbolicValue(std::array::from_fn(|i| if i < count { self.0[i] } else { SymbolicBit::Known(false) }))
+    }
+
+    /// Everything any bit of the value depends on.
+    fn combined(&self) -> SymbolicBit {
+        self.0.iter().fold(SymbolicBit::Known(false), |acc, bit| acc.merge(*bit))
+    }
+
+    /// The value shifted right by `amount`, which may itself be unknown. Each result bit
+    /// could come from any source bit within the range of possible shift amounts.
+    fn shift_right(&self, amount: &SymbolicValue) -> SymbolicValue {
+        let min_amount = (0..WORD_BITS)
+            .filter(|&i| amount.0[i] == SymbolicBit::Known(true))
+            .fold(0, |min, i| min | 1 << i)
+            .min(WORD_BITS);
+        let max_amount = (0..WORD_BITS)
+            .filter(|&i| amount.0[i] != SymbolicBit::Known(false))
+            .fold(0, |max, i| max | 1 << i)
+            .min(WORD_BITS);
+
+        let amount_dependencies = amount.combined();
+
+        SymbolicValue(std::array::from_fn(|i| {
+            let bit = (min_amount..=max_amount)
+                .map(|amount| self.bit(i + amount))
+                .reduce(SymbolicBit::merge)
+                .unwrap();
+
+            if min_amount == max_amount || matches!(bit, SymbolicBit::Known(_)) {
+                bit
+            } else {
+                bit.merge(amount_dependencies)
+            }
+        }))
+    }
+}
+
+struct SymbolicState {
+    register_a: SymbolicValue,
+    register_b: SymbolicValue,
+    register_c: SymbolicValue
+}
+
+impl SymbolicState {
+    fn new() -> SymbolicState {
+        SymbolicState {
+            register_a: SymbolicValue::register_a(),
+            register_b: SymbolicValue::carried(),
+            register_c: SymbolicValue::carried()
+        }
+    }
+
+    fn eval_operand(&self, operand: ComboOperand) -> SymbolicValue {
+        match operand {
+            ComboOperand::Literal(literal) => SymbolicValue::constant(literal),
+            ComboOperand::RegisterA => self.register_a,
+            ComboOperand::RegisterB => self.register_b,
+            ComboOperand::RegisterC => self.register_c
+        }
+    }
+
+    /// Applies a non-jump instruction, returning what the output depends on for `out`.
+    fn execute(&mut self, instruction: &Instruction) -> Option<OutputDependencies> {
+        match instruction {
+            Instruction::Adv(operand) => {
+                self.register_a = self.register_a.shift_right(&self.eval_operand(*operand));
+            },
+            Instruction::Bxl(operand) => {
+                self.register_b = self.register_b.xor(&SymbolicValue::constant(*operand));
+            },
+            Instruction::Bst(operand) => {
+                self.register_b = self.eval_operand(*operand).low_bits(3);
+            },
+            Instruction::Jnz(_) => { },
+            Instruction::Bxc(_) => {
+                self.register_b = self.register_b.xor(&self.register_c);
+            },
+            Instruction::Out(operand) => {
+                let (a_bits, carried) = self.eval_operand(*operand).low_bits(3).combined().dependencies();
+
+                return Some(OutputDependencies { a_bits, carried });
+            },
+            Instruction::Bdv(operand) => {
+                self.register_b = self.register_a.shift_right(&self.eval_operand(*operand));
+            },
+            Instruction::Cdv(operand) => {
+                self.register_c = self.register_a.shift_right(&self.eval_operand(*operand));
+            }
+        }
+
+        None
+    }
+}
+
+struct OutputDependencies {
+    a_bits: u64,
+    carried: bool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    fn parse(program: &str) -> DebuggerInfo {
+        format!("Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: {}", program)
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn example() {
+        let debugger_info = parse("0,3,5,4,3,0");
+
+        let solutions: Vec<_> = search_for_quine(&debugger_info).unwrap().collect();
+        assert_eq!(solutions.first(), Some(&117440));
+    }
+
+    #[test]
+    pub fn analysis() {
+        let debugger_info = parse("2,4,1,3,7,5,0,3,1,5,4,4,5,5,3,0");
+        let analysis = QuineAnalysis::analyze(debugger_info.program()).unwrap();
+
+        // B = A % 8 picks a shift of up to 7 for C, so the output sees bits 0 to 9
+        assert_eq!(analysis.shift(), 3);
+        assert_eq!(analysis.output_dependencies(), &[0b11_1111_1111]);
+        assert_eq!(analysis.window(), 10);
+
+        assert!(matches!(
+            QuineAnalysis::analyze(parse("5,5,0,3,3,0").program()),
+            Err(QuineError::DependsOnPreviousIteration)
+        ));
+        assert!(matches!(QuineAnalysis::analyze(parse("5,4,0,4,3,0").program()), Err(QuineError::UnsupportedLoop)));
+        assert!(matches!(
+            crate::Day::part2(&parse("5,4,0,4,3,0")),
+            Err(SolutionError::Unsolvable(message)) if message == QuineError::UnsupportedLoop.to_string()
+        ));
+    }
+
+    #[test]
+    pub fn all_solutions_sorted() {
+        let debugger_info = parse("2,4,1,3,7,5,0,3,1,5,4,4,5,5,3,0");
+
+        let solutions: Vec<_> = search_for_quine(&debugger_info).unwrap().collect();
+        assert!(!solutions.is_empty());
+        assert!(solutions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        for a in solutions {
+            let mut emulator = Emulator::from_debugger_info(&debugger_info);
+            *emulator.register_a_mut() = a;
+            while emulator.step() { }
+
+            assert_eq!(emulator.output_buffer(), debugger_info.original_program());
+        }
+    }
+
+    #[test]
+    pub fn window_wider_than_shift() {
+        // Shifts A by one bit per iteration, but C = A >> (A % 8 ^ 3) reads up to bit 9
+        let debugger_info = parse("2,4,1,3,7,5,4,0,5,5,0,1,3,0");
+        let analysis = QuineAnalysis::analyze(debugger_info.program()).unwrap();
+        assert_eq!(analysis.shift(), 1);
+        assert_eq!(analysis.window(), 10);
+
+        let run = |a| {
+            let mut emulator = Emulator::from_debugger_info(&debugger_info);
+            *emulator.register_a_mut() = a;
+            while emulator.step() { }
+
+            emulator.output_buffer().clone()
+        };
+
+        for a in [0b1011_0110_1101, 0b1000_0000_0001, 0b1111_1111_1111] {
+            let expected = run(a);
+
+            let solutions: Vec<_> = search_for_output(&debugger_info, &expected).unwrap().collect();
+            let brute_force: Vec<_> = (1 << 11..1 << 12).filter(|&a| run(a) == expected).collect();
+
+            assert!(solutions.contains(&a));
+            assert_eq!(solutions, brute_force);
+        }
+    }
+}
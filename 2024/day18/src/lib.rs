@@ -1,16 +1,13 @@
-use core::num;
 use std::fs;
-use std::io;
-use std::mem;
 
 use clap::Parser;
 use clap::Subcommand;
-use memory_space::MemorySpace;
-use memory_space::ParsePositionError;
 use memory_space::PuzzleInput;
-use thiserror::Error;
 
-mod memory_space;
+use aoc_common::params::{Param, ParamError, ParamOptions, Params};
+use aoc_common::{ApplicationError, Solution, SolutionError};
+
+pub mod memory_space;
 
 #[derive(Parser)]
 pub struct CliOptions {
@@ -22,73 +19,116 @@ pub struct CliOptions {
 enum Commands {
     Part1 {
         filename: std::path::PathBuf,
-        memory_space_size: usize,
-        num_bytes: usize
+
+        #[command(flatten)]
+        params: ParamOptions
     },
     Part2 {
         filename: std::path::PathBuf,
-        memory_space_size: usize
+
+        #[command(flatten)]
+        params: ParamOptions
     }
 }
 
+pub const PARAMS: &[Param] = &[
+    Param { name: "size", description: "width and height of the memory space", default: "71", example: "7" },
+    Param { name: "bytes", description: "bytes fallen before part 1's path is found", default: "1024", example: "12" }
+];
+
 pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
     let result = match options.command {
-        Commands::Part1 { filename, memory_space_size, num_bytes} =>
-            run_part1(filename, memory_space_size, num_bytes),
-        Commands::Part2 { filename, memory_space_size} =>
-            run_part2(filename, memory_space_size)
-    }?;
+        Commands::Part1 { filename, params } =>
+            Day::part1_with(&read_puzzle_input(filename)?, &params.resolve(PARAMS)?)?.to_string(),
+        Commands::Part2 { filename, params } =>
+            Day::part2_with(&read_puzzle_input(filename)?, &params.resolve(PARAMS)?)?
+    };
 
     Ok(result)
 }
 
-fn run_part1(filename: std::path::PathBuf, memory_space_size: usize, num_bytes: usize) -> Result<String, ApplicationError> {
-    let puzzle_input = read_puzzle_input(filename)?;
-    
-    let memory_space = puzzle_input.to_memory_space(memory_space_size, num_bytes);
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 18;
+
+    type Input = PuzzleInput;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    const PARAMS: &'static [Param] = PARAMS;
+
+    fn part1(puzzle_input: &PuzzleInput) -> Result<usize, SolutionError> {
+        Self::part1_with(puzzle_input, &Params::defaults(PARAMS))
+    }
+
+    fn part2(puzzle_input: &PuzzleInput) -> Result<String, SolutionError> {
+        Self::part2_with(puzzle_input, &Params::defaults(PARAMS))
+    }
+
+    fn part1_with(puzzle_input: &PuzzleInput, params: &Params) -> Result<usize, SolutionError> {
+        let memory_space_size = memory_space_size(puzzle_input, params)?;
+        let num_bytes: usize = params.get("bytes")?;
+
+        if num_bytes > puzzle_input.len() {
+            return Err(ParamError::DoesntFitInput(
+                format!("{} bytes requested but the input only has {}", num_bytes, puzzle_input.len())
+            ).into());
+        }
+
+        shortest_path(puzzle_input, memory_space_size, num_bytes)
+    }
 
-    if let Some(solution) = memory_space.solve() {
-        Ok(solution.to_string())
-    } else {
-        Err(ApplicationError::CouldntFindSolution)
+    fn part2_with(puzzle_input: &PuzzleInput, params: &Params) -> Result<String, SolutionError> {
+        first_blocking_byte(puzzle_input, memory_space_size(puzzle_input, params)?)
     }
 }
 
-fn run_part2(filename: std::path::PathBuf, memory_space_size: usize) -> Result<String, ApplicationError> {
-    let puzzle_input = read_puzzle_input(filename)?;
+fn memory_space_size(puzzle_input: &PuzzleInput, params: &Params) -> Result<usize, ParamError> {
+    let memory_space_size: usize = params.get("size")?;
 
-    for n in 1..puzzle_input.len() {
-        println!("{}", n);
+    let limit = i32::try_from(memory_space_size)
+        .map_err(|_| ParamError::InvalidValue("size".to_string(), memory_space_size.to_string()))?;
 
+    let outside = (0..puzzle_input.len())
+        .filter_map(|i| puzzle_input.get(i))
+        .find(|byte| !(0..limit).contains(&byte.x) || !(0..limit).contains(&byte.y));
+
+    if let Some(byte) = outside {
+        return Err(ParamError::DoesntFitInput(
+            format!("byte at {},{} is outside a memory space of size {}", byte.x, byte.y, memory_space_size)
+        ));
+    }
+
+    Ok(memory_space_size)
+}
+
+fn shortest_path(puzzle_input: &PuzzleInput, memory_space_size: usize, num_bytes: usize) -> Result<usize, SolutionError> {
+    let memory_space = puzzle_input.to_memory_space(memory_space_size, num_bytes);
+
+    memory_space.solve().ok_or(SolutionError::NoSolution)
+}
+
+fn first_blocking_byte(puzzle_input: &PuzzleInput, memory_space_size: usize) -> Result<String, SolutionError> {
+    for n in 1..puzzle_input.len() {
         let memory_space = puzzle_input.to_memory_space(memory_space_size, n);
-        if let None = memory_space.solve() {
-            if let Some(position) = puzzle_input.get(n - 1) {
-                let x = position.x();
-                let y = position.y();
+        if memory_space.solve().is_none()
+            && let Some(position) = puzzle_input.get(n - 1) {
+            let x = position.x;
+            let y = position.y;
 
-                return Ok(format!("{},{}", x, y));
-            }
+            return Ok(format!("{},{}", x, y));
         }
     }
 
-    Err(ApplicationError::CouldntFindSolution)
+    Err(SolutionError::NoSolution)
 }
 
 fn read_puzzle_input(filename: std::path::PathBuf) -> Result<PuzzleInput, ApplicationError> {
     let puzzle_input = fs::read_to_string(filename)?;
-    let puzzle_input: PuzzleInput = puzzle_input.parse()?;
+    let puzzle_input = puzzle_input.parse::<PuzzleInput>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
     
     Ok(puzzle_input)
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse puzzle input: {0}")]
-    CouldntParseInput(#[from] ParsePositionError),
-    #[error("couldn't find solution")]
-    CouldntFindSolution
-}
\ No newline at end of file
@@ -1,75 +1,206 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::str::FromStr;
 
 use thiserror::Error;
 
+use crate::trie::TowelTrie;
+
 pub struct TowelSolver<'a> {
-    towels: HashSet<String>,
-    largest_towel: usize,
-    orders: &'a Vec<String>
+    towels: &'a [String],
+    trie: TowelTrie,
+    orders: &'a [String]
 }
 
 impl<'a> TowelSolver<'a> {
-    pub fn new(towels: &'a Vec<String>, orders: &'a Vec<String>) -> TowelSolver<'a> {       
-        let towels: HashSet<_> = towels.iter().cloned().collect();
-        let largest_towel = towels.iter().max_by_key(|t| t.len()).unwrap().len();
-        
-        TowelSolver { towels, largest_towel, orders }
+    pub fn new(towels: &'a [String], orders: &'a [String]) -> TowelSolver<'a> {
+        TowelSolver { towels, trie: TowelTrie::new(towels), orders }
     }
 
-    pub fn solve(&mut self) -> Vec<(String, usize)> {
-        let mut result = vec![];
+    pub fn solve(&self) -> Vec<CompletedOrder> {
+        self.orders.iter()
+            .map(|order| CompletedOrder { order: order.to_string(), combinations: self.solve_order(order) })
+            .collect()
+    }
 
-        for order in self.orders.iter() {
-            let unique_combinations = self.solve_order(order);
-            result.push((order.to_string(), unique_combinations))
-        }
-        
-        result
+    /// The number of ways to arrange towels into `order`.
+    pub fn solve_order(&self, order: &str) -> usize {
+        self.ways_to_finish(order.as_bytes())[0]
     }
 
-    fn solve_order(&self, order: &str) -> usize {
-        let mut walkers = HashMap::new();
-        walkers.insert(0, 1);
-        
-        let mut unique_combinations = 0;
+    /// For each position, the number of ways to arrange towels into the rest of the order.
+    fn ways_to_finish(&self, order: &[u8]) -> Vec<usize> {
+        let mut ways = vec![0; order.len() + 1];
+        ways[order.len()] = 1;
 
-        while walkers.len() > 0 {
-            let mut next_walkers = HashMap::new();
+        for start in (0..order.len()).rev() {
+            ways[start] = self.trie.matches(order, start).map(|(end, _)| ways[end]).sum();
+        }
 
-            for (i, count) in walkers {
-                if i == order.len() {
-                    unique_combinations += count;
-                    continue;
+        ways
+    }
+
+    /// For each position, whether towels can be arranged into the order up to there.
+    fn reachable(&self, order: &[u8]) -> Vec<bool> {
+        let mut reachable = vec![false; order.len() + 1];
+        reachable[0] = true;
+
+        for start in 0..order.len() {
+            if reachable[start] {
+                for (end, _) in self.trie.matches(order, start) {
+                    reachable[end] = true;
                 }
+            }
+        }
+
+        reachable
+    }
+
+    /// Every arrangement of towels making `order`, produced one at a time. Dead ends are
+    /// never explored, so each step finds another arrangement quickly even when there are
+    /// far too many to collect.
+    pub fn arrangements<'s>(&'s self, order: &'s str) -> Arrangements<'s> {
+        let can_finish = self.ways_to_finish(order.as_bytes()).iter().map(|ways| *ways > 0).collect();
+
+        Arrangements { solver: self, order: order.as_bytes(), can_finish, stack: vec![], path: vec![], started: false }
+    }
+
+    /// An arrangement of `order` using as few towels as possible, if there is one.
+    pub fn fewest_towels(&self, order: &str) -> Option<Vec<&'a str>> {
+        self.extreme_arrangement(order, |count, best| count < best)
+    }
 
-                for n in 1..=self.largest_towel {
-                    let next_i = i + n;
+    /// An arrangement of `order` using as many towels as possible, if there is one.
+    pub fn most_towels(&self, order: &str) -> Option<Vec<&'a str>> {
+        self.extreme_arrangement(order, |count, best| count > best)
+    }
+
+    fn extreme_arrangement(&self, order: &str, is_better: fn(usize, usize) -> bool) -> Option<Vec<&'a str>> {
+        let order = order.as_bytes();
+
+        // For each position, the best towel count for the rest of the order and the first
+        // towel to use
+        let mut best: Vec<Option<(usize, usize, usize)>> = vec![None; order.len() + 1];
+        best[order.len()] = Some((0, 0, order.len()));
 
-                    if next_i > order.len() {
-                        break;
+        for start in (0..order.len()).rev() {
+            for (end, towel) in self.trie.matches(order, start) {
+                if let Some((count, _, _)) = best[end] {
+                    let count = count + 1;
+
+                    if best[start].is_none_or(|(best_count, _, _)| is_better(count, best_count)) {
+                        best[start] = Some((count, towel, end));
                     }
+                }
+            }
+        }
+
+        best[0]?;
+
+        let mut arrangement = vec![];
+        let mut position = 0;
 
-                    let potential_towel = &order[i..next_i];
-                    if self.towels.contains(potential_towel) {
-                        next_walkers.entry(next_i)
-                            .and_modify(|c| *c += count)
-                            .or_insert(count);
+        while let Some((_, towel, end)) = best[position] && position < order.len() {
+            arrangement.push(self.towels[towel].as_str());
+            position = end;
+        }
+
+        Some(arrangement)
+    }
+
+    /// Towels that aren't part of any arrangement of any order, in the order they were given.
+    pub fn unneeded_towels(&self) -> Vec<&'a str> {
+        let mut used = vec![false; self.towels.len()];
+
+        for order in self.orders.iter() {
+            let order = order.as_bytes();
+            let reachable = self.reachable(order);
+            let ways_to_finish = self.ways_to_finish(order);
+
+            for start in (0..order.len()).filter(|&start| reachable[start]) {
+                for (end, towel) in self.trie.matches(order, start) {
+                    if ways_to_finish[end] > 0 {
+                        used[towel] = true;
                     }
                 }
             }
+        }
 
-            walkers = next_walkers;
+        let mut seen = HashSet::new();
+
+        self.towels.iter()
+            .enumerate()
+            .filter(|(_, towel)| !towel.is_empty() && seen.insert(towel.as_str()))
+            .filter(|(index, _)| !used[*index])
+            .map(|(_, towel)| towel.as_str())
+            .collect()
+    }
+}
+
+pub struct Arrangements<'s> {
+    solver: &'s TowelSolver<'s>,
+    order: &'s [u8],
+    can_finish: Vec<bool>,
+    stack: Vec<(Vec<(usize, usize)>, usize)>,
+    path: Vec<usize>,
+    started: bool
+}
+
+impl Arrangements<'_> {
+    fn push_position(&mut self, start: usize) {
+        let matches = self.solver.trie.matches(self.order, start)
+            .filter(|(end, _)| self.can_finish[*end])
+            .collect();
+
+        self.stack.push((matches, 0));
+    }
+}
+
+impl<'s> Iterator for Arrangements<'s> {
+    type Item = Vec<&'s str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+
+            if !self.can_finish[0] {
+                return None;
+            }
+
+            // The empty order is made with no towels at all, just once
+            if self.order.is_empty() {
+                return Some(vec![]);
+            }
+
+            self.push_position(0);
         }
 
-        unique_combinations
-    }    
+        loop {
+            let (matches, next) = self.stack.last_mut()?;
+
+            if let Some(&(end, towel)) = matches.get(*next) {
+                *next += 1;
+                self.path.push(towel);
+
+                if end == self.order.len() {
+                    let arrangement = self.path.iter().map(|&towel| self.solver.towels[towel].as_str()).collect();
+                    self.path.pop();
+
+                    return Some(arrangement);
+                }
+
+                self.push_position(end);
+            } else {
+                self.stack.pop();
+                self.path.pop();
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct CompletedOrder {
     order: String,
-    combinations: Vec<Vec<String>>
+    combinations: usize
 }
 
 impl CompletedOrder {
@@ -77,12 +208,12 @@ impl CompletedOrder {
         &self.order
     }
 
-    pub fn combinations(&self) -> &Vec<Vec<String>> {
-        &self.combinations
+    pub fn combinations(&self) -> usize {
+        self.combinations
     }
 
     pub fn is_possible(&self) -> bool {
-        self.combinations.len() > 0
+        self.combinations > 0
     }
 }
 
@@ -93,7 +224,7 @@ pub struct PuzzleInput {
 }
 
 impl PuzzleInput {
-    pub fn to_solver(&self) -> TowelSolver {
+    pub fn to_solver(&self) -> TowelSolver<'_> {
         TowelSolver::new(&self.towels, &self.orders)
     }
 }
@@ -151,4 +282,26 @@ mod tests {
             orders: ["brwrr", "bggr", "gbbr", "rrbgbr", "ubwu", "bwurrg", "brgr", "bbrgwb"].iter().map(|s| s.to_string()).collect()
         });
     }
+
+    #[test]
+    pub fn arrangements() {
+        let puzzle_input: PuzzleInput = "r, wr, b, g, bwu, rb, gb, br, rbg, r, bw, ww\n\nbrwrr\nrrbgbr\nubwu\nbwurrg".parse().unwrap();
+        let solver = puzzle_input.to_solver();
+
+        let mut brwrr: Vec<Vec<&str>> = solver.arrangements("brwrr").collect();
+        brwrr.sort();
+        assert_eq!(brwrr, vec![vec!["b", "r", "wr", "r"], vec!["br", "wr", "r"]]);
+
+        assert_eq!(solver.arrangements("").collect::<Vec<_>>(), vec![Vec::<&str>::new()]);
+
+        for order in ["rrbgbr", "ubwu", "bwurrg", ""] {
+            assert_eq!(solver.arrangements(order).count(), solver.solve_order(order));
+        }
+
+        assert_eq!(solver.fewest_towels("rrbgbr"), Some(vec!["r", "rbg", "br"]));
+        assert_eq!(solver.most_towels("rrbgbr"), Some(vec!["r", "r", "b", "g", "b", "r"]));
+        assert_eq!(solver.fewest_towels("ubwu"), None);
+
+        assert_eq!(solver.unneeded_towels(), vec!["bw", "ww"]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+/// A prefix tree over the towel patterns. Finding every towel that starts at a position is
+/// one walk down the tree, rather than a set lookup for every length up to the longest towel.
+pub struct TowelTrie {
+    nodes: Vec<TrieNode>
+}
+
+#[derive(Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    towel: Option<usize>
+}
+
+impl TowelTrie {
+    /// Towels are identified by their index in `towels`. Empty towels are left out, and a
+    /// repeated towel keeps the index of its first appearance.
+    pub fn new(towels: &[String]) -> TowelTrie {
+        let mut trie = TowelTrie { nodes: vec![TrieNode::default()] };
+
+        for (index, towel) in towels.iter().enumerate().filter(|(_, towel)| !towel.is_empty()) {
+            let mut node = 0;
+
+            for &stripe in towel.as_bytes() {
+                node = match trie.child(node, stripe) {
+                    Some(child) => child,
+                    None => {
+                        trie.nodes.push(TrieNode::default());
+                        let child = trie.nodes.len() - 1;
+                        trie.nodes[node].children.push((stripe, child));
+
+                        child
+                    }
+                };
+            }
+
+            trie.nodes[node].towel.get_or_insert(index);
+        }
+
+        trie
+    }
+
+    fn child(&self, node: usize, stripe: u8) -> Option<usize> {
+        self.nodes[node].children.iter()
+            .find(|(child_stripe, _)| *child_stripe == stripe)
+            .map(|(_, child)| *child)
+    }
+
+    /// Every towel matching `order` at `start`, shortest first, as `(end, towel index)`.
+    pub fn matches<'t>(&'t self, order: &'t [u8], start: usize) -> impl Iterator<Item = (usize, usize)> + 't {
+        let mut node = Some(0);
+        let mut end = start;
+
+        std::iter::from_fn(move || {
+            while let Some(current) = node {
+                let stripe = order.get(end)?;
+                node = self.child(current, *stripe);
+                end += 1;
+
+                if let Some(towel) = node.and_then(|node| self.nodes[node].towel) {
+                    return Some((end, towel));
+                }
+            }
+
+            None
+        })
+    }
+}
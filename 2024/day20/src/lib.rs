@@ -1,55 +1,43 @@
-use std::fs;
-use std::io;
+use aoc_common::params::{Param, Params};
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
 use maze::{Maze, MazeSimulation};
-use thiserror::Error;
 
-mod maze;
+pub mod maze;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+pub const PARAMS: &[Param] = &[
+    Param { name: "threshold", description: "picoseconds a cheat has to save to be counted", default: "100", example: "50" }
+];
 
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    let puzzle_input = fs::read_to_string(filename)?;
-
-    let maze: Maze = puzzle_input.parse().unwrap();
-
-    maze.print();
-    
-    let result = match options.part {
-        1 => run_part1(&maze),
-        2 => run_part2(&maze),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+pub struct Day;
 
-fn run_part1(maze: &Maze) -> Result<usize, ApplicationError> {
-    let mut simulation = MazeSimulation::new(&maze);
-    let solution = simulation.simulate(100, 2).ok_or(ApplicationError::CouldntFindSolution)?;
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 20;
 
-    Ok(solution)
-}
+    type Input = Maze;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn run_part2(maze: &Maze) -> Result<usize, ApplicationError> {
-    let mut simulation = MazeSimulation::new(&maze);
-    let solution = simulation.simulate(100, 20).ok_or(ApplicationError::CouldntFindSolution)?;
+    const PARAMS: &'static [Param] = PARAMS;
 
-    Ok(solution)
-}
+    fn part1(maze: &Maze) -> Result<usize, SolutionError> {
+        Self::part1_with(maze, &Params::defaults(PARAMS))
+    }
+
+    fn part2(maze: &Maze) -> Result<usize, SolutionError> {
+        Self::part2_with(maze, &Params::defaults(PARAMS))
+    }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't find solution")]
-    CouldntFindSolution
-}
\ No newline at end of file
+    fn part1_with(maze: &Maze, params: &Params) -> Result<usize, SolutionError> {
+        let mut simulation = MazeSimulation::new(maze);
+
+        simulation.simulate(params.get("threshold")?, 2).ok_or(SolutionError::NoSolution)
+    }
+
+    fn part2_with(maze: &Maze, params: &Params) -> Result<usize, SolutionError> {
+        let mut simulation = MazeSimulation::new(maze);
+
+        simulation.simulate(params.get("threshold")?, 20).ok_or(SolutionError::NoSolution)
+    }
+}
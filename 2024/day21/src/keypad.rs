@@ -1,5 +1,34 @@
-use std::collections::{HashMap, HashSet};
-use std::usize;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use aoc_grid::{Direction, Point};
+use aoc_search::bfs;
+
+#[derive(Debug)]
+pub struct DoorCodes(Vec<String>);
+
+impl FromStr for DoorCodes {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let codes = s.lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        Ok(DoorCodes(codes))
+    }
+}
+
+impl Deref for DoorCodes {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
 
 pub fn solve(code: &str, directional_keypad_count: usize) -> usize {
     let mut keypads = vec![
@@ -15,12 +44,8 @@ pub fn solve(code: &str, directional_keypad_count: usize) -> usize {
     let solution = solve_segment(code, &keypads, 0, &mut cache);
 
     let value = code_numeric_value(code).unwrap();
-    let complexity = solution * value;
-
-    println!("{}: {}", code, solution);
-    println!("{}: {} * {} = {}", code, solution, value, complexity);
 
-    complexity
+    solution * value
 }
 
 fn solve_segment(code: &str, keypads: &Vec<Keypad>, keypad_i: usize, cache: &mut HashMap<(usize, String), usize>) -> usize {
@@ -29,7 +54,7 @@ fn solve_segment(code: &str, keypads: &Vec<Keypad>, keypad_i: usize, cache: &mut
         *value
     } else {
         let value = if let Some(keypad) = keypads.get(keypad_i) {
-            let solution = keypad.solve_code(&code);
+            let solution = keypad.solve_code(code);
             let mut accum = 0;
     
             for segment in solution {
@@ -72,7 +97,7 @@ pub fn simulate(code: &str) -> Option<String> {
 }
 
 fn code_numeric_value(code: &str) -> Option<usize> {
-    let digits: String = code.chars().filter(|c| c.is_digit(10)).collect();
+    let digits: String = code.chars().filter(|c| c.is_ascii_digit()).collect();
     digits.parse().ok()
 }
 
@@ -97,28 +122,28 @@ fn path_to_code(path: &Vec<Direction>) -> String {
 
 #[derive(Debug)]
 pub struct Keypad {
-    pos_to_key: HashMap<Position, char>,
-    key_to_pos: HashMap<char, Position>
+    pos_to_key: HashMap<Point, char>,
+    key_to_pos: HashMap<char, Point>
 }
 
 impl Keypad {
     pub fn numeric_keypad() -> Keypad {
         let mut keys = HashMap::new();
         
-        keys.insert(Position(0, 0), '7');
-        keys.insert(Position(1, 0), '8');
-        keys.insert(Position(2, 0), '9');
+        keys.insert(Point::new(0, 0), '7');
+        keys.insert(Point::new(1, 0), '8');
+        keys.insert(Point::new(2, 0), '9');
 
-        keys.insert(Position(0, 1), '4');
-        keys.insert(Position(1, 1), '5');
-        keys.insert(Position(2, 1), '6');
+        keys.insert(Point::new(0, 1), '4');
+        keys.insert(Point::new(1, 1), '5');
+        keys.insert(Point::new(2, 1), '6');
 
-        keys.insert(Position(0, 2), '1');
-        keys.insert(Position(1, 2), '2');
-        keys.insert(Position(2, 2), '3');
+        keys.insert(Point::new(0, 2), '1');
+        keys.insert(Point::new(1, 2), '2');
+        keys.insert(Point::new(2, 2), '3');
 
-        keys.insert(Position(1, 3), '0');
-        keys.insert(Position(2, 3), 'A');
+        keys.insert(Point::new(1, 3), '0');
+        keys.insert(Point::new(2, 3), 'A');
 
         Keypad::from_positions_to_keys(keys)
     }
@@ -126,17 +151,17 @@ impl Keypad {
     pub fn directional_keypad() -> Keypad {
         let mut keys = HashMap::new();
         
-        keys.insert(Position(1, 0), '^');
-        keys.insert(Position(2, 0), 'A');
+        keys.insert(Point::new(1, 0), '^');
+        keys.insert(Point::new(2, 0), 'A');
 
-        keys.insert(Position(0, 1), '<');
-        keys.insert(Position(1, 1), 'v');
-        keys.insert(Position(2, 1), '>');
+        keys.insert(Point::new(0, 1), '<');
+        keys.insert(Point::new(1, 1), 'v');
+        keys.insert(Point::new(2, 1), '>');
 
         Keypad::from_positions_to_keys(keys)
     }
 
-    fn from_positions_to_keys(pos_to_key: HashMap<Position, char>) -> Keypad {
+    fn from_positions_to_keys(pos_to_key: HashMap<Point, char>) -> Keypad {
         let key_to_pos: HashMap<_, _> = pos_to_key
             .iter()
             .map(|(&k, &v)| (v, k))
@@ -145,11 +170,11 @@ impl Keypad {
         Keypad { pos_to_key, key_to_pos }
     }
 
-    pub fn get_pos_for_key(&self, key: char) -> Option<Position> {
+    pub fn get_pos_for_key(&self, key: char) -> Option<Point> {
         self.key_to_pos.get(&key).cloned()
     }
 
-    pub fn get_key_for_pos(&self, pos: &Position) -> Option<char> {
+    pub fn get_key_for_pos(&self, pos: &Point) -> Option<char> {
         self.pos_to_key.get(pos).cloned()
     }
 
@@ -159,10 +184,10 @@ impl Keypad {
         let mut current_pos = self.get_pos_for_key('A').unwrap();
         for char in code.chars() {
             let next_pos = match char {
-                '^' => current_pos.move_one(Direction::North),
-                '>' => current_pos.move_one(Direction::East),
-                'v' => current_pos.move_one(Direction::South),
-                '<' => current_pos.move_one(Direction::West),
+                '^' => current_pos.step(Direction::North),
+                '>' => current_pos.step(Direction::East),
+                'v' => current_pos.step(Direction::South),
+                '<' => current_pos.step(Direction::West),
                 'A' => {
                     let current_key = self.get_key_for_pos(&current_pos).unwrap();
                     result.push(current_key);
@@ -172,7 +197,7 @@ impl Keypad {
                 _ => return None
             };
 
-            if let None = self.get_key_for_pos(&next_pos) {
+            if self.get_key_for_pos(&next_pos).is_none() {
                 panic!("out of bounds on '{}'", char);
             }
 
@@ -183,45 +208,19 @@ impl Keypad {
     }
 
     pub fn solve_path(&self, start: char, goal: char) -> Vec<Vec<Direction>> {
-        if start == goal {
-            return vec![vec![]];
-        }
-
         let start_pos = self.get_pos_for_key(start).unwrap();
-        
-        let mut walkers = vec![KeypadWalker::new(start_pos)];
-        let mut completed = vec![];
-
-        while walkers.len() > 0 {
-            let mut next_walkers = vec![];
-
-            for walker in walkers {
-                for &direction in DIRECTIONS {
-                    if let Some(next_walker) = walker.move_one(direction) {
-                        if let Some(next_key) = self.get_key_for_pos(next_walker.pos()) {
-                            if next_key == goal {
-                                completed.push(next_walker);
-                            } else {
-                                next_walkers.push(next_walker);
-                            }
-                        }
-                    }                    
-                }
-            }
-
-            walkers = next_walkers;
-        }
-        
-        let best_score = completed.iter().map(|w| w.len()).min().unwrap();        
-
-        let mut best_paths = vec![];
-        for walker in completed.into_iter() {
-            if walker.len() == best_score {
-                best_paths.push(walker.into_path());
-            }
-        }
-
-        best_paths
+        let goal_pos = self.get_pos_for_key(goal).unwrap();
+
+        let tree = bfs(start_pos, |pos| {
+            pos.neighbors4()
+                .into_iter()
+                .filter(|next_pos| self.get_key_for_pos(next_pos).is_some())
+        });
+
+        tree.all_paths_to(&goal_pos)
+            .into_iter()
+            .map(|path| path.windows(2).map(|step| direction_between(step[0], step[1])).collect())
+            .collect()
     }
 
     pub fn solve_code(&self, code: &str) -> Vec<Vec<String>> {
@@ -244,77 +243,12 @@ impl Keypad {
     }
 }
 
-#[derive(Debug)]
-struct KeypadWalker {
-    pos: Position,
-    path: Vec<Direction>,
-    visited: HashSet<Position>
-}
-
-impl KeypadWalker {
-    pub fn new(pos: Position) -> KeypadWalker {
-        let path = vec![];
-
-        let mut visited = HashSet::new();
-        visited.insert(pos);
-
-        KeypadWalker { pos, path, visited }
-    }
-    
-    pub fn pos(&self) -> &Position {
-        &self.pos
-    }
-
-    pub fn len(&self) -> usize {
-        self.path.len()
-    }
-
-    pub fn into_path(self) -> Vec<Direction> {
-        self.path
-    }
-
-    pub fn move_one(&self, direction: Direction) -> Option<KeypadWalker> {
-        let pos = self.pos.move_one(direction);
-    
-        if self.visited.contains(&pos) {
-            return None;
-        }
-
-        let mut path = self.path.clone();
-        path.push(direction);
+fn direction_between(from: Point, to: Point) -> Direction {
+    let offset = to - from;
 
-        let mut visited = self.visited.clone();
-        visited.insert(pos);
-
-        Some(KeypadWalker { pos, path, visited })
-    }
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West
-}
-
-static DIRECTIONS: &[Direction] = &[Direction::North, Direction::East, Direction::South, Direction::West];
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-pub struct Position(i32, i32);
-
-impl Position {
-    pub fn move_one(&self, direction: Direction) -> Position {
-        let x = self.0;
-        let y = self.1;
-
-        match direction {
-            Direction::North => Position(x, y - 1),
-            Direction::East => Position(x + 1, y),
-            Direction::South => Position(x, y + 1),
-            Direction::West => Position(x - 1, y)
-        }
-    }
+    Direction::ALL.into_iter()
+        .find(|direction| direction.offset() == offset)
+        .expect("points aren't adjacent")
 }
 
 #[cfg(test)]
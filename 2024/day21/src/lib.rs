@@ -1,58 +1,24 @@
-use std::fs;
-use std::io;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use keypad::solve;
-use thiserror::Error;
+use keypad::{solve, DoorCodes};
 
-mod keypad;
+pub mod keypad;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+pub struct Day;
 
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    let codes: Vec<String> = fs::read_to_string(filename)?
-        .lines()
-        .map(|s| s.trim().to_string())
-        .collect();
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 21;
 
-    let result = match options.part {
-        1 => run_part1(codes),
-        2 => run_part2(codes),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+    type Input = DoorCodes;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn run_part1(codes: Vec<String>) -> Result<usize, ApplicationError> {
-    let mut total = 0;
-    for code in codes {
-        let complexity = solve(&code, 2);
-        total += complexity;
+    fn part1(codes: &DoorCodes) -> Result<usize, SolutionError> {
+        Ok(codes.iter().map(|code| solve(code, 2)).sum())
     }
 
-    Ok(total)
-}
-
-fn run_part2(codes: Vec<String>) -> Result<usize, ApplicationError> {
-    let mut total = 0;
-    for code in codes {
-        let complexity = solve(&code, 25);
-        total += complexity;
+    fn part2(codes: &DoorCodes) -> Result<usize, SolutionError> {
+        Ok(codes.iter().map(|code| solve(code, 25)).sum())
     }
-
-    Ok(total)
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
-}
\ No newline at end of file
@@ -1,68 +1,34 @@
-use std::fs;
-use std::io;
-use std::num::ParseIntError;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use secret::MarketSimulator;
-use secret::SecretGenerator;
-use thiserror::Error;
+use market::MarketSimulator;
+use secret::{BuyerList, SecretGenerator};
 
-mod secret;
+mod linear;
+pub mod market;
+pub mod secret;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
-
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    let puzzle_input = read_puzzle_input(filename)?;
-    
-    let result = match options.part {
-        1 => run_part1(puzzle_input),
-        2 => run_part2(puzzle_input),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+pub struct Day;
 
-fn read_puzzle_input(filename: std::path::PathBuf) -> Result<Vec<u64>, ApplicationError> {
-    let puzzle_input: Result<Vec<u64>, _> = fs::read_to_string(filename)?
-        .lines()
-        .map(|line| line.parse::<u64>())
-        .collect();
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 22;
 
-    Ok(puzzle_input?)
-}
+    type Input = BuyerList;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-fn run_part1(puzzle_input: Vec<u64>) -> Result<u64, ApplicationError> {
-    let mut secret_generator = SecretGenerator::new(puzzle_input);
+    fn part1(buyers: &BuyerList) -> Result<u64, SolutionError> {
+        let mut secret_generator = SecretGenerator::new(buyers.secrets().to_vec());
+        secret_generator.jump(2000);
 
-    for n in 0..2000 {
-        secret_generator.step();
+        Ok(secret_generator.sum())
     }
-    
-    Ok(secret_generator.sum())
-}
 
-fn run_part2(puzzle_input: Vec<u64>) -> Result<u64, ApplicationError> {
-    let market = MarketSimulator::new();
-    
-    let history = market.simulate(puzzle_input, 2000);
-    let bananas = history.find_best_bananas();
-    let bananas: u64 = bananas.try_into().unwrap();
+    fn part2(buyers: &BuyerList) -> Result<u64, SolutionError> {
+        let market = MarketSimulator::new();
 
-    Ok(bananas)
-}
+        let best = market.best_sale(buyers.secrets(), 2000).ok_or(SolutionError::NoSolution)?;
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse puzzle input: {0}")]
-    CouldntParseInput(#[from] ParseIntError)
-}
\ No newline at end of file
+        Ok(best.bananas)
+    }
+}
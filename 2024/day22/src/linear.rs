@@ -0,0 +1,53 @@
+/// Secrets stay below 2^24, so they're vectors of 24 bits.
+pub(crate) const SECRET_BITS: usize = 24;
+pub(crate) const SECRET_MASK: u64 = (1 << SECRET_BITS) - 1;
+
+/// A linear map on 24-bit secrets over GF(2), where adding is XOR. Each column is where the
+/// map sends one bit, so applying it is XORing together the columns of the set bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BitMatrix {
+    columns: [u32; SECRET_BITS]
+}
+
+impl BitMatrix {
+    pub(crate) fn identity() -> BitMatrix {
+        BitMatrix { columns: std::array::from_fn(|bit| 1 << bit) }
+    }
+
+    /// Builds the matrix of a function by feeding it each bit on its own. The function has
+    /// to be linear for the result to mean anything.
+    pub(crate) fn from_fn(f: impl Fn(u64) -> u64) -> BitMatrix {
+        BitMatrix { columns: std::array::from_fn(|bit| (f(1 << bit) & SECRET_MASK) as u32) }
+    }
+
+    pub(crate) fn apply(&self, secret: u64) -> u64 {
+        let secret = secret & SECRET_MASK;
+
+        self.columns.iter()
+            .enumerate()
+            .filter(|(bit, _)| secret & (1 << bit) != 0)
+            .fold(0, |result, (_, column)| result ^ *column as u64)
+    }
+
+    /// The map that applies `other` first and then `self`.
+    pub(crate) fn compose(&self, other: &BitMatrix) -> BitMatrix {
+        BitMatrix { columns: other.columns.map(|column| self.apply(column as u64) as u32) }
+    }
+
+    /// The map applied `n` times, by repeated squaring.
+    pub(crate) fn pow(&self, mut n: u64) -> BitMatrix {
+        let mut result = BitMatrix::identity();
+        let mut square = *self;
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.compose(&square);
+            }
+
+            square = square.compose(&square);
+            n >>= 1;
+        }
+
+        result
+    }
+}
@@ -0,0 +1,170 @@
+use rayon::prelude::*;
+
+use crate::secret::step_batch;
+
+/// Buyers advanced together through `step_batch`.
+const BATCH_SIZE: usize = 32;
+
+/// Each price change is between -9 and 9, so shifted up by 9 it fits in 5 bits, and the last
+/// four changes fit in 20.
+const WINDOW_BITS: u32 = 20;
+const WINDOW_COUNT: usize = 1 << WINDOW_BITS;
+const WINDOW_MASK: u32 = (1 << WINDOW_BITS) - 1;
+
+#[derive(Debug, Default)]
+pub struct MarketSimulator { }
+
+/// The sequence of four price changes that earns the most bananas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestSale {
+    pub changes: [i8; 4],
+    pub bananas: u64,
+    /// What each buyer sells for, in input order, or `None` if they never see the sequence.
+    pub prices: Vec<Option<u8>>
+}
+
+impl MarketSimulator {
+    pub fn new() -> MarketSimulator {
+        MarketSimulator { }
+    }
+
+    /// Finds the best sequence over `generations` new secrets per buyer. Gives `None` when
+    /// there are no buyers or too few generations to see four changes.
+    pub fn best_sale(&self, secrets: &[u64], generations: usize) -> Option<BestSale> {
+        if secrets.is_empty() || generations < 4 {
+            return None;
+        }
+
+        let totals = secrets.par_chunks(BATCH_SIZE)
+            .enumerate()
+            .fold(WindowTotals::new, |mut totals, (chunk, batch)| {
+                totals.add_batch(chunk * BATCH_SIZE, batch, generations);
+                totals
+            })
+            .reduce(WindowTotals::new, WindowTotals::merge);
+
+        // Ties go to the lowest window, so the answer doesn't depend on thread scheduling
+        let (window, &bananas) = totals.bananas.iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, bananas)| **bananas)?;
+
+        let window = window as u32;
+        let prices = secrets.par_iter()
+            .map(|&secret| first_sale(secret, generations, window))
+            .collect();
+
+        Some(BestSale { changes: decode(window), bananas: bananas as u64, prices })
+    }
+}
+
+/// One thread's running banana totals for every window. `last_buyer` stamps each window with
+/// the last buyer (counting from 1) that sold on it, so only a buyer's first sale counts
+/// without clearing anything between buyers.
+struct WindowTotals {
+    bananas: Vec<u32>,
+    last_buyer: Vec<u32>
+}
+
+impl WindowTotals {
+    fn new() -> WindowTotals {
+        WindowTotals { bananas: vec![0; WINDOW_COUNT], last_buyer: vec![0; WINDOW_COUNT] }
+    }
+
+    fn add_batch(&mut self, first_buyer: usize, secrets: &[u64], generations: usize) {
+        let mut batch = secrets.to_vec();
+        let mut prices: Vec<u8> = batch.iter().map(|secret| (secret % 10) as u8).collect();
+        let mut windows = vec![0; batch.len()];
+
+        for generation in 1..=generations {
+            step_batch(&mut batch);
+
+            for (i, secret) in batch.iter().enumerate() {
+                let price = (secret % 10) as u8;
+                windows[i] = push_change(windows[i], prices[i], price);
+                prices[i] = price;
+
+                if generation < 4 {
+                    continue;
+                }
+
+                let buyer = (first_buyer + i + 1) as u32;
+                let window = windows[i] as usize;
+
+                if self.last_buyer[window] != buyer {
+                    self.last_buyer[window] = buyer;
+                    self.bananas[window] += price as u32;
+                }
+            }
+        }
+    }
+
+    fn merge(mut self, other: WindowTotals) -> WindowTotals {
+        for (bananas, other) in self.bananas.iter_mut().zip(other.bananas) {
+            *bananas += other;
+        }
+
+        self
+    }
+}
+
+/// The price a buyer sells for the first time their last four changes match `target`.
+fn first_sale(secret: u64, generations: usize, target: u32) -> Option<u8> {
+    let mut secret = [secret];
+    let mut price = (secret[0] % 10) as u8;
+    let mut window = 0;
+
+    for generation in 1..=generations {
+        step_batch(&mut secret);
+
+        let next = (secret[0] % 10) as u8;
+        window = push_change(window, price, next);
+        price = next;
+
+        if generation >= 4 && window == target {
+            return Some(price);
+        }
+    }
+
+    None
+}
+
+#[inline]
+fn push_change(window: u32, from: u8, to: u8) -> u32 {
+    let change = (to as i32 - from as i32 + 9) as u32;
+
+    ((window << 5) | change) & WINDOW_MASK
+}
+
+fn decode(window: u32) -> [i8; 4] {
+    std::array::from_fn(|i| ((window >> (5 * (3 - i))) & 31) as i8 - 9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn part2_example() {
+        let market = MarketSimulator::new();
+        let best = market.best_sale(&[1, 2, 3, 2024], 2000).unwrap();
+
+        assert_eq!(best, BestSale {
+            changes: [-2, 1, -1, 3],
+            bananas: 23,
+            prices: vec![Some(7), Some(7), None, Some(9)]
+        });
+    }
+
+    #[test]
+    pub fn short_runs() {
+        let market = MarketSimulator::new();
+
+        assert_eq!(market.best_sale(&[], 2000), None);
+        assert_eq!(market.best_sale(&[123], 3), None);
+
+        // 123 goes 3, 0, 6, 5, 4, 4, 6, 4, 4, 2
+        let best = market.best_sale(&[123], 9).unwrap();
+        assert_eq!((best.changes, best.bananas), ([-1, -1, 0, 2], 6));
+    }
+}
@@ -1,177 +1,130 @@
-use std::collections::{HashMap, HashSet};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use crate::linear::{BitMatrix, SECRET_MASK};
 
 #[derive(Debug)]
-pub struct MarketSimulator {
+pub struct BuyerList {
+    secrets: Vec<u64>
 }
 
-impl MarketSimulator {
-    pub fn new() -> MarketSimulator {
-        MarketSimulator { }
+impl BuyerList {
+    pub fn secrets(&self) -> &[u64] {
+        &self.secrets
     }
+}
 
-    pub fn simulate(&self, secrets: Vec<u64>, generations: usize) -> MarketHistory {
-        let mut secrets = secrets;
-        
-        let mut history = MarketHistory::new();
-        for (i, secret) in secrets.iter().enumerate() {
-            history.record_secret(i, secret);
-        }
-
-        for _ in 0..generations {
-            for (i, secret) in secrets.iter_mut().enumerate() {
-                *secret = mix_prune(*secret, *secret * 64);
-                *secret = mix_prune(*secret, *secret >> 5);
-                *secret = mix_prune(*secret, *secret * 2048);
-                
-                history.record_secret(i, secret);
-            }
-        }
+impl FromStr for BuyerList {
+    type Err = ParseIntError;
 
-        // for price in &history.price_histories.get(&0).unwrap().prices {
-        //     println!("{} ({})", price.price, price.changes[3]);
-        // }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let secrets = s.lines()
+            .map(|line| line.trim().parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()?;
 
-        history
+        Ok(BuyerList { secrets })
     }
 }
 
 #[derive(Debug)]
-pub struct MarketHistory {
-    price_histories: HashMap<usize, PriceHistory>,
-    all_changes: HashSet<[i8;4]>
+pub struct SecretGenerator {
+    secrets: Vec<u64>
 }
 
-impl MarketHistory {
-    pub fn new() -> MarketHistory {
-        let price_histories = HashMap::new();
-        let all_changes = HashSet::new();
-
-        MarketHistory { price_histories, all_changes }
+impl SecretGenerator {
+    pub fn new(secrets: Vec<u64>) -> SecretGenerator {
+        SecretGenerator { secrets }
     }
 
-    pub fn record_secret(&mut self, buyer_id: usize, secret: &u64) {
-        let entry = self.price_histories.entry(buyer_id)
-            .or_insert_with(|| PriceHistory::new());
-
-        let price_point = entry.record_secret(*secret);
-        self.all_changes.insert(price_point.changes.clone());
+    pub fn step(&mut self) {
+        step_batch(&mut self.secrets);
     }
 
-    pub fn find_best_bananas(&self) -> i64 {
-        let mut best_bananas = 0;
-
-        for changes in self.all_changes.iter() {
-            let bananas = self.count_bananas(changes);
-            if bananas > best_bananas {
-                best_bananas = bananas;
-            }
+    /// Advances every secret by `n` generations at once. Each generation only shifts and
+    /// XORs the bits of a 24-bit secret, so it's a linear map, and `n` of them are one
+    /// matrix power away.
+    pub fn jump(&mut self, n: u64) {
+        if n == 0 {
+            return;
         }
 
-        best_bananas
-    }
-
-    pub fn count_bananas(&self, changes: &[i8;4]) -> i64 {
-        let mut total_bananas: i64 = 0;
+        let generations = BitMatrix::from_fn(next_secret).pow(n);
 
-        for price_history in self.price_histories.values() {
-            if let Some(price_point) = price_history.get_price_for_changes(changes) {
-                total_bananas += price_point.price as i64;
-            }
+        for secret in self.secrets.iter_mut() {
+            *secret = generations.apply(*secret);
         }
-
-        total_bananas
     }
-}
 
-#[derive(Debug)]
-struct PriceHistory {
-    prices: Vec<PricePoint>,
-    first_change_occurences: HashMap<[i8;4], PricePoint>
-}
+    /// Takes every secret back one generation. Only the lowest 24 bits of a secret ever
+    /// affect what comes after it, so that's all that can be recovered.
+    pub fn previous(&mut self) {
+        for secret in self.secrets.iter_mut() {
+            let mut value = *secret & SECRET_MASK;
 
-impl PriceHistory {
-    pub fn new() -> PriceHistory {
-        let prices = vec![];
-        let first_change_occurences = HashMap::new();
+            value = unshift_left(value, 11);
+            value = unshift_right(value, 5);
+            value = unshift_left(value, 6);
 
-        PriceHistory { prices, first_change_occurences }
+            *secret = value;
+        }
+    }
+    
+    pub fn sum(&self) -> u64 {
+        self.secrets.iter().sum()
     }
 
-    pub fn record_secret(&mut self, secret: u64) -> &PricePoint {
-        let price: i8 = (secret % 10).try_into().unwrap();
-        
-        self.add_price(price)
+    pub fn secrets(&self) -> &Vec<u64> {
+        &self.secrets
     }
+}
 
-    pub fn add_price(&mut self, price: i8) -> &PricePoint {
-        if self.prices.len() > 0 {
-            let previous_price = &self.prices[self.prices.len() - 1];
-            let new_price = previous_price.add(price);
-            
-            if self.prices.len() >= 4 {
-                if !self.first_change_occurences.contains_key(&new_price.changes) {
-                    self.first_change_occurences.insert(new_price.changes.clone(), new_price.clone());
-                }
-            }
-
-            self.prices.push(new_price);
-        } else {
-            self.prices.push(PricePoint::new(price, [0, 0, 0, 0]));
-        }
+/// Advances every secret by one generation. Each stage runs over the whole batch before the
+/// next starts, which lets the compiler vectorise the loops.
+pub fn step_batch(secrets: &mut [u64]) {
+    for secret in secrets.iter_mut() {
+        *secret = mix_prune(*secret, *secret * 64);
+    }
 
-        &self.prices[self.prices.len() - 1]
+    for secret in secrets.iter_mut() {
+        *secret = mix_prune(*secret, *secret >> 5);
     }
 
-    pub fn get_price_for_changes(&self, changes: &[i8;4]) -> Option<&PricePoint> {
-        self.first_change_occurences.get(changes)
+    for secret in secrets.iter_mut() {
+        *secret = mix_prune(*secret, *secret * 2048);
     }
 }
 
-#[derive(Debug, Clone)]
-struct PricePoint {
-    price: i8,
-    changes: [i8;4]
-}
+fn next_secret(secret: u64) -> u64 {
+    let mut secret = [secret];
+    step_batch(&mut secret);
 
-impl PricePoint {
-    pub fn new(price: i8, changes: [i8;4]) -> PricePoint {
-        PricePoint { price, changes }
-    }
+    secret[0]
+}
 
-    pub fn add(&self, new_price: i8) -> PricePoint {
-        let latest_change = new_price - self.price;
-        let [_, b, c, d] = self.changes;
-        let new_changes = [b, c, d, latest_change];
+/// Undoes `value ^= value << shift` within 24 bits, recovering the bits from the bottom up.
+fn unshift_left(value: u64, shift: u32) -> u64 {
+    let mut result = value;
+    let mut shifted = shift;
 
-        PricePoint::new(new_price, new_changes)
+    while (shifted as usize) < 24 {
+        result ^= value << shifted;
+        shifted += shift;
     }
-}
 
-#[derive(Debug)]
-pub struct SecretGenerator {
-    secrets: Vec<u64>
+    result & SECRET_MASK
 }
 
-impl SecretGenerator {
-    pub fn new(secrets: Vec<u64>) -> SecretGenerator {
-        SecretGenerator { secrets }
-    }
+/// Undoes `value ^= value >> shift`, recovering the bits from the top down.
+fn unshift_right(value: u64, shift: u32) -> u64 {
+    let mut result = value;
+    let mut shifted = shift;
 
-    pub fn step(&mut self) {
-        for secret in self.secrets.iter_mut() {
-            *secret = mix_prune(*secret, *secret * 64);
-            *secret = mix_prune(*secret, *secret >> 5);
-            *secret = mix_prune(*secret, *secret * 2048);
-        }
-    }
-    
-    pub fn sum(&self) -> u64 {
-        self.secrets.iter().sum()
+    while (shifted as usize) < 24 {
+        result ^= value >> shifted;
+        shifted += shift;
     }
 
-    pub fn secrets(&self) -> &Vec<u64> {
-        &self.secrets
-    }
+    result
 }
 
 #[inline]
@@ -181,8 +134,6 @@ fn mix_prune(secret: u64, n: u64) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use crate::secret::MarketSimulator;
-
     use super::SecretGenerator;
 
     #[test]
@@ -190,26 +141,36 @@ mod tests {
         let secrets = vec![123];
         let mut generator = SecretGenerator::new(secrets);
 
-        generator.step();
+        let mut sequence = vec![];
+        for _ in 0..3 {
+            generator.step();
+            sequence.push(generator.secrets()[0]);
+        }
 
-        println!("{:?}", generator);
+        assert_eq!(sequence, vec![15887950, 16495136, 527345]);
     }
 
     #[test]
-    pub fn part2_example1() {
-        let secrets = vec![123];
-        let market = MarketSimulator::new();
+    pub fn jump_and_previous() {
+        let secrets = vec![1, 10, 100, 2024, 123];
 
-        market.simulate(secrets, 10);
-    }
+        let mut stepped = SecretGenerator::new(secrets.clone());
+        for _ in 0..2000 {
+            stepped.step();
+        }
 
-    #[test]
-    pub fn part2_example2() {
-        let secrets = vec![1, 2, 3, 2024];
-        let market = MarketSimulator::new();
+        let mut jumped = SecretGenerator::new(secrets.clone());
+        jumped.jump(2000);
+        assert_eq!(jumped.secrets(), stepped.secrets());
+        assert_eq!(jumped.secrets()[..4].iter().sum::<u64>(), 37327623);
 
-        let history = market.simulate(secrets, 2000);
+        let mut ten = SecretGenerator::new(vec![123]);
+        ten.jump(10);
+        assert_eq!(ten.secrets(), &vec![5908254]);
 
-        assert_eq!(history.find_best_bananas(), 23);
+        for _ in 0..2000 {
+            jumped.previous();
+        }
+        assert_eq!(jumped.secrets(), &secrets);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,267 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Write;
+
+use crate::cliques::IndexedGraph;
+use crate::network_map::NetworkMap;
+
+impl IndexedGraph<'_> {
+    fn sorted_neighbors(&self, node: usize) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = self.neighbors[node].iter().copied().collect();
+        neighbors.sort_unstable();
+        neighbors
+    }
+
+    /// Articulation points and bridges from one depth-first search, comparing when each
+    /// computer was discovered with the earliest computer reachable from its subtree. The
+    /// search keeps its own stack so long chains can't overflow the real one.
+    fn cut_points(&self) -> (Vec<usize>, Vec<(usize, usize)>) {
+        let count = self.names.len();
+        let mut discovered = vec![usize::MAX; count];
+        let mut low = vec![0; count];
+        let mut is_articulation = vec![false; count];
+        let mut bridges = vec![];
+        let mut time = 0;
+
+        for root in 0..count {
+            if discovered[root] != usize::MAX {
+                continue;
+            }
+
+            discovered[root] = time;
+            low[root] = time;
+            time += 1;
+
+            let mut root_children = 0;
+            let mut stack = vec![(root, None, self.sorted_neighbors(root), 0)];
+
+            while let Some((node, parent, neighbors, next)) = stack.last_mut() {
+                let (node, parent) = (*node, *parent);
+
+                if *next < neighbors.len() {
+                    let neighbor = neighbors[*next];
+                    *next += 1;
+
+                    if Some(neighbor) == parent {
+                        continue;
+                    }
+
+                    if discovered[neighbor] == usize::MAX {
+                        discovered[neighbor] = time;
+                        low[neighbor] = time;
+                        time += 1;
+
+                        if node == root {
+                            root_children += 1;
+                        }
+
+                        stack.push((neighbor, Some(node), self.sorted_neighbors(neighbor), 0));
+                    } else {
+                        low[node] = low[node].min(discovered[neighbor]);
+                    }
+                } else {
+                    stack.pop();
+
+                    if let Some(parent) = parent {
+                        low[parent] = low[parent].min(low[node]);
+
+                        if low[node] > discovered[parent] {
+                            bridges.push((parent.min(node), parent.max(node)));
+                        }
+
+                        if parent != root && low[node] >= discovered[parent] {
+                            is_articulation[parent] = true;
+                        }
+                    }
+                }
+            }
+
+            if root_children > 1 {
+                is_articulation[root] = true;
+            }
+        }
+
+        let articulation_points = (0..count).filter(|&node| is_articulation[node]).collect();
+        bridges.sort_unstable();
+
+        (articulation_points, bridges)
+    }
+}
+
+impl NetworkMap {
+    /// Every connection once, as a sorted pair, in sorted order.
+    pub fn connections(&self) -> Vec<(&String, &String)> {
+        let mut connections: Vec<(&String, &String)> = self.computers()
+            .flat_map(|computer| self.neighbors(computer).map(move |neighbor| (computer, neighbor)))
+            .filter(|(computer, neighbor)| computer < neighbor)
+            .collect();
+
+        connections.sort_unstable();
+        connections
+    }
+
+    /// Groups of computers that can reach each other, largest first. Names within a group
+    /// are sorted.
+    pub fn connected_components(&self) -> Vec<Vec<&String>> {
+        let graph = IndexedGraph::new(self);
+        let mut seen = vec![false; graph.names.len()];
+        let mut components = vec![];
+
+        for start in 0..graph.names.len() {
+            if seen[start] {
+                continue;
+            }
+
+            seen[start] = true;
+            let mut component = vec![];
+            let mut queue = VecDeque::from([start]);
+
+            while let Some(node) = queue.pop_front() {
+                component.push(node);
+
+                for &neighbor in graph.neighbors[node].iter() {
+                    if !seen[neighbor] {
+                        seen[neighbor] = true;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component.into_iter().map(|node| graph.names[node]).collect::<Vec<_>>());
+        }
+
+        components.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        components
+    }
+
+    /// How many computers have each number of connections.
+    pub fn degree_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for computer in self.computers() {
+            *histogram.entry(self.neighbors(computer).count()).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    /// Computers whose removal would split their group in two, sorted.
+    pub fn articulation_points(&self) -> Vec<&String> {
+        let graph = IndexedGraph::new(self);
+        let (articulation_points, _) = graph.cut_points();
+
+        articulation_points.into_iter().map(|node| graph.names[node]).collect()
+    }
+
+    /// Connections whose removal would split their group in two, sorted like
+    /// [`NetworkMap::connections`].
+    pub fn bridges(&self) -> Vec<(&String, &String)> {
+        let graph = IndexedGraph::new(self);
+        let (_, bridges) = graph.cut_points();
+
+        bridges.into_iter().map(|(a, b)| (graph.names[a], graph.names[b])).collect()
+    }
+
+    /// For each computer, the largest `k` where it's part of the `k`-core.
+    pub fn core_numbers(&self) -> HashMap<&String, usize> {
+        let graph = IndexedGraph::new(self);
+        let (_, cores) = graph.peel();
+
+        graph.names.iter().copied().zip(cores).collect()
+    }
+
+    /// The largest group of computers where each has at least `k` connections within the
+    /// group, sorted. It doesn't have to be connected.
+    pub fn k_core(&self, k: usize) -> Vec<&String> {
+        let mut core: Vec<&String> = self.core_numbers()
+            .into_iter()
+            .filter(|(_, core)| *core >= k)
+            .map(|(computer, _)| computer)
+            .collect();
+
+        core.sort_unstable();
+        core
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut output = "graph network {\n".to_string();
+
+        for (a, b) in self.connections() {
+            writeln!(output, "    \"{}\" -- \"{}\";", escape_dot(a), escape_dot(b)).unwrap();
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let mut output = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+            <graph id=\"network\" edgedefault=\"undirected\">\n".to_string();
+
+        let mut computers: Vec<&String> = self.computers().collect();
+        computers.sort_unstable();
+
+        for computer in computers {
+            writeln!(output, "    <node id=\"{}\"/>", escape_xml(computer)).unwrap();
+        }
+
+        for (a, b) in self.connections() {
+            writeln!(output, "    <edge source=\"{}\" target=\"{}\"/>", escape_xml(a), escape_xml(b)).unwrap();
+        }
+
+        output.push_str("  </graph>\n</graphml>\n");
+        output
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names<'a>(computers: impl IntoIterator<Item = &'a String>) -> Vec<&'a str> {
+        computers.into_iter().map(|computer| computer.as_str()).collect()
+    }
+
+    /// Two triangles joined through `c-d`, with `e` hanging off `d`, plus a separate pair.
+    fn example() -> NetworkMap {
+        "a-b\nb-c\nc-a\nc-d\nd-e\nd-f\nf-g\ng-d\nx-y".parse().unwrap()
+    }
+
+    #[test]
+    pub fn structure() {
+        let network_map = example();
+
+        let components: Vec<Vec<&str>> = network_map.connected_components().into_iter().map(names).collect();
+        assert_eq!(components, vec![vec!["a", "b", "c", "d", "e", "f", "g"], vec!["x", "y"]]);
+
+        assert_eq!(network_map.degree_histogram(), BTreeMap::from([(1, 3), (2, 4), (3, 1), (4, 1)]));
+        assert_eq!(names(network_map.articulation_points()), vec!["c", "d"]);
+
+        let bridges: Vec<(&str, &str)> = network_map.bridges().into_iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+        assert_eq!(bridges, vec![("c", "d"), ("d", "e"), ("x", "y")]);
+
+        assert_eq!(names(network_map.k_core(2)), vec!["a", "b", "c", "d", "f", "g"]);
+        assert!(network_map.k_core(3).is_empty());
+    }
+
+    #[test]
+    pub fn writers() {
+        let network_map: NetworkMap = "b-a\na-\"c\"".parse().unwrap();
+
+        assert_eq!(network_map.to_dot(), "graph network {\n    \"\\\"c\\\"\" -- \"a\";\n    \"a\" -- \"b\";\n}\n");
+        assert!(network_map.to_graphml().contains("    <edge source=\"&quot;c&quot;\" target=\"a\"/>\n"));
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::network_map::NetworkMap;
+
+/// The graph with computers numbered in name order, so sorting a clique's indices also
+/// sorts its names.
+pub(crate) struct IndexedGraph<'a> {
+    pub(crate) names: Vec<&'a String>,
+    pub(crate) neighbors: Vec<HashSet<usize>>
+}
+
+impl<'a> IndexedGraph<'a> {
+    pub(crate) fn new(network_map: &'a NetworkMap) -> IndexedGraph<'a> {
+        let mut names: Vec<&String> = network_map.computers().collect();
+        names.sort();
+
+        let neighbors = names.iter()
+            .map(|name| {
+                network_map.neighbors(name)
+                    .map(|neighbor| names.binary_search(&neighbor).expect("neighbors are computers"))
+                    .collect()
+            })
+            .collect();
+
+        IndexedGraph { names, neighbors }
+    }
+
+    /// Computers in the order they'd be removed by repeatedly taking the one with the fewest
+    /// remaining neighbors. Each computer then has at most the graph's degeneracy neighbors
+    /// later in the order.
+    fn degeneracy_order(&self) -> Vec<usize> {
+        self.peel().0
+    }
+
+    /// The degeneracy order along with each computer's core number: the largest `k` where it
+    /// belongs to a subgraph with every computer having at least `k` neighbors inside it.
+    pub(crate) fn peel(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut degrees: Vec<usize> = self.neighbors.iter().map(|neighbors| neighbors.len()).collect();
+        let mut remaining: BTreeSet<(usize, usize)> = degrees.iter().copied().zip(0..).collect();
+        let mut removed = vec![false; self.names.len()];
+        let mut order = Vec::with_capacity(self.names.len());
+        let mut cores = vec![0; self.names.len()];
+        let mut core = 0;
+
+        while let Some((degree, node)) = remaining.pop_first() {
+            core = core.max(degree);
+            cores[node] = core;
+
+            removed[node] = true;
+            order.push(node);
+
+            for &neighbor in self.neighbors[node].iter().filter(|&&neighbor| !removed[neighbor]) {
+                remaining.remove(&(degrees[neighbor], neighbor));
+                degrees[neighbor] -= 1;
+                remaining.insert((degrees[neighbor], neighbor));
+            }
+        }
+
+        (order, cores)
+    }
+
+    /// Bron–Kerbosch with pivoting, started once per computer in degeneracy order so each
+    /// search only considers that computer's later neighbors.
+    fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let order = self.degeneracy_order();
+        let position = positions(&order);
+
+        let mut cliques = vec![];
+
+        for (i, &node) in order.iter().enumerate() {
+            let (later, earlier): (HashSet<usize>, HashSet<usize>) = self.neighbors[node].iter()
+                .partition(|&&neighbor| position[neighbor] > i);
+
+            self.bron_kerbosch(&mut vec![node], later, earlier, &mut cliques);
+        }
+
+        cliques
+    }
+
+    fn bron_kerbosch(&self, clique: &mut Vec<usize>, mut candidates: HashSet<usize>, mut excluded: HashSet<usize>, cliques: &mut Vec<Vec<usize>>) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                let mut clique = clique.clone();
+                clique.sort_unstable();
+                cliques.push(clique);
+            }
+
+            return;
+        }
+
+        // Any maximal clique contains the pivot or one of its non-neighbors, so only those
+        // need to be tried
+        let pivot = candidates.iter()
+            .chain(excluded.iter())
+            .max_by_key(|&&node| self.neighbors[node].intersection(&candidates).count())
+            .copied()
+            .expect("candidates isn't empty");
+
+        let branches: Vec<usize> = candidates.difference(&self.neighbors[pivot]).copied().collect();
+
+        for node in branches {
+            let neighbors = &self.neighbors[node];
+
+            clique.push(node);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbors).copied().collect(),
+                excluded.intersection(neighbors).copied().collect(),
+                cliques
+            );
+            clique.pop();
+
+            candidates.remove(&node);
+            excluded.insert(node);
+        }
+    }
+
+    /// Every clique of exactly `size` computers, found by extending each computer only with
+    /// neighbors later in the degeneracy order so each clique is built once.
+    fn cliques_of_size(&self, size: usize) -> Vec<Vec<usize>> {
+        let mut cliques = vec![];
+        if size == 0 {
+            return cliques;
+        }
+
+        let order = self.degeneracy_order();
+        let position = positions(&order);
+
+        for (i, &node) in order.iter().enumerate() {
+            let later: Vec<usize> = self.neighbors[node].iter()
+                .copied()
+                .filter(|&neighbor| position[neighbor] > i)
+                .collect();
+
+            self.extend_clique(&mut vec![node], &later, size, &mut cliques);
+        }
+
+        cliques
+    }
+
+    fn extend_clique(&self, clique: &mut Vec<usize>, candidates: &[usize], size: usize, cliques: &mut Vec<Vec<usize>>) {
+        if clique.len() == size {
+            let mut clique = clique.clone();
+            clique.sort_unstable();
+            cliques.push(clique);
+            return;
+        }
+
+        for (i, &node) in candidates.iter().enumerate() {
+            let remaining: Vec<usize> = candidates[i + 1..].iter()
+                .copied()
+                .filter(|other| self.neighbors[node].contains(other))
+                .collect();
+
+            if clique.len() + 1 + remaining.len() < size {
+                continue;
+            }
+
+            clique.push(node);
+            self.extend_clique(clique, &remaining, size, cliques);
+            clique.pop();
+        }
+    }
+
+    fn to_names(&self, mut cliques: Vec<Vec<usize>>) -> Vec<Vec<&'a String>> {
+        cliques.sort_unstable();
+
+        cliques.into_iter()
+            .map(|clique| clique.into_iter().map(|node| self.names[node]).collect())
+            .collect()
+    }
+}
+
+/// Where each computer appears in `order`.
+fn positions(order: &[usize]) -> Vec<usize> {
+    let mut positions = vec![0; order.len()];
+    for (i, &node) in order.iter().enumerate() {
+        positions[node] = i;
+    }
+
+    positions
+}
+
+impl NetworkMap {
+    /// Every set of computers that are all connected to each other and can't be grown any
+    /// further. Names within a clique and the cliques themselves are sorted.
+    pub fn all_maximal_cliques(&self) -> Vec<Vec<&String>> {
+        let graph = IndexedGraph::new(self);
+
+        graph.to_names(graph.maximal_cliques())
+    }
+
+    /// The largest set of computers that are all connected to each other, with names
+    /// sorted. Ties go to the alphabetically first; an empty map gives an empty clique.
+    pub fn maximum_clique(&self) -> Vec<&String> {
+        let cliques = self.all_maximal_cliques();
+        let largest = cliques.iter().map(|clique| clique.len()).max().unwrap_or(0);
+
+        cliques.into_iter()
+            .find(|clique| clique.len() == largest)
+            .unwrap_or_default()
+    }
+
+    /// Every set of exactly `size` computers that are all connected to each other, sorted
+    /// like [`NetworkMap::all_maximal_cliques`].
+    pub fn cliques_of_size(&self, size: usize) -> Vec<Vec<&String>> {
+        let graph = IndexedGraph::new(self);
+
+        graph.to_names(graph.cliques_of_size(size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn random_map(rng: &mut StdRng) -> NetworkMap {
+        let size = rng.random_range(0..=10);
+        let density = rng.random_range(0.1..0.9);
+
+        let mut graph: HashMap<String, HashSet<String>> = HashMap::new();
+        for a in 0..size {
+            for b in a + 1..size {
+                if rng.random_bool(density) {
+                    graph.entry(format!("n{}", a)).or_default().insert(format!("n{}", b));
+                    graph.entry(format!("n{}", b)).or_default().insert(format!("n{}", a));
+                }
+            }
+        }
+
+        NetworkMap::new(graph)
+    }
+
+    /// Checks every subset of computers, sorted the same way as the real results.
+    fn brute_force_cliques(network_map: &NetworkMap) -> Vec<Vec<&String>> {
+        let mut names: Vec<&String> = network_map.computers().collect();
+        names.sort();
+
+        let mut cliques: Vec<Vec<&String>> = (1..1_u32 << names.len())
+            .map(|mask| (0..names.len()).filter(|i| mask & (1 << i) != 0).map(|i| names[i]).collect::<Vec<_>>())
+            .filter(|subset| {
+                subset.iter().enumerate().all(|(i, a)| {
+                    subset[i + 1..].iter().all(|b| network_map.neighbors(a).any(|neighbor| neighbor == *b))
+                })
+            })
+            .collect();
+
+        cliques.sort();
+        cliques
+    }
+
+    #[test]
+    pub fn matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(23);
+
+        for _ in 0..200 {
+            let network_map = random_map(&mut rng);
+            let cliques = brute_force_cliques(&network_map);
+
+            let maximal: Vec<Vec<&String>> = cliques.iter()
+                .filter(|clique| !cliques.iter().any(|other| other.len() > clique.len() && clique.iter().all(|name| other.contains(name))))
+                .cloned()
+                .collect();
+            assert_eq!(network_map.all_maximal_cliques(), maximal);
+
+            let largest = cliques.iter().map(|clique| clique.len()).max().unwrap_or(0);
+            let first_largest = cliques.iter().find(|clique| clique.len() == largest).cloned().unwrap_or_default();
+            assert_eq!(network_map.maximum_clique(), first_largest);
+
+            for size in 1..=4 {
+                let expected: Vec<Vec<&String>> = cliques.iter().filter(|clique| clique.len() == size).cloned().collect();
+                assert_eq!(network_map.cliques_of_size(size), expected);
+            }
+        }
+    }
+
+    #[test]
+    pub fn empty_map() {
+        let network_map = NetworkMap::new(HashMap::new());
+
+        assert!(network_map.maximum_clique().is_empty());
+        assert!(network_map.all_maximal_cliques().is_empty());
+        assert!(network_map.cliques_of_size(3).is_empty());
+    }
+}
@@ -1,57 +1,131 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::io;
+use std::path::PathBuf;
 
 use clap::Parser;
+use clap::Subcommand;
+use aoc_common::{solve, ApplicationError, Part, Solution, SolutionError};
+
 use network_map::NetworkMap;
-use network_map::ParseNetworkMapError;
-use thiserror::Error;
 
-mod network_map;
+pub mod analytics;
+pub mod cliques;
+pub mod network_map;
 
 #[derive(Parser)]
 pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
+    #[command(subcommand)]
+    command: Commands
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Part1 {
+        input: PathBuf
+    },
+    Part2 {
+        input: PathBuf
+    },
+    /// Print components, degrees, cut points and cores of the network
+    Stats {
+        input: PathBuf,
+
+        /// Also write the network as Graphviz DOT to this file
+        #[arg(long)]
+        dot: Option<PathBuf>,
+
+        /// Also write the network as GraphML to this file
+        #[arg(long)]
+        graphml: Option<PathBuf>
+    }
 }
 
 pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    let puzzle_input = fs::read_to_string(filename)?;
-    let network_map: NetworkMap = puzzle_input.parse()?;
-    
-    let result = match options.part {
-        1 => run_part1(&network_map),
-        2 => run_part2(&network_map),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
+    match options.command {
+        Commands::Part1 { input } => solve::<Day>(Part::One, &fs::read_to_string(input)?),
+        Commands::Part2 { input } => solve::<Day>(Part::Two, &fs::read_to_string(input)?),
+        Commands::Stats { input, dot, graphml } => stats(input, dot, graphml)
+    }
 }
 
-fn run_part1(network_map: &NetworkMap) -> Result<String, ApplicationError> {
-    let clusters = network_map.clusters_of_three();
+fn stats(input: PathBuf, dot: Option<PathBuf>, graphml: Option<PathBuf>) -> Result<String, ApplicationError> {
+    let network_map = fs::read_to_string(input)?
+        .parse::<NetworkMap>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
 
-    let t_count = clusters.iter()
-        .filter(|c| c.iter().any(|n| n.starts_with('t')))
-        .inspect(|[a,b,c]| println!("{},{},{}", a, b, c))
-        .count();
+    if let Some(path) = dot {
+        fs::write(path, network_map.to_dot())?;
+    }
 
-    Ok(t_count.to_string())
-}
+    if let Some(path) = graphml {
+        fs::write(path, network_map.to_graphml())?;
+    }
+
+    let join = |computers: Vec<&String>| {
+        if computers.is_empty() {
+            "none".to_string()
+        } else {
+            computers.iter().map(|computer| computer.as_str()).collect::<Vec<_>>().join(",")
+        }
+    };
+
+    let components = network_map.connected_components();
+    let component_sizes: Vec<String> = components.iter().map(|component| component.len().to_string()).collect();
+
+    let degrees: Vec<String> = network_map.degree_histogram().iter()
+        .map(|(degree, count)| format!("{}: {}", degree, count))
+        .collect();
+
+    let mut core_sizes = BTreeMap::new();
+    for core in network_map.core_numbers().into_values() {
+        *core_sizes.entry(core).or_insert(0) += 1;
+    }
+    let degeneracy = core_sizes.keys().max().copied().unwrap_or(0);
 
-fn run_part2(network_map: &NetworkMap) -> Result<String, ApplicationError> {
-    let largest_network = network_map.largest_network();
-    let solution = largest_network.join(",");
+    let bridges: Vec<String> = network_map.bridges().iter().map(|(a, b)| format!("{}-{}", a, b)).collect();
 
-    Ok(solution)
+    let lines = [
+        format!("computers: {}, connections: {}", network_map.computers().count(), network_map.connections().len()),
+        format!("components: {} (sizes {})", components.len(), component_sizes.join(", ")),
+        format!("degrees: {}", degrees.join(", ")),
+        format!("degeneracy: {}, {}-core: {}", degeneracy, degeneracy, join(network_map.k_core(degeneracy))),
+        format!("articulation points: {}", join(network_map.articulation_points())),
+        format!("bridges: {}", if bridges.is_empty() { "none".to_string() } else { bridges.join(", ") })
+    ];
+
+    Ok(lines.join("\n"))
 }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse puzzle input: {0}")]
-    CouldntParseInput(#[from] ParseNetworkMapError)
-}
\ No newline at end of file
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 23;
+
+    type Input = NetworkMap;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn part1(network_map: &NetworkMap) -> Result<usize, SolutionError> {
+        let clusters = network_map.clusters_of_three();
+
+        let t_count = clusters.iter()
+            .filter(|c| c.iter().any(|n| n.starts_with('t')))
+            .count();
+
+        Ok(t_count)
+    }
+
+    fn part2(network_map: &NetworkMap) -> Result<String, SolutionError> {
+        let largest_network: Vec<&str> = network_map.maximum_clique()
+            .into_iter()
+            .map(|computer| computer.as_str())
+            .collect();
+
+        if largest_network.is_empty() {
+            return Err(SolutionError::NoSolution);
+        }
+
+        Ok(largest_network.join(","))
+    }
+}
@@ -11,53 +11,19 @@ impl NetworkMap {
         NetworkMap { graph }
     }
 
-    pub fn clusters_of_three(&self) -> HashSet<[&String;3]> {
-        let mut clusters = HashSet::new();
-
-        for (computer, neighbors) in self.graph.iter() {
-            for neighbor in neighbors.iter() {
-                if let Some(twobors) = self.graph.get(neighbor) {
-                    for twobor in twobors.iter() {
-                        if let Some(threebors) = self.graph.get(twobor) {
-                            if threebors.contains(computer) {
-                                let mut cluster = [computer, neighbor, twobor];
-                                cluster.sort();
-
-                                clusters.insert(cluster);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        clusters
+    pub fn computers(&self) -> impl Iterator<Item = &String> {
+        self.graph.keys()
     }
 
-    pub fn largest_network(&self) -> Vec<String> {
-        let mut masks = HashMap::new();
-        for (node, neighbors) in self.graph.iter() {
-            let mut mask = neighbors.clone();
-            mask.insert(node.clone());
-
-            masks.insert(node, mask);
-        }
-
-        let mut combined = HashMap::new();
-        for (_, mask) in masks.iter() {
-            for (_, other_mask) in masks.iter() {
-                let mut intersection: Vec<_> = mask.intersection(other_mask).cloned().collect();
-                intersection.sort_unstable();
-
-                if !intersection.is_empty() {
-                    
-                    *combined.entry(intersection).or_insert(0_usize) += 1;
-                }
-            }
-        }
+    pub fn neighbors(&self, computer: &str) -> impl Iterator<Item = &String> {
+        self.graph.get(computer).into_iter().flatten()
+    }
 
-        let most_common = combined.iter().max_by_key(|(_, count)| **count).unwrap().0;
-        most_common.clone()
+    pub fn clusters_of_three(&self) -> HashSet<[&String;3]> {
+        self.cliques_of_size(3)
+            .into_iter()
+            .map(|clique| [clique[0], clique[1], clique[2]])
+            .collect()
     }
 }
 
@@ -69,10 +35,10 @@ impl FromStr for NetworkMap {
 
         for line in s.lines() {
             if let Some((left, right)) = line.split_once('-') {
-                let left_entry = graph.entry(left.to_string()).or_insert_with(|| HashSet::new());
+                let left_entry = graph.entry(left.to_string()).or_insert_with(HashSet::new);
                 left_entry.insert(right.to_string());
 
-                let right_entry = graph.entry(right.to_string()).or_insert_with(|| HashSet::new());
+                let right_entry = graph.entry(right.to_string()).or_insert_with(HashSet::new);
                 right_entry.insert(left.to_string());
             } else {
                 return Err(ParseNetworkMapError::InvalidSyntax);
@@ -0,0 +1,435 @@
+use std::fmt::Display;
+
+use aoc_common::SolutionError;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use thiserror::Error;
+
+use crate::gates::{Device, GateOperation};
+
+const VALIDATION_ROUNDS: usize = 100;
+
+/// Repairs with more swaps than this aren't trimmed, as every subset would be validated.
+const MAX_TRIMMED_FAULTS: usize = 12;
+
+impl Device {
+    /// Checks that the device is a ripple-carry adder of its x and y wires into its z wires,
+    /// matching each stage against the usual full adder:
+    ///
+    /// ```text
+    /// x XOR y -> s      s XOR carry_in -> z      t OR a -> carry_out
+    /// x AND y -> a      s AND carry_in -> t
+    /// ```
+    ///
+    /// The repair is greedy: wherever a gate's output doesn't fit, the wire it should have
+    /// been swapped with is recorded and the swap applied before carrying on, so that later
+    /// stages are judged against a repaired device. The swaps are then checked by adding
+    /// random inputs, and trimmed to the smallest subset of them that still adds correctly.
+    /// That's minimal among the swaps the stage matching proposes, not over every possible
+    /// pair of wires.
+    pub fn verify_adder(&self) -> Result<AdderReport, VerifyAdderError> {
+        let bits = self.wires_with_prefix("z").len();
+        if bits < 2 {
+            return Err(VerifyAdderError::TooFewOutputs(bits));
+        }
+
+        // Validation adds the inputs as plain integers, so the sum has to fit in one
+        if bits > usize::BITS as usize {
+            return Err(VerifyAdderError::TooManyOutputs(bits));
+        }
+
+        let input_bits = bits - 1;
+        let mut device = self.clone();
+        let mut faults = vec![];
+        let mut carry = None;
+        let mut stage = 0;
+
+        while stage < input_bits {
+            // Guards against fixes that keep undoing each other
+            if faults.len() > self.wires().len() {
+                return Err(VerifyAdderError::Unrepairable(stage));
+            }
+
+            match device.match_adder_stage(stage, carry.as_deref())? {
+                StageMatch::Carry(next_carry) => {
+                    carry = Some(next_carry);
+                    stage += 1;
+                },
+                StageMatch::Fault(fault) => {
+                    device.apply_fix(&fault)?;
+
+                    // The carry gate itself may have been one side of the swap
+                    let (actual, expected) = fault.swap();
+                    if carry.as_deref() == Some(actual) {
+                        carry = Some(expected.to_string());
+                    } else if carry.as_deref() == Some(expected) {
+                        carry = Some(actual.to_string());
+                    }
+
+                    faults.push(fault);
+                }
+            }
+        }
+
+        let carry = carry.expect("at least one stage was matched");
+        let last_output = wire_name('z', input_bits);
+        if carry != last_output {
+            let fault = AdderFault::new(input_bits - 1, AdderRole::CarryOut, carry, last_output);
+            device.apply_fix(&fault)?;
+            faults.push(fault);
+        }
+
+        let validated = device.validate_adder(input_bits);
+        let faults = if validated { self.smallest_repair(faults, input_bits) } else { faults };
+
+        Ok(AdderReport { bits, faults, validated })
+    }
+
+    /// The fewest of `faults` that still make a working adder when their swaps are applied
+    /// in order, trying every subset from the smallest up. `faults` itself is assumed to work.
+    fn smallest_repair(&self, faults: Vec<AdderFault>, input_bits: usize) -> Vec<AdderFault> {
+        if faults.len() > MAX_TRIMMED_FAULTS {
+            return faults;
+        }
+
+        for size in 0..faults.len() {
+            for subset in (0u32..1 << faults.len()).filter(|subset| subset.count_ones() as usize == size) {
+                let chosen: Vec<_> = faults.iter()
+                    .enumerate()
+                    .filter(|(i, _)| subset & 1 << i != 0)
+                    .map(|(_, fault)| fault.clone())
+                    .collect();
+
+                let mut device = self.clone();
+                if chosen.iter().all(|fault| device.apply_fix(fault).is_ok()) && device.validate_adder(input_bits) {
+                    return chosen;
+                }
+            }
+        }
+
+        faults
+    }
+
+    fn match_adder_stage(&self, stage: usize, carry: Option<&str>) -> Result<StageMatch, VerifyAdderError> {
+        let x = wire_name('x', stage);
+        let y = wire_name('y', stage);
+        let z = wire_name('z', stage);
+
+        let find = |left: &str, right: &str, operation, role| {
+            self.find_output(left, right, operation)
+                .ok_or(VerifyAdderError::MissingGate(stage, role))
+        };
+
+        let input_xor = find(&x, &y, GateOperation::Xor, AdderRole::InputXor)?;
+        let input_and = find(&x, &y, GateOperation::And, AdderRole::InputAnd)?;
+
+        // The first stage is a half adder, with no carry in
+        let Some(carry) = carry else {
+            if input_xor != z {
+                return Ok(StageMatch::Fault(AdderFault::new(stage, AdderRole::InputXor, input_xor, z)));
+            }
+
+            return Ok(StageMatch::Carry(input_and));
+        };
+
+        let sum = match self.find_output(carry, &input_xor, GateOperation::Xor) {
+            Some(sum) => sum,
+            None => {
+                // Whichever of the two is still wired into a sum gate is right, and the
+                // other input of that gate is what the wrong one should have been
+                if let Some(other) = self.other_input(carry, GateOperation::Xor) {
+                    let fault = AdderFault::new(stage, AdderRole::InputXor, input_xor, other);
+                    return Ok(StageMatch::Fault(fault));
+                }
+
+                if let Some(other) = self.other_input(&input_xor, GateOperation::Xor) {
+                    let fault = AdderFault::new(stage - 1, AdderRole::CarryOut, carry.to_string(), other);
+                    return Ok(StageMatch::Fault(fault));
+                }
+
+                return Err(VerifyAdderError::MissingGate(stage, AdderRole::Sum));
+            }
+        };
+
+        if sum != z {
+            return Ok(StageMatch::Fault(AdderFault::new(stage, AdderRole::Sum, sum, z)));
+        }
+
+        let carry_and = find(carry, &input_xor, GateOperation::And, AdderRole::CarryAnd)?;
+
+        match self.find_output(&carry_and, &input_and, GateOperation::Or) {
+            Some(next_carry) => Ok(StageMatch::Carry(next_carry)),
+            None => {
+                if let Some(other) = self.other_input(&carry_and, GateOperation::Or) {
+                    return Ok(StageMatch::Fault(AdderFault::new(stage, AdderRole::InputAnd, input_and, other)));
+                }
+
+                if let Some(other) = self.other_input(&input_and, GateOperation::Or) {
+                    return Ok(StageMatch::Fault(AdderFault::new(stage, AdderRole::CarryAnd, carry_and, other)));
+                }
+
+                Err(VerifyAdderError::MissingGate(stage, AdderRole::CarryOut))
+            }
+        }
+    }
+
+    /// The other input of the first `operation` gate that `wire` feeds into.
+    fn other_input(&self, wire: &str, operation: GateOperation) -> Option<String> {
+        self.gates_with_input(wire).iter()
+            .find(|gate| gate.operation() == operation)
+            .map(|gate| if gate.left_input() == wire { gate.right_input() } else { gate.left_input() })
+            .map(|other| other.to_string())
+    }
+
+    fn apply_fix(&mut self, fault: &AdderFault) -> Result<(), VerifyAdderError> {
+        let (actual, expected) = fault.swap();
+        if self.gate_for_output(actual).is_none() || self.gate_for_output(expected).is_none() {
+            return Err(VerifyAdderError::Unrepairable(fault.stage));
+        }
+
+        self.swap_wires(actual, expected);
+
+        Ok(())
+    }
+
+    fn validate_adder(&mut self, input_bits: usize) -> bool {
+        let mut rng = StdRng::seed_from_u64(24);
+        let mask = (1 << input_bits) - 1;
+
+        (0..VALIDATION_ROUNDS).all(|_| {
+            let x = rng.random::<u64>() as usize & mask;
+            let y = rng.random::<u64>() as usize & mask;
+
+            let values = self.values_mut();
+            for bit in 0..input_bits {
+                values.insert(wire_name('x', bit), x >> bit & 1 == 1);
+                values.insert(wire_name('y', bit), y >> bit & 1 == 1);
+            }
+
+            // A swap that creates a cycle can't be right
+            self.solve().is_ok_and(|output| output.to_usize() == x + y)
+        })
+    }
+}
+
+fn wire_name(prefix: char, bit: usize) -> String {
+    format!("{}{:02}", prefix, bit)
+}
+
+enum StageMatch {
+    Carry(String),
+    Fault(AdderFault)
+}
+
+/// The gates making up one stage of a ripple-carry adder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdderRole {
+    InputXor,
+    InputAnd,
+    Sum,
+    CarryAnd,
+    CarryOut
+}
+
+impl Display for AdderRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdderRole::InputXor => write!(f, "x XOR y"),
+            AdderRole::InputAnd => write!(f, "x AND y"),
+            AdderRole::Sum => write!(f, "sum"),
+            AdderRole::CarryAnd => write!(f, "carry AND"),
+            AdderRole::CarryOut => write!(f, "carry out")
+        }
+    }
+}
+
+/// A gate whose output is wired to `actual` where the adder needs `expected`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdderFault {
+    stage: usize,
+    role: AdderRole,
+    actual: String,
+    expected: String
+}
+
+impl AdderFault {
+    fn new(stage: usize, role: AdderRole, actual: String, expected: String) -> AdderFault {
+        AdderFault { stage, role, actual, expected }
+    }
+
+    pub fn stage(&self) -> usize {
+        self.stage
+    }
+
+    pub fn role(&self) -> AdderRole {
+        self.role
+    }
+
+    /// The pair of wires to pass to [`Device::swap_wires`] to fix this fault.
+    pub fn swap(&self) -> (&str, &str) {
+        (&self.actual, &self.expected)
+    }
+}
+
+impl Display for AdderFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bit {}: {} gate outputs {}, expected {}", self.stage, self.role, self.actual, self.expected)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdderReport {
+    bits: usize,
+    faults: Vec<AdderFault>,
+    validated: bool
+}
+
+impl AdderReport {
+    /// Number of output bits, including the final carry.
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    pub fn faults(&self) -> &[AdderFault] {
+        &self.faults
+    }
+
+    /// Every gate output involved in a proposed swap, sorted.
+    pub fn deviating_wires(&self) -> Vec<&str> {
+        let mut wires: Vec<_> = self.faults.iter()
+            .flat_map(|fault| [fault.actual.as_str(), fault.expected.as_str()])
+            .collect();
+
+        wires.sort();
+        wires
+    }
+
+    /// Whether the device, with every proposed swap applied, added up random inputs correctly.
+    pub fn is_validated(&self) -> bool {
+        self.validated
+    }
+}
+
+impl Display for AdderReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}-bit adder, {} fault(s)", self.bits, self.faults.len())?;
+
+        for fault in self.faults.iter() {
+            let (actual, expected) = fault.swap();
+            writeln!(f, "  {} (swap {} with {})", fault, actual, expected)?;
+        }
+
+        if self.validated {
+            write!(f, "swaps validated on {} random inputs", VALIDATION_ROUNDS)
+        } else {
+            write!(f, "swaps did not validate")
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum VerifyAdderError {
+    #[error("an adder needs at least 2 z wires, found {0}")]
+    TooFewOutputs(usize),
+    #[error("too many z wires to check the sums as integers: {0}")]
+    TooManyOutputs(usize),
+    #[error("bit {0}: no {1} gate")]
+    MissingGate(usize, AdderRole),
+    #[error("bit {0}: couldn't be repaired by swapping gate outputs")]
+    Unrepairable(usize)
+}
+
+impl From<VerifyAdderError> for SolutionError {
+    fn from(err: VerifyAdderError) -> SolutionError {
+        SolutionError::Unsolvable(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use crate::gates::PuzzleInput;
+
+    use super::*;
+
+    /// A correct ripple-carry adder of two `bits`-bit numbers.
+    fn adder_input(bits: usize) -> PuzzleInput {
+        let mut lines = vec![];
+        for bit in 0..bits {
+            lines.push(format!("x{:02}: 0", bit));
+            lines.push(format!("y{:02}: 0", bit));
+        }
+
+        lines.push("".to_string());
+        lines.push("x00 XOR y00 -> z00".to_string());
+        lines.push("y00 AND x00 -> c00".to_string());
+
+        for bit in 1..bits {
+            let carry_out = if bit == bits - 1 { wire_name('z', bits) } else { format!("c{:02}", bit) };
+
+            lines.push(format!("x{0:02} XOR y{0:02} -> s{0:02}", bit));
+            lines.push(format!("x{0:02} AND y{0:02} -> a{0:02}", bit));
+            lines.push(format!("c{:02} XOR s{:02} -> z{:02}", bit - 1, bit, bit));
+            lines.push(format!("s{:02} AND c{:02} -> t{:02}", bit, bit - 1, bit));
+            lines.push(format!("a{0:02} OR t{0:02} -> {1}", bit, carry_out));
+        }
+
+        lines.join("\n").parse().unwrap()
+    }
+
+    /// [`adder_input`] with the given outputs swapped.
+    fn adder(bits: usize, swaps: &[(&str, &str)]) -> Device {
+        let mut device = adder_input(bits).into_device();
+
+        for (one, two) in swaps {
+            device.swap_wires(one, two);
+        }
+
+        device
+    }
+
+    #[test]
+    pub fn correct_adder() {
+        let report = adder(6, &[]).verify_adder().unwrap();
+
+        assert_eq!(report.bits(), 7);
+        assert!(report.faults().is_empty());
+        assert!(report.is_validated());
+
+        assert_eq!(crate::Day::part2(&adder_input(6)).unwrap(), "");
+        assert!(adder(63, &[]).verify_adder().unwrap().is_validated());
+        assert!(matches!(adder(64, &[]).verify_adder(), Err(VerifyAdderError::TooManyOutputs(65))));
+    }
+
+    #[test]
+    pub fn finds_swaps() {
+        let device = adder(8, &[("z03", "t03"), ("s05", "a05"), ("z07", "c06"), ("a02", "z08")]);
+        let report = device.verify_adder().unwrap();
+
+        assert_eq!(
+            report.deviating_wires(),
+            vec!["a02", "a05", "c06", "s05", "t03", "z03", "z07", "z08"]
+        );
+        assert!(report.is_validated());
+
+        let sum_fault = &report.faults()[1];
+        assert_eq!(sum_fault.stage(), 3);
+        assert_eq!(sum_fault.role(), AdderRole::Sum);
+        assert_eq!(sum_fault.to_string(), "bit 3: sum gate outputs t03, expected z03");
+    }
+
+    #[test]
+    pub fn trims_redundant_swaps() {
+        let fault = |stage, actual: &str, expected: &str| {
+            AdderFault::new(stage, AdderRole::Sum, actual.to_string(), expected.to_string())
+        };
+
+        // Swapping the same pair twice undoes itself, so only the real fix is needed
+        let device = adder(6, &[("z03", "t03")]);
+        let faults = vec![fault(1, "s01", "a01"), fault(3, "t03", "z03"), fault(1, "s01", "a01")];
+
+        assert_eq!(device.smallest_repair(faults, 6), vec![fault(3, "t03", "z03")]);
+        assert_eq!(adder(6, &[]).smallest_repair(vec![fault(1, "s01", "a01"), fault(1, "a01", "s01")], 6), vec![]);
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use aoc_common::SolutionError;
+use thiserror::Error;
+
+use crate::gates::{Gate, GateOperation};
+
+/// The gates compiled down to wire indices and sorted so that every gate comes after the
+/// gates feeding it. Evaluating them in order needs a single pass.
+#[derive(Debug, Clone)]
+pub(crate) struct EvaluationPlan {
+    wire_names: Vec<String>,
+    wire_ids: HashMap<String, usize>,
+    steps: Vec<Step>,
+    step_for_wire: Vec<Option<usize>>,
+    consumers: Vec<Vec<usize>>
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    left: usize,
+    right: usize,
+    output: usize,
+    operation: GateOperation
+}
+
+impl EvaluationPlan {
+    /// Wires are numbered in sorted order, so recompiling the same wires after a swap keeps
+    /// their ids stable.
+    pub(crate) fn compile<'a>(wires: impl IntoIterator<Item = &'a String>, gates: &[Gate]) -> Result<EvaluationPlan, EvaluationError> {
+        let wire_names: Vec<String> = wires.into_iter().cloned().collect::<BTreeSet<_>>().into_iter().collect();
+        let wire_ids: HashMap<String, usize> = wire_names.iter()
+            .enumerate()
+            .map(|(id, name)| (name.clone(), id))
+            .collect();
+
+        let unordered: Vec<Step> = gates.iter()
+            .map(|gate| Step {
+                left: wire_ids[gate.left_input()],
+                right: wire_ids[gate.right_input()],
+                output: wire_ids[gate.output()],
+                operation: gate.operation()
+            })
+            .collect();
+
+        let mut driver = vec![None; wire_names.len()];
+        for (i, step) in unordered.iter().enumerate() {
+            driver[step.output] = Some(i);
+        }
+
+        // Kahn's algorithm: a gate is ready once every gate driving its inputs has been placed
+        let mut pending_inputs: Vec<usize> = unordered.iter()
+            .map(|step| [step.left, step.right].iter().filter(|&&wire| driver[wire].is_some()).count())
+            .collect();
+        let mut readers = vec![vec![]; wire_names.len()];
+        for (i, step) in unordered.iter().enumerate() {
+            readers[step.left].push(i);
+            readers[step.right].push(i);
+        }
+
+        let mut ready: VecDeque<usize> = (0..unordered.len()).filter(|&i| pending_inputs[i] == 0).collect();
+        let mut order = vec![];
+
+        while let Some(i) = ready.pop_front() {
+            order.push(i);
+
+            for &reader in readers[unordered[i].output].iter() {
+                pending_inputs[reader] -= 1;
+                if pending_inputs[reader] == 0 {
+                    ready.push_back(reader);
+                }
+            }
+        }
+
+        if order.len() < unordered.len() {
+            let cycle = find_cycle(&unordered, &driver, &pending_inputs);
+            let cycle = cycle.into_iter().map(|wire| wire_names[wire].clone()).collect();
+
+            return Err(EvaluationError::Cycle(cycle));
+        }
+
+        let steps: Vec<Step> = order.iter().map(|&i| unordered[i]).collect();
+
+        let mut step_for_wire = vec![None; wire_names.len()];
+        let mut consumers = vec![vec![]; wire_names.len()];
+        for (i, step) in steps.iter().enumerate() {
+            step_for_wire[step.output] = Some(i);
+            consumers[step.left].push(i);
+            consumers[step.right].push(i);
+        }
+
+        Ok(EvaluationPlan { wire_names, wire_ids, steps, step_for_wire, consumers })
+    }
+
+    pub(crate) fn wire_count(&self) -> usize {
+        self.wire_names.len()
+    }
+
+    pub(crate) fn wire_names(&self) -> &[String] {
+        &self.wire_names
+    }
+
+    pub(crate) fn wire_id(&self, wire: &str) -> Option<usize> {
+        self.wire_ids.get(wire).copied()
+    }
+
+    /// Wires that aren't driven by any gate, so their values have to be supplied.
+    pub(crate) fn input_wires(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.wire_names.len()).filter(|&wire| self.step_for_wire[wire].is_none())
+    }
+
+    pub(crate) fn output_wires(&self) -> impl Iterator<Item = usize> + '_ {
+        self.steps.iter().map(|step| step.output)
+    }
+
+    /// Gates reading from `wire`, as positions in the evaluation order.
+    pub(crate) fn consumers(&self, wire: usize) -> &[usize] {
+        &self.consumers[wire]
+    }
+
+    pub(crate) fn step_for_wire(&self, wire: usize) -> Option<usize> {
+        self.step_for_wire[wire]
+    }
+
+    /// Swaps which wires two gates drive, as [`crate::gates::Device::swap_wires`] does. Only
+    /// gates reading the two wires can end up before the gate now driving them, so only the
+    /// steps between the earliest and latest of those are sorted again. Gives false and
+    /// leaves the plan alone if the swap would create a cycle.
+    pub(crate) fn swap_outputs(&mut self, wire_one: &str, wire_two: &str) -> bool {
+        let (Some(one), Some(two)) = (self.wire_id(wire_one), self.wire_id(wire_two)) else {
+            return false;
+        };
+
+        let (Some(step_one), Some(step_two)) = (self.step_for_wire[one], self.step_for_wire[two]) else {
+            return false;
+        };
+
+        self.set_output(step_one, two);
+        self.set_output(step_two, one);
+
+        let mut window: Option<(usize, usize)> = None;
+        for (producer, wire) in [(step_one, two), (step_two, one)] {
+            for &consumer in self.consumers[wire].iter().filter(|&&consumer| consumer <= producer) {
+                let (low, high) = window.unwrap_or((consumer, producer));
+                window = Some((low.min(consumer), high.max(producer)));
+            }
+        }
+
+        let Some((low, high)) = window else {
+            return true;
+        };
+
+        match self.sort_window(low, high) {
+            Some(order) => {
+                self.reorder_window(low, &order);
+                true
+            },
+            None => {
+                self.set_output(step_one, one);
+                self.set_output(step_two, two);
+                false
+            }
+        }
+    }
+
+    fn set_output(&mut self, step: usize, wire: usize) {
+        self.steps[step].output = wire;
+        self.step_for_wire[wire] = Some(step);
+    }
+
+    /// Kahn's algorithm over the steps from `low` to `high`, ignoring gates outside them,
+    /// which are already in order relative to the whole window. Ties keep the old order.
+    fn sort_window(&self, low: usize, high: usize) -> Option<Vec<usize>> {
+        let in_window = |step: &usize| (low..=high).contains(step);
+
+        let mut pending_inputs: Vec<usize> = self.steps[low..=high].iter()
+            .map(|step| [step.left, step.right].iter().filter(|&&wire| self.step_for_wire[wire].is_some_and(|driver| in_window(&driver))).count())
+            .collect();
+
+        let mut ready: BTreeSet<usize> = (low..=high).filter(|&i| pending_inputs[i - low] == 0).collect();
+        let mut order = vec![];
+
+        while let Some(i) = ready.pop_first() {
+            order.push(i);
+
+            for &reader in self.consumers[self.steps[i].output].iter().filter(|reader| in_window(reader)) {
+                pending_inputs[reader - low] -= 1;
+                if pending_inputs[reader - low] == 0 {
+                    ready.insert(reader);
+                }
+            }
+        }
+
+        (order.len() == high - low + 1).then_some(order)
+    }
+
+    /// Moves the steps listed in `order` into the positions starting at `low`, updating every
+    /// index that pointed at one of them.
+    fn reorder_window(&mut self, low: usize, order: &[usize]) {
+        let mut new_position = HashMap::new();
+        for (offset, &old) in order.iter().enumerate() {
+            new_position.insert(old, low + offset);
+        }
+
+        let window: Vec<Step> = order.iter().map(|&old| self.steps[old]).collect();
+        self.steps[low..low + window.len()].copy_from_slice(&window);
+
+        let mut inputs = BTreeSet::new();
+        for (offset, step) in window.iter().enumerate() {
+            self.step_for_wire[step.output] = Some(low + offset);
+            inputs.extend([step.left, step.right]);
+        }
+
+        for wire in inputs {
+            for consumer in self.consumers[wire].iter_mut() {
+                if let Some(&position) = new_position.get(consumer) {
+                    *consumer = position;
+                }
+            }
+        }
+    }
+
+    /// Re-evaluates `dirty` gates and everything downstream of a gate whose output changed,
+    /// leaving the rest of `values` untouched.
+    pub(crate) fn evaluate(&self, values: &mut [Option<bool>], dirty: impl IntoIterator<Item = usize>) -> Result<(), EvaluationError> {
+        let mut dirty: BTreeSet<usize> = dirty.into_iter().collect();
+
+        while let Some(i) = dirty.pop_first() {
+            let step = self.steps[i];
+            let left = values[step.left].ok_or_else(|| self.missing_value(step.left))?;
+            let right = values[step.right].ok_or_else(|| self.missing_value(step.right))?;
+
+            let output = Some(step.operation.apply(left, right));
+            if values[step.output] != output {
+                values[step.output] = output;
+                dirty.extend(self.consumers[step.output].iter().copied());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn missing_value(&self, wire: usize) -> EvaluationError {
+        EvaluationError::MissingValue(self.wire_names[wire].clone())
+    }
+}
+
+/// Follows unplaced gates back through their unplaced inputs until a wire repeats. Every
+/// unplaced gate has at least one such input, so this always finds a cycle.
+fn find_cycle(steps: &[Step], driver: &[Option<usize>], pending_inputs: &[usize]) -> Vec<usize> {
+    let unplaced = |wire: usize| driver[wire].filter(|&i| pending_inputs[i] > 0);
+
+    let start = (0..steps.len()).find(|&i| pending_inputs[i] > 0).expect("some gate wasn't placed");
+    let mut path = vec![steps[start].output];
+    let mut current = start;
+
+    loop {
+        let step = steps[current];
+        let previous = if unplaced(step.left).is_some() { step.left } else { step.right };
+
+        let repeated = path.iter().position(|&wire| wire == previous);
+        path.push(previous);
+
+        if let Some(position) = repeated {
+            // The path runs against the flow of values, so flip it
+            return path[position..].iter().rev().copied().collect();
+        }
+
+        current = unplaced(previous).expect("unplaced gates have an unplaced input");
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum EvaluationError {
+    #[error("wires form a cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("wire {0} has no value and no gate")]
+    MissingValue(String)
+}
+
+impl From<EvaluationError> for SolutionError {
+    fn from(err: EvaluationError) -> SolutionError {
+        SolutionError::Unsolvable(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gates::{Device, PuzzleInput};
+
+    use super::*;
+
+    const EXAMPLE: &str = "\
+x00: 1
+x01: 0
+x02: 1
+y00: 1
+y01: 1
+y02: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+x01 AND y01 -> a01
+s01 XOR c00 -> z01
+s01 AND c00 -> t01
+a01 OR t01 -> c01
+x02 XOR y02 -> s02
+x02 AND y02 -> a02
+s02 XOR c01 -> z02
+s02 AND c01 -> t02
+a02 OR t02 -> z03";
+
+    fn example() -> Device {
+        EXAMPLE.parse::<PuzzleInput>().unwrap().into_device()
+    }
+
+    #[test]
+    pub fn incremental_inputs() {
+        let mut device = example();
+        assert_eq!(device.solve().unwrap().to_usize(), 0b101 + 0b011);
+
+        device.values_mut().insert("y02".to_string(), true);
+        assert_eq!(device.solve().unwrap().to_usize(), 0b101 + 0b111);
+
+        device.values_mut().retain(|wire, _| wire.starts_with('x') || wire.starts_with('y'));
+        device.values_mut().insert("x01".to_string(), true);
+        assert_eq!(device.solve().unwrap().to_usize(), 0b111 + 0b111);
+    }
+
+    #[test]
+    pub fn incremental_swaps() {
+        let mut device = example();
+        device.solve().unwrap();
+
+        device.swap_wires("z01", "t01");
+        let swapped = device.solve().unwrap();
+
+        let mut fresh = example();
+        fresh.swap_wires("z01", "t01");
+        assert_eq!(fresh.solve().unwrap(), swapped);
+
+        device.swap_wires("z01", "t01");
+        assert_eq!(device.solve().unwrap().to_usize(), 0b101 + 0b011);
+    }
+
+    #[test]
+    pub fn swaps_that_reorder_gates() {
+        // p is read early on, while s OR y is sorted last, so swapping their outputs moves
+        // gates around without making a cycle
+        let source = "x: 1\ny: 0\n\nx AND y -> p\np XOR y -> z00\nz00 OR x -> z01\nx XOR y -> s\ns OR y -> z02";
+        let input: PuzzleInput = source.parse().unwrap();
+
+        let mut device = input.to_device();
+        device.solve().unwrap();
+        device.swap_wires("p", "z02");
+
+        let mut fresh = input.to_device();
+        fresh.swap_wires("p", "z02");
+        assert_eq!(device.solve().unwrap(), fresh.solve().unwrap());
+        assert_eq!(device.solve().unwrap().to_usize(), 0b011);
+
+        // Swapping back leaves a valid order too
+        device.swap_wires("p", "z02");
+        assert_eq!(device.solve().unwrap().to_usize(), 0b110);
+    }
+
+    #[test]
+    pub fn cycles() {
+        let mut device = example();
+
+        // The sum gate for bit 1 then feeds back into its own carry
+        device.swap_wires("z01", "c00");
+
+        assert_eq!(
+            device.solve(),
+            Err(EvaluationError::Cycle(vec!["c00".to_string(), "c00".to_string()]))
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+
+use crate::evaluator::EvaluationError;
+use crate::gates::{Device, Gate, GateOperation};
+
+/// Renders a device's gates as a graph, with a node for every wire and every gate.
+pub struct GraphExport {
+    gates: Vec<Gate>,
+    wires: BTreeSet<String>,
+    highlighted: HashSet<String>,
+    differing: HashSet<String>,
+    cycle: HashSet<String>
+}
+
+impl GraphExport {
+    pub fn new(device: &Device) -> GraphExport {
+        let gates = device.gates();
+        let wires = device.wires().iter().cloned().collect();
+
+        GraphExport { gates, wires, highlighted: HashSet::new(), differing: HashSet::new(), cycle: HashSet::new() }
+    }
+
+    /// An export marked with what's wrong with the device: the output wires that differ from
+    /// [`Device::expected_output`], or the cycle that stops it being evaluated at all.
+    pub fn with_faults(device: &mut Device) -> GraphExport {
+        let export = GraphExport::new(device);
+        let expected_output = device.expected_output();
+
+        match device.solve() {
+            Ok(actual_output) => {
+                // The expected output leaves out leading zeroes, so go by the wires actually present
+                let differing: Vec<_> = actual_output.trues().into_iter()
+                    .chain(actual_output.falses())
+                    .filter(|wire| expected_output.get(wire).unwrap_or(false) != actual_output.get(wire).unwrap_or(false))
+                    .collect();
+
+                export.mark_differing(differing)
+            },
+            Err(EvaluationError::Cycle(wires)) => export.mark_cycle(wires),
+            Err(EvaluationError::MissingValue(_)) => export
+        }
+    }
+
+    /// Highlights these wires, along with any gate connecting two of them.
+    /// Meant for the sets returned by [`Device::find_upstream`] and [`Device::find_downstream`].
+    pub fn highlight(mut self, wires: impl IntoIterator<Item = String>) -> GraphExport {
+        self.highlighted.extend(wires);
+        self
+    }
+
+    /// Marks wires whose value is wrong, such as the differences from [`Device::expected_output`].
+    pub fn mark_differing(mut self, wires: impl IntoIterator<Item = String>) -> GraphExport {
+        self.differing.extend(wires);
+        self
+    }
+
+    /// Marks the wires of a cycle, such as the one in [`EvaluationError::Cycle`], along with
+    /// the gates joining them.
+    pub fn mark_cycle(mut self, wires: impl IntoIterator<Item = String>) -> GraphExport {
+        self.cycle.extend(wires);
+        self
+    }
+
+    fn is_gate_in_cycle(&self, gate: &Gate) -> bool {
+        self.cycle.contains(gate.output())
+            && (self.cycle.contains(gate.left_input()) || self.cycle.contains(gate.right_input()))
+    }
+
+    fn is_gate_highlighted(&self, gate: &Gate) -> bool {
+        self.highlighted.contains(gate.output())
+            && (self.highlighted.contains(gate.left_input()) || self.highlighted.contains(gate.right_input()))
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut output = String::new();
+
+        writeln!(output, "digraph device {{").unwrap();
+        writeln!(output, "    rankdir=LR;").unwrap();
+
+        for wire in self.wires.iter() {
+            let mut attributes = vec![format!("label=\"{}\"", wire), "shape=plaintext".to_string()];
+            if self.highlighted.contains(wire) {
+                attributes.push("style=filled".to_string());
+                attributes.push("fillcolor=lightblue".to_string());
+            }
+
+            if self.differing.contains(wire) {
+                attributes.push("fontcolor=red".to_string());
+            }
+
+            if self.cycle.contains(wire) {
+                attributes.push("fontcolor=orange".to_string());
+            }
+
+            writeln!(output, "    \"{}\" [{}];", wire, attributes.join(", ")).unwrap();
+        }
+
+        for (n, gate) in self.gates.iter().enumerate() {
+            let shape = match gate.operation() {
+                GateOperation::And => "box",
+                GateOperation::Or => "ellipse",
+                GateOperation::Xor => "diamond"
+            };
+
+            let mut attributes = vec![format!("label=\"{}\"", gate.operation()), format!("shape={}", shape)];
+            if self.is_gate_highlighted(gate) {
+                attributes.push("style=filled".to_string());
+                attributes.push("fillcolor=lightblue".to_string());
+            }
+
+            writeln!(output, "    g{} [{}];", n, attributes.join(", ")).unwrap();
+
+            let in_cycle = self.is_gate_in_cycle(gate);
+            for input in [gate.left_input(), gate.right_input()] {
+                if in_cycle && self.cycle.contains(input) {
+                    writeln!(output, "    \"{}\" -> g{} [color=orange, penwidth=2];", input, n).unwrap();
+                } else {
+                    writeln!(output, "    \"{}\" -> g{};", input, n).unwrap();
+                }
+            }
+
+            if in_cycle {
+                writeln!(output, "    g{} -> \"{}\" [color=orange, penwidth=2];", n, gate.output()).unwrap();
+            } else if self.differing.contains(gate.output()) {
+                writeln!(output, "    g{} -> \"{}\" [color=red, penwidth=2];", n, gate.output()).unwrap();
+            } else {
+                writeln!(output, "    g{} -> \"{}\";", n, gate.output()).unwrap();
+            }
+        }
+
+        writeln!(output, "}}").unwrap();
+
+        output
+    }
+
+    /// A Mermaid flowchart of the same graph. Wire nodes are prefixed with `w_` so they can't
+    /// collide with Mermaid keywords.
+    pub fn to_mermaid(&self) -> String {
+        let mut output = String::new();
+
+        writeln!(output, "flowchart LR").unwrap();
+
+        for wire in self.wires.iter() {
+            writeln!(output, "    w_{}[\"{}\"]", wire, wire).unwrap();
+        }
+
+        for (n, gate) in self.gates.iter().enumerate() {
+            let node = match gate.operation() {
+                GateOperation::And => format!("g{}[AND]", n),
+                GateOperation::Or => format!("g{}([OR])", n),
+                GateOperation::Xor => format!("g{}{{XOR}}", n)
+            };
+
+            writeln!(output, "    {}", node).unwrap();
+            writeln!(output, "    w_{} --> g{}", gate.left_input(), n).unwrap();
+            writeln!(output, "    w_{} --> g{}", gate.right_input(), n).unwrap();
+            writeln!(output, "    g{} --> w_{}", n, gate.output()).unwrap();
+        }
+
+        let highlighted_wires = self.wires.iter()
+            .filter(|wire| self.highlighted.contains(*wire))
+            .map(|wire| format!("w_{}", wire));
+        let highlighted_gates = self.gates.iter().enumerate()
+            .filter(|(_, gate)| self.is_gate_highlighted(gate))
+            .map(|(n, _)| format!("g{}", n));
+        let highlighted: Vec<_> = highlighted_wires.chain(highlighted_gates).collect();
+
+        if !highlighted.is_empty() {
+            writeln!(output, "    classDef highlighted fill:#add8e6").unwrap();
+            writeln!(output, "    class {} highlighted", highlighted.join(",")).unwrap();
+        }
+
+        let differing: Vec<_> = self.wires.iter()
+            .filter(|wire| self.differing.contains(*wire))
+            .map(|wire| format!("w_{}", wire))
+            .collect();
+
+        if !differing.is_empty() {
+            writeln!(output, "    classDef differing stroke:#f00,stroke-width:2px,color:#f00").unwrap();
+            writeln!(output, "    class {} differing", differing.join(",")).unwrap();
+        }
+
+        let cycle_wires = self.wires.iter()
+            .filter(|wire| self.cycle.contains(*wire))
+            .map(|wire| format!("w_{}", wire));
+        let cycle_gates = self.gates.iter().enumerate()
+            .filter(|(_, gate)| self.is_gate_in_cycle(gate))
+            .map(|(n, _)| format!("g{}", n));
+        let cycle: Vec<_> = cycle_wires.chain(cycle_gates).collect();
+
+        if !cycle.is_empty() {
+            writeln!(output, "    classDef cycle stroke:#ffa500,stroke-width:2px,color:#ffa500").unwrap();
+            writeln!(output, "    class {} cycle", cycle.join(",")).unwrap();
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gates::PuzzleInput;
+
+    use super::*;
+
+    fn example() -> Device {
+        let puzzle_input: PuzzleInput = "\
+x00: 1
+x01: 1
+y00: 0
+y01: 1
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x00 OR y01 -> z02"
+            .parse()
+            .unwrap();
+
+        puzzle_input.into_device()
+    }
+
+    #[test]
+    pub fn dot() {
+        let device = example();
+        let export = GraphExport::new(&device)
+            .highlight(device.find_upstream("z01", usize::MAX))
+            .mark_differing(["z00".to_string()]);
+
+        let dot = export.to_dot();
+
+        assert!(dot.starts_with("digraph device {\n"));
+        assert!(dot.contains("    \"x01\" [label=\"x01\", shape=plaintext, style=filled, fillcolor=lightblue];\n"));
+        assert!(dot.contains("    \"z00\" [label=\"z00\", shape=plaintext, fontcolor=red];\n"));
+        assert!(dot.contains("    g0 [label=\"AND\", shape=box];\n"));
+        assert!(dot.contains("    g1 [label=\"XOR\", shape=diamond, style=filled, fillcolor=lightblue];\n"));
+        assert!(dot.contains("    g2 [label=\"OR\", shape=ellipse];\n"));
+        assert!(dot.contains("    g0 -> \"z00\" [color=red, penwidth=2];\n"));
+    }
+
+    #[test]
+    pub fn mermaid() {
+        let device = example();
+        let export = GraphExport::new(&device).highlight(device.find_downstream("y01", usize::MAX));
+
+        let mermaid = export.to_mermaid();
+
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("    g1{XOR}\n    w_x01 --> g1\n    w_y01 --> g1\n    g1 --> w_z01\n"));
+        assert!(mermaid.contains("    class w_y01,w_z01,w_z02,g1,g2 highlighted\n"));
+        assert!(!mermaid.contains("differing"));
+    }
+
+    #[test]
+    pub fn cyclic_device() {
+        let puzzle_input: PuzzleInput = "\
+x00: 1
+y00: 1
+
+x00 AND y00 -> a
+a XOR y00 -> b
+b OR x00 -> z00
+a AND b -> z01"
+            .parse()
+            .unwrap();
+
+        // b now feeds a, which feeds b
+        let mut device = puzzle_input.into_device();
+        device.swap_wires("a", "z00");
+
+        let export = GraphExport::with_faults(&mut device);
+
+        let dot = export.to_dot();
+        assert!(dot.contains("    \"b\" [label=\"b\", shape=plaintext, fontcolor=orange];\n"));
+        assert!(dot.contains("    \"x00\" [label=\"x00\", shape=plaintext];\n"));
+        assert!(dot.contains("    g2 -> \"a\" [color=orange, penwidth=2];\n"));
+        assert!(dot.contains("    \"b\" -> g2 [color=orange, penwidth=2];\n"));
+        assert!(dot.contains("    \"x00\" -> g2;\n"));
+        assert!(!dot.contains("red"));
+
+        let mermaid = export.to_mermaid();
+        assert!(mermaid.contains("    class w_a,w_b,g1,g2 cycle\n"));
+    }
+
+    #[test]
+    pub fn faults_on_working_device() {
+        let mut device = example();
+
+        // 3 + 2 should be 101, but the gates give 100
+        let dot = GraphExport::with_faults(&mut device).to_dot();
+        assert!(dot.contains("    \"z00\" [label=\"z00\", shape=plaintext, fontcolor=red];\n"));
+        assert!(dot.contains("    \"z01\" [label=\"z01\", shape=plaintext];\n"));
+        assert!(!dot.contains("orange"));
+    }
+}
@@ -1,18 +1,25 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::rc::Rc;
 use std::str::FromStr;
 
-use itertools::chain;
 use thiserror::Error;
 
-#[derive(Debug, Clone)]
+use crate::evaluator::{EvaluationError, EvaluationPlan};
+
+#[derive(Debug)]
 pub struct Device {
     wires: HashSet<String>,
     values: HashMap<String, bool>,
     gates: Vec<Rc<RefCell<Gate>>>,
     gates_by_output: HashMap<String, Rc<RefCell<Gate>>>,
-    gates_by_input: HashMap<String, Vec<Rc<RefCell<Gate>>>>
+    gates_by_input: HashMap<String, Vec<Rc<RefCell<Gate>>>>,
+    plan: Option<EvaluationPlan>,
+    // Every wire's value as of the last solve, indexed by the plan's wire ids
+    evaluated: Vec<Option<bool>>,
+    // Outputs swapped since the last solve, whose gates need evaluating again
+    stale_wires: Vec<String>
 }
 
 impl Device {
@@ -24,7 +31,7 @@ impl Device {
         }
         
         let mut gates_by_output = HashMap::new();
-        let mut gates_by_input = HashMap::new();
+        let mut gates_by_input: HashMap<String, Vec<Rc<RefCell<Gate>>>> = HashMap::new();
 
         let gates: Vec<Rc<RefCell<Gate>>> = gates.into_iter().map(|g| Rc::new(RefCell::new(g))).collect();
 
@@ -33,13 +40,22 @@ impl Device {
             wires.insert(gate.borrow().right_input.to_string());
             wires.insert(gate.borrow().output.to_string());
 
-            gates_by_input.entry(gate.borrow().left_input.to_string()).or_insert_with(|| vec![]).push(Rc::clone(gate));
-            gates_by_input.entry(gate.borrow().right_input.to_string()).or_insert_with(|| vec![]).push(Rc::clone(gate));
+            gates_by_input.entry(gate.borrow().left_input.to_string()).or_default().push(Rc::clone(gate));
+            gates_by_input.entry(gate.borrow().right_input.to_string()).or_default().push(Rc::clone(gate));
 
             gates_by_output.insert(gate.borrow().output.to_string(), Rc::clone(gate));
         }
 
-        Device { wires, values, gates, gates_by_output, gates_by_input }
+        Device {
+            wires,
+            values,
+            gates,
+            gates_by_output,
+            gates_by_input,
+            plan: None,
+            evaluated: vec![],
+            stale_wires: vec![]
+        }
     }
 
     pub fn get_value(&self, wire: &str) -> Option<bool> {
@@ -54,49 +70,103 @@ impl Device {
         &self.wires
     }
 
+    pub fn gates(&self) -> Vec<Gate> {
+        self.gates.iter().map(|gate| gate.borrow().clone()).collect()
+    }
+
     pub fn wires_with_prefix(&self, prefix: &str) -> Vec<&String> {
         self.wires.iter().filter(|w| w.starts_with(prefix)).collect()
     }
 
     pub fn swap_wires(&mut self, wire_one: &str, wire_two: &str) {
-        let gate_one = self.gates_by_output.get(wire_one).unwrap();
-        let gate_two = self.gates_by_output.get(wire_two).unwrap();
+        let gate_one = self.gates_by_output.remove(wire_one).unwrap();
+        let gate_two = self.gates_by_output.remove(wire_two).unwrap();
 
         gate_one.borrow_mut().output = wire_two.to_string();
         gate_two.borrow_mut().output = wire_one.to_string();
+
+        self.gates_by_output.insert(wire_two.to_string(), gate_one);
+        self.gates_by_output.insert(wire_one.to_string(), gate_two);
+
+        // A swap that creates a cycle can't be patched in, so the plan is compiled again and
+        // the cycle reported by the next solve
+        if let Some(plan) = self.plan.as_mut()
+            && !plan.swap_outputs(wire_one, wire_two) {
+            self.plan = None;
+        }
+
+        self.stale_wires.push(wire_one.to_string());
+        self.stale_wires.push(wire_two.to_string());
+    }
+
+    pub fn gate_for_output(&self, wire: &str) -> Option<Gate> {
+        self.gates_by_output.get(wire).map(|gate| gate.borrow().clone())
+    }
+
+    pub fn gates_with_input(&self, wire: &str) -> Vec<Gate> {
+        self.gates_by_input.get(wire)
+            .map(|gates| gates.iter().map(|gate| gate.borrow().clone()).collect())
+            .unwrap_or_default()
     }
 
     pub fn find_output(&self, input_one: &str, input_two: &str, operation: GateOperation) -> Option<String> {
         let matching_gate = self.gates.iter()
-            .filter(|gate| ((gate.borrow().left_input == input_one && gate.borrow().right_input == input_two) ||
+            .find(|gate| ((gate.borrow().left_input == input_one && gate.borrow().right_input == input_two) ||
                 (gate.borrow().left_input == input_two && gate.borrow().right_input == input_one))
-                && gate.borrow().operation == operation)
-            .next();
+                && gate.borrow().operation == operation);
 
         matching_gate.map(|gate| gate.borrow().output().to_string())
     }
 
-    pub fn solve(&mut self) -> DeviceOutput {
-        let all_z_wires: Vec<_> = self.wires.iter().filter(|w| w.starts_with('z')).cloned().collect();
-        let mut current_wires = all_z_wires;
-
-        while let Some(wire) = current_wires.pop() {
-            if !self.values.contains_key(&wire) {
-                let gate = self.gates_by_output.get(&wire).expect("wire should either have a value or a gate");
-                match self.evaluate_gate(&gate.borrow()) {
-                    Some(value) => {
-                        self.values.insert(wire.to_string(), value);
-                    },
-                    None => {
-                        current_wires.push(wire);
-                        current_wires.push(gate.borrow().left_input.to_string());
-                        current_wires.push(gate.borrow().right_input.to_string());
-                    }
-                }
+    /// Evaluates every gate and returns the z wires. Only gates downstream of inputs changed
+    /// through [`Device::values_mut`] or outputs moved by [`Device::swap_wires`] since the last
+    /// call are evaluated again.
+    pub fn solve(&mut self) -> Result<DeviceOutput, EvaluationError> {
+        if self.plan.is_none() {
+            self.plan = Some(EvaluationPlan::compile(&self.wires, &self.gates())?);
+        }
+
+        let plan = self.plan.as_ref().unwrap();
+        if self.evaluated.len() != plan.wire_count() {
+            self.evaluated = vec![None; plan.wire_count()];
+        }
+
+        let mut dirty = vec![];
+
+        for wire in plan.input_wires() {
+            let value = self.values.get(&plan.wire_names()[wire]).copied();
+            if value != self.evaluated[wire] {
+                self.evaluated[wire] = value;
+                dirty.extend_from_slice(plan.consumers(wire));
+            }
+        }
+
+        for wire in plan.output_wires() {
+            if self.evaluated[wire].is_none() {
+                dirty.extend(plan.step_for_wire(wire));
             }
         }
 
-        self.wireset_value("z")
+        for wire in self.stale_wires.drain(..) {
+            dirty.extend(plan.wire_id(&wire).and_then(|wire| plan.step_for_wire(wire)));
+        }
+
+        if let Err(err) = plan.evaluate(&mut self.evaluated, dirty) {
+            // Some gates were left unevaluated, so start from scratch next time
+            self.evaluated.clear();
+            return Err(err);
+        }
+
+        for wire in plan.output_wires() {
+            let value = self.evaluated[wire].expect("every gate was evaluated");
+            let name = &plan.wire_names()[wire];
+
+            if self.values.get(name) != Some(&value) {
+                self.values.insert(name.to_string(), value);
+            }
+        }
+
+        Ok(self.wireset_value("z"))
     }
 
     pub fn find_upstream(&self, target_wire: &str, depth_limit: usize) -> HashSet<String> {
@@ -106,11 +176,10 @@ impl Device {
         while let Some((wire, depth)) = wire_stack.pop() {
             connections.insert(wire.to_string());
 
-            if depth > 0 {
-                if let Some(gate) = self.gates_by_output.get(&wire) {
-                    wire_stack.push((gate.borrow().left_input().to_string(), depth - 1));
-                    wire_stack.push((gate.borrow().right_input().to_string(), depth - 1));
-                }
+            if depth > 0
+                && let Some(gate) = self.gates_by_output.get(&wire) {
+                wire_stack.push((gate.borrow().left_input().to_string(), depth - 1));
+                wire_stack.push((gate.borrow().right_input().to_string(), depth - 1));
             }            
         }
 
@@ -124,32 +193,17 @@ impl Device {
         while let Some((wire, depth)) = wire_stack.pop() {
             connections.insert(wire.to_string());
 
-            if depth > 0 {
-                if let Some(gates) = self.gates_by_input.get(&wire) {
-                    for gate in gates {
-                        wire_stack.push((gate.borrow().output().to_string(), depth - 1));
-                    }
-                }    
+            if depth > 0
+                && let Some(gates) = self.gates_by_input.get(&wire) {
+                for gate in gates {
+                    wire_stack.push((gate.borrow().output().to_string(), depth - 1));
+                }
             }            
         }
 
         connections
     }
 
-    fn evaluate_gate(&self, gate: &Gate) -> Option<bool> {
-        let left = self.values.get(&gate.left_input)?;
-        let right = self.values.get(&gate.right_input)?;
-
-        let output = match (left, gate.operation, right) {
-            (true, GateOperation::And, true) => true,
-            (true, GateOperation::Or, _) | (_, GateOperation::Or, true) => true,
-            (true, GateOperation::Xor, false) | (false, GateOperation::Xor, true) => true,
-            _ => false
-        };
-
-        Some(output)
-    }
-
     pub fn expected_output(&self) -> DeviceOutput {
         let xs_value = self.wireset_value("x");
         let ys_value = self.wireset_value("y");
@@ -171,6 +225,16 @@ impl Device {
     }
 }
 
+// Gates are shared between the lookup tables, so a clone needs fresh copies of them rather
+// than more references to the same ones
+impl Clone for Device {
+    fn clone(&self) -> Device {
+        let gates = self.gates.iter().map(|gate| gate.borrow().clone()).collect();
+
+        Device::new(self.values.clone(), gates)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DeviceOutput {
     values: HashMap<String, bool>
@@ -232,13 +296,15 @@ impl DeviceOutput {
 
     pub fn trues(&self) -> HashSet<String> {
         self.values.iter()
-            .filter_map(|(w, v)| v.then(|| w.to_string()))
+            .filter(|(_, v)| **v)
+            .map(|(w, _)| w.to_string())
             .collect()
     }
 
     pub fn falses(&self) -> HashSet<String> {
         self.values.iter()
-            .filter_map(|(w, v)| (!v).then(|| w.to_string()))
+            .filter(|(_, v)| !**v)
+            .map(|(w, _)| w.to_string())
             .collect()
     }
 }
@@ -276,7 +342,7 @@ impl FromStr for PuzzleInput {
 
         let mut lines = s.lines();
 
-        while let Some(line) = lines.next() {
+        for line in lines.by_ref() {
             if line.is_empty() {
                 break;
             }
@@ -292,7 +358,7 @@ impl FromStr for PuzzleInput {
             }
         }
 
-        while let Some(line) = lines.next() {
+        for line in lines {
             let gate: Gate = line.parse()?;
             gates.push(gate);
         }
@@ -374,6 +440,26 @@ pub enum GateOperation {
     Xor
 }
 
+impl GateOperation {
+    pub fn apply(self, left: bool, right: bool) -> bool {
+        match self {
+            GateOperation::And => left && right,
+            GateOperation::Or => left || right,
+            GateOperation::Xor => left ^ right
+        }
+    }
+}
+
+impl Display for GateOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GateOperation::And => write!(f, "AND"),
+            GateOperation::Or => write!(f, "OR"),
+            GateOperation::Xor => write!(f, "XOR")
+        }
+    }
+}
+
 impl FromStr for GateOperation {
     type Err = ParseGateOperationError;
 
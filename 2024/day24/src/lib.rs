@@ -1,17 +1,22 @@
 use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
-use std::io;
 use std::io::LineWriter;
 use std::io::Write;
 use std::path::PathBuf;
 
 use clap::Parser;
 use clap::Subcommand;
-use gates::{ParsePuzzleInputError, PuzzleInput, GateOperation};
-use thiserror::Error;
+use clap::ValueEnum;
+use aoc_common::{solve, ApplicationError, Part, Solution, SolutionError};
 
-mod gates;
+use export::GraphExport;
+use gates::PuzzleInput;
+
+pub mod adder;
+pub mod evaluator;
+pub mod export;
+pub mod gates;
 
 #[derive(Parser)]
 pub struct CliOptions {
@@ -44,103 +49,82 @@ enum Commands {
 
         #[arg(long, default_value_t = usize::MAX)]
         depth: usize
+    },
+    /// Check the device is a ripple-carry adder and propose wire swaps to repair it
+    VerifyAdder {
+        input: PathBuf
+    },
+    /// Render the gates as a graph, marking z wires that don't match the sum of x and y
+    ExportDot {
+        input: PathBuf,
+
+        /// Highlight everything feeding into this wire
+        #[arg(long)]
+        upstream: Option<String>,
+
+        /// Highlight everything this wire feeds into
+        #[arg(long)]
+        downstream: Option<String>,
+
+        #[arg(long, default_value_t = usize::MAX)]
+        depth: usize,
+
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat
     }
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "kebab_case")]
+enum GraphFormat {
+    Dot,
+    Mermaid
+}
+
 pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
     let result = match options.command {
-        Commands::Part1 { input } => run_part1(input),
-        Commands::Part2 { input } => run_part2(input),
+        Commands::Part1 { input } => solve::<Day>(Part::One, &fs::read_to_string(input)?),
+        Commands::Part2 { input } => solve::<Day>(Part::Two, &fs::read_to_string(input)?),
         Commands::OutputCsv { input, output } => output_csv(input, output),
         Commands::MapUpstream { input, target_wire, depth } => map_upstream(input, &target_wire, depth),
         Commands::MapDownstream { input, target_wire, depth } => map_downstream(input, &target_wire, depth),
+        Commands::VerifyAdder { input } => verify_adder(input),
+        Commands::ExportDot { input, upstream, downstream, depth, format } => {
+            export_dot(input, upstream, downstream, depth, format)
+        }
     }?;
 
     Ok(result.to_string())
 }
 
-fn run_part1(input: PathBuf) -> Result<String, ApplicationError> {
-    let puzzle_input = read_puzzle_input(input)?;
-    let mut device = puzzle_input.into_device();
-    
-    Ok(device.solve().to_usize().to_string())
-}
+pub struct Day;
 
-fn run_part2(input: PathBuf) -> Result<String, ApplicationError> {
-    let puzzle_input = read_puzzle_input(input)?;
-    let mut device = puzzle_input.into_device();
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 24;
 
-    let mut previous_carry = device.find_output("x00", "y00", GateOperation::And)
-        .expect("puzzle input missing initial carry gate");
-
-    let mut swapped_wires = vec![];
-
-    let mut n = 1;
-    while n < 45 {
-        println!("inspecting '{n}' adder (carry: {previous_carry})");
-
-        let x = format!("x{n:02}");
-        let y = format!("y{n:02}");
-        let z = format!("z{n:02}");
-
-        let xy_xor = device.find_output(&x, &y, GateOperation::Xor)
-            .expect("puzzle input missing input XOR gate");
-
-        let xy_and = device.find_output(&x, &y, GateOperation::And)
-            .expect("puzzle input missing input AND gate");
-
-        if let Some(carry_xor) = device.find_output(&previous_carry, &xy_xor, GateOperation::Xor) {
-            if carry_xor != z {
-                swapped_wires.push(carry_xor.clone());
-                swapped_wires.push(z.clone());
-
-                device.swap_wires(&carry_xor, &z);
-
-                // Once wires are swapped, continue to attempt this step again
-                continue;
-            }
-
-            if let Some(carry_and) = device.find_output(&previous_carry, &xy_xor, GateOperation::And) {
-                if let Some(next_carry) = device.find_output(&carry_and, &xy_and, GateOperation::Or) {
-                    previous_carry = next_carry;
-                } else {
-                    // some error
-                    todo!();
-                }
-            } else {
-                // some error
-                todo!();
-            }
-        } else {
-            // look for swapped outputs of 'x XOR y' and 'x AND y'
-            if let Some(_) = device.find_output(&previous_carry, &xy_and, GateOperation::Xor) {
-                swapped_wires.push(xy_and.clone());
-                swapped_wires.push(xy_xor.clone());
-                
-                device.swap_wires(&xy_and, &xy_xor);
-                
-                // Once wires are swapped, continue to attempt this step again
-                continue;
-            } else {
-                todo!();
-            }
-        }
+    type Input = PuzzleInput;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn part1(puzzle_input: &PuzzleInput) -> Result<usize, SolutionError> {
+        let mut device = puzzle_input.to_device();
 
-        n += 1;
+        let output = device.solve()?;
+
+        Ok(output.to_usize())
     }
 
-    // Confirm device is now configured correctly
-    let expected_solution = device.expected_output();
-    let solution = device.solve();
+    fn part2(puzzle_input: &PuzzleInput) -> Result<String, SolutionError> {
+        let device = puzzle_input.to_device();
+        let report = device.verify_adder()?;
 
-    if expected_solution != solution {
-        swapped_wires.sort();
+        // An adder that's already correct needs no swaps, and gives an empty answer
+        if !report.is_validated() {
+            return Err(SolutionError::NoSolution);
+        }
 
-        let swapped_wires = swapped_wires.join(",");
-        
-        Ok(swapped_wires)
-    } else {
-        Err(ApplicationError::CouldntFindSolution)
+        Ok(report.deviating_wires().join(","))
     }
 }
 
@@ -149,7 +133,7 @@ fn output_csv(input: PathBuf, output: PathBuf) -> Result<String, ApplicationErro
     let mut device = puzzle_input.to_device();
 
     let expected_output = device.expected_output();
-    let actual_output = device.solve();
+    let actual_output = device.solve().map_err(SolutionError::from)?;
 
     let difference = expected_output.difference(&actual_output);
     
@@ -186,9 +170,7 @@ fn map_upstream(input: PathBuf, target_wire: &str, depth: usize) -> Result<Strin
     let mut connections: Vec<_> = connections.iter().collect();
     connections.sort();
 
-    println!("{:?} ({})", connections, connections.len());
-
-    Ok("".to_string())
+    Ok(format!("{:?} ({})", connections, connections.len()))
 }
 
 fn map_downstream(input: PathBuf, target_wire: &str, depth: usize) -> Result<String, ApplicationError> {
@@ -199,26 +181,49 @@ fn map_downstream(input: PathBuf, target_wire: &str, depth: usize) -> Result<Str
     let mut connections: Vec<_> = connections.iter().collect();
     connections.sort();
 
-    println!("{:?} ({})", connections, connections.len());
+    Ok(format!("{:?} ({})", connections, connections.len()))
+}
 
-    Ok("".to_string())
+fn verify_adder(input: PathBuf) -> Result<String, ApplicationError> {
+    let puzzle_input = read_puzzle_input(input)?;
+    let device = puzzle_input.into_device();
+
+    let report = device.verify_adder().map_err(SolutionError::from)?;
+
+    Ok(report.to_string())
+}
+
+fn export_dot(
+    input: PathBuf,
+    upstream: Option<String>,
+    downstream: Option<String>,
+    depth: usize,
+    format: GraphFormat
+) -> Result<String, ApplicationError> {
+    let puzzle_input = read_puzzle_input(input)?;
+    let mut device = puzzle_input.into_device();
+
+    let mut export = GraphExport::with_faults(&mut device);
+    if let Some(wire) = upstream {
+        export = export.highlight(device.find_upstream(&wire, depth));
+    }
+
+    if let Some(wire) = downstream {
+        export = export.highlight(device.find_downstream(&wire, depth));
+    }
+
+    let output = match format {
+        GraphFormat::Dot => export.to_dot(),
+        GraphFormat::Mermaid => export.to_mermaid()
+    };
+
+    Ok(output)
 }
 
 fn read_puzzle_input(filename: PathBuf) -> Result<PuzzleInput, ApplicationError> {
     let puzzle_input = fs::read_to_string(filename)?;
-    let puzzle_input: PuzzleInput = puzzle_input.parse()?;
+    let puzzle_input = puzzle_input.parse::<PuzzleInput>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
 
     Ok(puzzle_input)
 }
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error),
-    #[error("couldn't parse puzzle input: {0}")]
-    CouldntParsePuzzleInput(#[from] ParsePuzzleInputError),
-    #[error("couldn't find solution")]
-    CouldntFindSolution
-}
\ No newline at end of file
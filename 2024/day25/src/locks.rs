@@ -17,9 +17,6 @@ impl PuzzleInput {
         let keys = self.schematics.iter().filter(|s| s.is_key());
         let locks = self.schematics.iter().filter(|s| s.is_lock());
 
-        println!("{} keys", keys.clone().count());
-        println!("{} locks", locks.clone().count());
-
         let mut count = 0;
 
         for lock in locks {
@@ -41,8 +38,7 @@ impl FromStr for PuzzleInput {
         fn read_schematic(lines: &mut Lines) -> Result<Option<Schematic>, ParsePuzzleInputError> {
             let mut grid = HashSet::new();
 
-            let mut y = 0;
-            while let Some(line) = lines.next() {
+            for (y, line) in lines.by_ref().enumerate() {
                 if line.is_empty() {
                     break;
                 }
@@ -56,8 +52,6 @@ impl FromStr for PuzzleInput {
                         _ => return Err(ParsePuzzleInputError::UnexpectedCharacter(char))
                     }
                 }
-
-                y += 1;
             }
 
             if !grid.is_empty() {
@@ -1,40 +1,21 @@
-use std::fs;
-use std::io;
+use aoc_common::{Solution, SolutionError};
 
-use clap::Parser;
-use thiserror::Error;
 
-#[derive(Parser)]
-pub struct CliOptions {
-    part: u32,
-    filename: std::path::PathBuf
-}
+pub struct Day;
 
-pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
-    let filename = options.filename;
-    let _puzzle_input = fs::read_to_string(filename)?;
-    
-    let result = match options.part {
-        1 => run_part1(),
-        2 => run_part2(),
-        _ => Err(ApplicationError::UnknownPart)
-    }?;
-    
-    Ok(result.to_string())
-}
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 0;
 
-fn run_part1() -> Result<u32, ApplicationError> {
-    todo!()
-}
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-fn run_part2() -> Result<u32, ApplicationError> {
-    todo!()
-}
+    fn part1(_puzzle_input: &String) -> Result<u32, SolutionError> {
+        todo!()
+    }
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error("unknown part")]
-    UnknownPart,
-    #[error("couldn't read puzzle input: {0}")]
-    CouldntReadInput(#[from] io::Error)
-}
\ No newline at end of file
+    fn part2(_puzzle_input: &String) -> Result<u32, SolutionError> {
+        todo!()
+    }
+}
@@ -0,0 +1,48 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Generates the puzzle registry from this crate's own dependencies: every dependency with
+/// a path into a year's directory is a day, and has to export a `Day`. Adding the dependency
+/// is all it takes to register a new day.
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_path = Path::new(&manifest_dir).join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path).unwrap();
+
+    let mut in_dependencies = false;
+    let mut puzzles = String::from("&[\n");
+
+    for line in manifest.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            in_dependencies = line == "[dependencies]";
+            continue;
+        }
+
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        if in_dependencies && is_day_path(value) {
+            puzzles.push_str(&format!("    &{}::Day,\n", name.trim().replace('-', "_")));
+        }
+    }
+
+    puzzles.push(']');
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("puzzles.rs");
+    fs::write(out_path, puzzles).unwrap();
+
+    println!("cargo::rerun-if-changed=Cargo.toml");
+}
+
+/// Whether a dependency's `{ path = "../<year>/..." }` points into a year's directory.
+fn is_day_path(value: &str) -> bool {
+    let Some((_, path)) = value.split_once("path = \"../") else {
+        return false;
+    };
+
+    path.split('/').next().is_some_and(|year| year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()))
+}
@@ -0,0 +1,291 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use aoc_common::Timings;
+use aoc_common::params::{Params, Profile};
+
+use crate::registry;
+use crate::regression::{self, CheckError};
+
+const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Runs before timing starts, to warm caches and the allocator.
+    pub warmup: usize,
+    pub repeats: usize,
+    pub year: Option<u16>,
+    pub day: Option<u8>
+}
+
+/// Summary of the repeated timings of one stage, in nanoseconds.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StageStats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64
+}
+
+impl StageStats {
+    pub fn from_samples(samples: &[Duration]) -> Option<StageStats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<u64> = samples.iter().map(|sample| sample.as_nanos() as u64).collect();
+        nanos.sort();
+
+        let middle = nanos.len() / 2;
+        let median_ns = if nanos.len().is_multiple_of(2) {
+            (nanos[middle - 1] + nanos[middle]) / 2
+        } else {
+            nanos[middle]
+        };
+
+        Some(StageStats {
+            min_ns: nanos[0],
+            median_ns,
+            mean_ns: nanos.iter().sum::<u64>() / nanos.len() as u64
+        })
+    }
+}
+
+/// Timings for one input file. Stages a day can't time separately are `None`; for days
+/// written as a [`aoc_common::Puzzle`] rather than a `Solution`, parsing is counted in each
+/// part.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub year: u16,
+    pub day: u8,
+    pub file: String,
+    pub parse: Option<StageStats>,
+    pub part1: Option<StageStats>,
+    pub part2: Option<StageStats>
+}
+
+impl BenchResult {
+    fn stage(&self, stage: &str) -> Option<StageStats> {
+        match stage {
+            "parse" => self.parse,
+            "part1" => self.part1,
+            "part2" => self.part2,
+            _ => None
+        }
+    }
+
+    fn matches(&self, other: &BenchResult) -> bool {
+        (self.year, self.day, &self.file) == (other.year, other.day, &other.file)
+    }
+}
+
+/// Times every day listed in `directory`'s manifest, starting each day's parameters from
+/// `profile`. Only parts with expected answers are run, since some example files only fit
+/// one part.
+pub fn bench_directory(directory: &Path, profile: Profile, options: &BenchOptions) -> Result<Vec<BenchResult>, BenchError> {
+    let manifest = regression::read_manifest(directory)?;
+    let mut results = vec![];
+
+    let entries = manifest.entries().iter()
+        .filter(|entry| options.year.is_none_or(|year| entry.year() == year))
+        .filter(|entry| options.day.is_none_or(|day| entry.day() == day));
+
+    for entry in entries {
+        let file = directory.join(entry.file());
+        let failed = |err: String| BenchError::PuzzleFailed(file.clone(), err);
+
+        // Older days register each part as its own puzzle
+        let puzzles: Vec<_> = registry::PUZZLES.iter()
+            .filter(|puzzle| puzzle.year() == entry.year() && puzzle.day() == entry.day())
+            .collect();
+
+        if puzzles.is_empty() {
+            return Err(failed("no solution registered".to_string()));
+        }
+
+        let parts = entry.parts();
+        let mut samples: [Vec<Duration>; 3] = Default::default();
+
+        for run in 0..options.warmup + options.repeats {
+            let mut timings = Timings::default();
+
+            for puzzle in puzzles.iter() {
+                let params = Params::new(puzzle.params(), profile, entry.overrides()).map_err(|err| failed(err.to_string()))?;
+                let puzzle_timings = puzzle.time(&file, &params, &parts).map_err(|err| failed(err.to_string()))?;
+
+                timings.parse = timings.parse.or(puzzle_timings.parse);
+                timings.part1 = timings.part1.or(puzzle_timings.part1);
+                timings.part2 = timings.part2.or(puzzle_timings.part2);
+            }
+
+            if run < options.warmup {
+                continue;
+            }
+
+            for (samples, timing) in samples.iter_mut().zip([timings.parse, timings.part1, timings.part2]) {
+                samples.extend(timing);
+            }
+        }
+
+        let [parse, part1, part2] = samples.map(|samples| StageStats::from_samples(&samples));
+
+        results.push(BenchResult {
+            year: entry.year(),
+            day: entry.day(),
+            file: entry.file().to_string(),
+            parse,
+            part1,
+            part2
+        });
+    }
+
+    Ok(results)
+}
+
+/// The change in one stage's median since a baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change {
+    pub baseline_ns: u64,
+    pub current_ns: u64
+}
+
+impl Change {
+    pub fn percent(&self) -> f64 {
+        (self.current_ns as f64 - self.baseline_ns as f64) / self.baseline_ns.max(1) as f64 * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+pub fn read_baseline(path: &Path) -> Result<Vec<BenchResult>, BenchError> {
+    let contents = fs::read_to_string(path).map_err(|err| BenchError::CouldntReadBaseline(path.to_path_buf(), err))?;
+
+    serde_json::from_str(&contents).map_err(|err| BenchError::InvalidBaseline(path.to_path_buf(), err))
+}
+
+pub fn to_json(results: &[BenchResult]) -> String {
+    serde_json::to_string_pretty(results).expect("bench results are always serializable")
+}
+
+/// The change in `stage` since the matching result in `baseline`, if both were timed.
+fn change_for(result: &BenchResult, stage: &str, baseline: &[BenchResult]) -> Option<Change> {
+    let current = result.stage(stage)?;
+    let previous = baseline.iter().find(|previous| previous.matches(result))?.stage(stage)?;
+
+    Some(Change { baseline_ns: previous.median_ns, current_ns: current.median_ns })
+}
+
+/// Every stage whose median grew by more than `threshold` percent since `baseline`.
+pub fn regressions(results: &[BenchResult], baseline: &[BenchResult], threshold: f64) -> Vec<String> {
+    let mut regressions = vec![];
+
+    for result in results.iter() {
+        for stage in STAGES {
+            if let Some(change) = change_for(result, stage, baseline).filter(|change| change.is_regression(threshold)) {
+                regressions.push(format!("{} day {} {} ({}): {} -> {} ({:+.1}%)",
+                    result.year, result.day, stage, result.file,
+                    format_duration(change.baseline_ns), format_duration(change.current_ns), change.percent()));
+            }
+        }
+    }
+
+    regressions
+}
+
+/// Medians as a table, with the change from `baseline` beside each one. Changes over
+/// `threshold` percent are marked with `!`.
+pub fn render_table(results: &[BenchResult], baseline: Option<&[BenchResult]>, threshold: f64) -> String {
+    let file_width = results.iter().map(|result| result.file.len()).max().unwrap_or(0).max(4);
+    let column_width = if baseline.is_some() { 22 } else { 10 };
+
+    let mut output = format!("{:<4} {:>3}  {:<file_width$}", "year", "day", "file");
+    for stage in STAGES {
+        write!(output, "  {:>column_width$}", stage).unwrap();
+    }
+
+    for result in results.iter() {
+        write!(output, "\n{:<4} {:>3}  {:<file_width$}", result.year, result.day, result.file).unwrap();
+
+        for stage in STAGES {
+            let mut cell = match result.stage(stage) {
+                Some(stats) => format_duration(stats.median_ns),
+                None => "-".to_string()
+            };
+
+            if let Some(change) = baseline.and_then(|baseline| change_for(result, stage, baseline)) {
+                let marker = if change.is_regression(threshold) { "!" } else { " " };
+                write!(cell, " ({:+.1}%){}", change.percent(), marker).unwrap();
+            }
+
+            write!(output, "  {:>column_width$}", cell).unwrap();
+        }
+    }
+
+    output
+}
+
+fn format_duration(nanos: u64) -> String {
+    match nanos {
+        0..1_000 => format!("{}ns", nanos),
+        1_000..1_000_000 => format!("{:.1}µs", nanos as f64 / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BenchError {
+    #[error("{0}")]
+    CouldntReadManifest(#[from] CheckError),
+    #[error("{0}: {1}")]
+    PuzzleFailed(PathBuf, String),
+    #[error("couldn't read baseline {0}: {1}")]
+    CouldntReadBaseline(PathBuf, io::Error),
+    #[error("invalid baseline {0}: {1}")]
+    InvalidBaseline(PathBuf, serde_json::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(file: &str, part1_ns: u64) -> BenchResult {
+        let stats = StageStats { min_ns: part1_ns, median_ns: part1_ns, mean_ns: part1_ns };
+
+        BenchResult { year: 2024, day: 1, file: file.to_string(), parse: None, part1: Some(stats), part2: None }
+    }
+
+    #[test]
+    pub fn stage_stats() {
+        let samples: Vec<Duration> = [5, 1, 3, 7].into_iter().map(Duration::from_nanos).collect();
+
+        assert_eq!(StageStats::from_samples(&samples), Some(StageStats { min_ns: 1, median_ns: 4, mean_ns: 4 }));
+        assert_eq!(StageStats::from_samples(&samples[..3]).unwrap().median_ns, 3);
+        assert_eq!(StageStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    pub fn baseline_comparison() {
+        let baseline = vec![result("2024-01.txt", 1_000), result("2024-01-big.txt", 2_000)];
+        let current = vec![result("2024-01.txt", 1_500), result("2024-01-big.txt", 2_000), result("2024-01-new.txt", 10)];
+
+        let baseline: Vec<BenchResult> = serde_json::from_str(&to_json(&baseline)).unwrap();
+        assert_eq!(change_for(&current[0], "part1", &baseline).unwrap().percent(), 50.0);
+        assert_eq!(change_for(&current[2], "part1", &baseline), None);
+        assert_eq!(change_for(&current[0], "parse", &baseline), None);
+
+        assert_eq!(regressions(&current, &baseline, 10.0), vec!["2024 day 1 part1 (2024-01.txt): 1.0µs -> 1.5µs (+50.0%)"]);
+        assert!(regressions(&current, &baseline, 60.0).is_empty());
+
+        let table = render_table(&current, Some(&baseline), 10.0);
+        assert!(table.contains("1.5µs (+50.0%)!"));
+        assert!(table.contains("2.0µs (+0.0%) "));
+    }
+}
@@ -0,0 +1,280 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use thiserror::Error;
+
+use aoc_common::params::{ParamError, ParamOptions, Profile};
+use aoc_common::{Part, Puzzle};
+
+pub mod bench;
+pub mod registry;
+pub mod regression;
+
+use bench::{BenchError, BenchOptions};
+use regression::CheckError;
+
+#[derive(Parser)]
+pub struct CliOptions {
+    #[command(subcommand)]
+    command: Commands
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Run {
+        year: u16,
+        day: u8,
+        part: Part,
+        input: PathBuf,
+
+        #[command(flatten)]
+        params: ParamOptions
+    },
+    List,
+    /// Check every answer listed in the examples manifest, and the real inputs manifest if
+    /// there is one
+    Check {
+        #[arg(long, default_value = "examples")]
+        examples: PathBuf,
+
+        #[arg(long, default_value = "inputs")]
+        inputs: PathBuf
+    },
+    /// Time parsing and each part separately for every input in a manifest
+    Bench(BenchArgs),
+    /// List a day's parameters with their defaults
+    Params {
+        year: u16,
+        day: u8
+    }
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Directory with inputs and a manifest; defaults to inputs, or examples if there's no
+    /// inputs manifest
+    #[arg(long)]
+    directory: Option<PathBuf>,
+
+    /// Use the parameters from the puzzles' worked examples
+    #[arg(long)]
+    examples: bool,
+
+    #[arg(long)]
+    year: Option<u16>,
+
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Untimed runs before timing each input
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
+
+    /// Timed runs of each input
+    #[arg(long, default_value_t = 10)]
+    repeats: usize,
+
+    #[arg(long, value_enum, default_value_t = BenchFormat::Table)]
+    format: BenchFormat,
+
+    /// Write the report to a file instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// Compare against results saved with --save-baseline
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+
+    /// Percent slowdown in a median that counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "kebab_case")]
+enum BenchFormat {
+    Table,
+    Json
+}
+
+pub fn run(options: CliOptions) -> Result<String, ApplicationError> {
+    let result = match options.command {
+        Commands::Run { year, day, part, input, params } => run_puzzle(year, day, part, input, params),
+        Commands::List => Ok(list_puzzles()),
+        Commands::Check { examples, inputs } => check(&examples, &inputs),
+        Commands::Bench(args) => bench(args),
+        Commands::Params { year, day } => list_params(year, day)
+    }?;
+
+    Ok(result)
+}
+
+fn run_puzzle(year: u16, day: u8, part: Part, input: PathBuf, params: ParamOptions) -> Result<String, ApplicationError> {
+    let puzzle = find_puzzle(year, day, part)?;
+    let params = params.resolve(puzzle.params())?;
+
+    puzzle.run_with(part, &input, &params).map_err(|err| ApplicationError::PuzzleFailed(err.to_string()))
+}
+
+fn check(examples: &Path, inputs: &Path) -> Result<String, ApplicationError> {
+    let mut results = regression::check_directory(examples, Profile::Example)?;
+
+    if inputs.join(regression::MANIFEST_NAME).exists() {
+        results.extend(regression::check_directory(inputs, Profile::Full)?);
+    }
+
+    let failed = results.iter().filter(|result| !result.passed()).count();
+
+    let mut lines: Vec<String> = results.iter().map(|result| result.to_string()).collect();
+    lines.push(format!("{} passed, {} failed", results.len() - failed, failed));
+
+    if failed > 0 {
+        Err(ApplicationError::Regressions(lines.join("\n")))
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+fn bench(args: BenchArgs) -> Result<String, ApplicationError> {
+    let inputs = Path::new("inputs");
+    let use_examples = args.examples || (args.directory.is_none() && !inputs.join(regression::MANIFEST_NAME).exists());
+
+    let profile = if use_examples { Profile::Example } else { Profile::Full };
+    let directory = match args.directory {
+        Some(directory) => directory,
+        None if use_examples => PathBuf::from("examples"),
+        None => inputs.to_path_buf()
+    };
+
+    let options = BenchOptions { warmup: args.warmup, repeats: args.repeats.max(1), year: args.year, day: args.day };
+    let results = bench::bench_directory(&directory, profile, &options)?;
+
+    let baseline = args.baseline.as_deref().map(bench::read_baseline).transpose()?;
+
+    if let Some(path) = &args.save_baseline {
+        fs::write(path, bench::to_json(&results)).map_err(|err| ApplicationError::CouldntWrite(path.clone(), err))?;
+    }
+
+    let report = match args.format {
+        BenchFormat::Table => bench::render_table(&results, baseline.as_deref(), args.threshold),
+        BenchFormat::Json => bench::to_json(&results)
+    };
+
+    let report = match &args.output {
+        Some(path) => {
+            fs::write(path, &report).map_err(|err| ApplicationError::CouldntWrite(path.clone(), err))?;
+            format!("timed {} inputs, report written to {}", results.len(), path.display())
+        },
+        None => report
+    };
+
+    let regressions = baseline
+        .map(|baseline| bench::regressions(&results, &baseline, args.threshold))
+        .unwrap_or_default();
+
+    if regressions.is_empty() {
+        Ok(report)
+    } else {
+        Err(ApplicationError::Regressions(format!("{}\n\n{}", report, regressions.join("\n"))))
+    }
+}
+
+fn list_params(year: u16, day: u8) -> Result<String, ApplicationError> {
+    let puzzles: Vec<_> = registry::PUZZLES.iter()
+        .filter(|puzzle| puzzle.year() == year && puzzle.day() == day)
+        .collect();
+
+    if puzzles.is_empty() {
+        return Err(ApplicationError::UnknownDay(year, day));
+    }
+
+    let lines: Vec<String> = puzzles.iter()
+        .flat_map(|puzzle| puzzle.params())
+        .map(|param| format!("{} = {} (example: {}) - {}", param.name, param.default, param.example, param.description))
+        .collect();
+
+    if lines.is_empty() {
+        Ok("no parameters".to_string())
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+fn list_puzzles() -> String {
+    let mut lines = vec![];
+    for puzzle in registry::PUZZLES {
+        let parts = puzzle.parts().iter().map(|part| part.to_string()).collect::<Vec<String>>();
+        lines.push(format!("{} {:>2} {}", puzzle.year(), puzzle.day(), parts.join(",")));
+    }
+
+    lines.join("\n")
+}
+
+pub fn find_puzzle(year: u16, day: u8, part: Part) -> Result<&'static dyn Puzzle, ApplicationError> {
+    let mut found_day = false;
+
+    for &puzzle in registry::PUZZLES {
+        if puzzle.year() == year && puzzle.day() == day {
+            found_day = true;
+
+            if puzzle.parts().contains(&part) {
+                return Ok(puzzle);
+            }
+        }
+    }
+
+    if found_day {
+        Err(ApplicationError::UnknownPart(year, day, part))
+    } else {
+        Err(ApplicationError::UnknownDay(year, day))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ApplicationError {
+    #[error("no solution registered for {0} day {1}")]
+    UnknownDay(u16, u8),
+    #[error("no solution registered for {0} day {1} {2}")]
+    UnknownPart(u16, u8, Part),
+    #[error("{0}")]
+    PuzzleFailed(String),
+    #[error("{0}")]
+    InvalidParams(#[from] ParamError),
+    #[error("{0}")]
+    CouldntCheck(#[from] CheckError),
+    #[error("{0}")]
+    CouldntBench(#[from] BenchError),
+    #[error("couldn't write {0}: {1}")]
+    CouldntWrite(PathBuf, io::Error),
+    #[error("regressions found:\n{0}")]
+    Regressions(String)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_registered_puzzles() {
+        assert_eq!(find_puzzle(2024, 17, Part::Two).unwrap().day(), 17);
+        assert_eq!(find_puzzle(2021, 1, Part::Two).unwrap().parts(), Part::TWO);
+        assert!(matches!(find_puzzle(2021, 16, Part::One), Err(ApplicationError::UnknownPart(2021, 16, Part::One))));
+        assert!(matches!(find_puzzle(2016, 1, Part::One), Err(ApplicationError::UnknownDay(2016, 1))));
+    }
+
+    #[test]
+    fn registry_has_no_duplicates() {
+        for (i, a) in registry::PUZZLES.iter().enumerate() {
+            for b in registry::PUZZLES.iter().skip(i + 1) {
+                let overlapping = a.parts().iter().any(|part| b.parts().contains(part));
+                assert!(!(a.year() == b.year() && a.day() == b.day() && overlapping),
+                    "{} day {} registered twice", a.year(), a.day());
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+use aoc_common::Puzzle;
+
+/// Every day this crate depends on, in the order they're listed in `Cargo.toml`. Generated
+/// by `build.rs`.
+pub static PUZZLES: &[&dyn Puzzle] = include!(concat!(env!("OUT_DIR"), "/puzzles.rs"));
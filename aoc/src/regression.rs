@@ -0,0 +1,294 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use aoc_common::Part;
+use aoc_common::params::{ParamError, ParamOverride, Params, Profile};
+
+use crate::find_puzzle;
+
+/// The file listing expected answers, in the same directory as the inputs it describes.
+pub const MANIFEST_NAME: &str = "answers.txt";
+
+/// Expected answers for a directory of inputs, one line per file:
+/// `2024-14.txt part1=12 width=11`. Anything other than `part1`/`part2` overrides a
+/// parameter. Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Manifest {
+    entries: Vec<ManifestEntry>
+}
+
+impl Manifest {
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = ParseManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(i, line)| line.parse().map_err(|err| ParseManifestError::InvalidLine(i + 1, Box::new(err))))
+            .collect::<Result<Vec<ManifestEntry>, _>>()?;
+
+        Ok(Manifest { entries })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ManifestEntry {
+    file: String,
+    year: u16,
+    day: u8,
+    answers: Vec<(Part, String)>,
+    overrides: Vec<ParamOverride>
+}
+
+impl ManifestEntry {
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The parts with expected answers, which are the only ones known to work on this file.
+    pub fn parts(&self) -> Vec<Part> {
+        self.answers.iter().map(|(part, _)| *part).collect()
+    }
+
+    pub fn overrides(&self) -> &[ParamOverride] {
+        &self.overrides
+    }
+}
+
+impl FromStr for ManifestEntry {
+    type Err = ParseManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let file = words.next().ok_or(ParseManifestError::MissingFile)?.to_string();
+
+        // Files are named <year>-<day>[-<label>].txt
+        let mut name = file.trim_end_matches(".txt").split('-');
+        let invalid_name = || ParseManifestError::InvalidFileName(file.clone());
+        let year = name.next().and_then(|year| year.parse().ok()).ok_or_else(invalid_name)?;
+        let day = name.next().and_then(|day| day.parse().ok()).ok_or_else(invalid_name)?;
+
+        let mut answers = vec![];
+        let mut overrides = vec![];
+
+        for word in words {
+            match word.split_once('=') {
+                Some(("part1", answer)) => answers.push((Part::One, answer.to_string())),
+                Some(("part2", answer)) => answers.push((Part::Two, answer.to_string())),
+                _ => overrides.push(word.parse()?)
+            }
+        }
+
+        Ok(ManifestEntry { file, year, day, answers, overrides })
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ParseManifestError {
+    #[error("line {0}: {1}")]
+    InvalidLine(usize, Box<ParseManifestError>),
+    #[error("missing file name")]
+    MissingFile,
+    #[error("file name '{0}' doesn't start with <year>-<day>")]
+    InvalidFileName(String),
+    #[error("{0}")]
+    InvalidOverride(#[from] ParamError)
+}
+
+/// The outcome of one part of one manifest entry.
+#[derive(Debug)]
+pub struct CheckResult {
+    year: u16,
+    day: u8,
+    part: Part,
+    file: PathBuf,
+    expected: String,
+    actual: Result<String, String>
+}
+
+impl CheckResult {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref().is_ok_and(|actual| *actual == self.expected)
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {} {} ({}): ", self.year, self.day, self.part, self.file.display())?;
+
+        match &self.actual {
+            _ if self.passed() => write!(f, "ok"),
+            Ok(actual) => write!(f, "expected {}, got {}", self.expected, actual),
+            Err(err) => write!(f, "expected {}, failed: {}", self.expected, err)
+        }
+    }
+}
+
+/// Runs every answer listed in `directory`'s manifest, starting each day's parameters from
+/// `profile`.
+pub fn check_directory(directory: &Path, profile: Profile) -> Result<Vec<CheckResult>, CheckError> {
+    let manifest = read_manifest(directory)?;
+    let mut results = vec![];
+
+    for entry in manifest.entries() {
+        let file = directory.join(&entry.file);
+
+        for (part, expected) in entry.answers.iter() {
+            let actual = find_puzzle(entry.year, entry.day, *part)
+                .map_err(|err| err.to_string())
+                .and_then(|puzzle| {
+                    let params = Params::new(puzzle.params(), profile, &entry.overrides).map_err(|err| err.to_string())?;
+
+                    puzzle.run_with(*part, &file, &params).map_err(|err| err.to_string())
+                })
+                .map(|answer| answer.trim().to_string());
+
+            results.push(CheckResult {
+                year: entry.year,
+                day: entry.day,
+                part: *part,
+                file: file.clone(),
+                expected: expected.clone(),
+                actual
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Reads `directory`'s manifest, failing if any `.txt` file beside it isn't listed, so an
+/// input can't be added and then silently never run.
+pub fn read_manifest(directory: &Path) -> Result<Manifest, CheckError> {
+    let manifest_path = directory.join(MANIFEST_NAME);
+
+    let manifest: Manifest = fs::read_to_string(&manifest_path)
+        .map_err(|err| CheckError::CouldntReadManifest(manifest_path.clone(), err))?
+        .parse()
+        .map_err(|err| CheckError::InvalidManifest(manifest_path.clone(), err))?;
+
+    let unlisted = unlisted_files(directory, &manifest)
+        .map_err(|err| CheckError::CouldntReadDirectory(directory.to_path_buf(), err))?;
+
+    if !unlisted.is_empty() {
+        return Err(CheckError::UnlistedFiles(manifest_path, unlisted.join(", ")));
+    }
+
+    Ok(manifest)
+}
+
+fn unlisted_files(directory: &Path, manifest: &Manifest) -> io::Result<Vec<String>> {
+    let mut unlisted = vec![];
+
+    for entry in fs::read_dir(directory)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+
+        if name.ends_with(".txt") && name != MANIFEST_NAME && !manifest.entries.iter().any(|entry| entry.file == name) {
+            unlisted.push(name);
+        }
+    }
+
+    unlisted.sort();
+
+    Ok(unlisted)
+}
+
+#[derive(Debug, Error)]
+pub enum CheckError {
+    #[error("couldn't read {0}: {1}")]
+    CouldntReadManifest(PathBuf, io::Error),
+    #[error("invalid manifest {0}: {1}")]
+    InvalidManifest(PathBuf, ParseManifestError),
+    #[error("couldn't list {0}: {1}")]
+    CouldntReadDirectory(PathBuf, io::Error),
+    #[error("files missing from {0}: {1}")]
+    UnlistedFiles(PathBuf, String)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace_directory(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(name)
+    }
+
+    fn assert_all_pass(results: &[CheckResult]) {
+        let failures: Vec<String> = results.iter()
+            .filter(|result| !result.passed())
+            .map(|result| result.to_string())
+            .collect();
+
+        assert!(failures.is_empty(), "regressions:\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn parse_manifest() {
+        let manifest: Manifest = "# comment\n\n2024-14-small.txt part1=12 width=11\n".parse().unwrap();
+        let entry = &manifest.entries()[0];
+
+        assert_eq!((entry.year, entry.day), (2024, 14));
+        assert_eq!(entry.answers, vec![(Part::One, "12".to_string())]);
+        assert_eq!(entry.overrides, vec!["width=11".parse().unwrap()]);
+
+        assert!(matches!(
+            "examples.txt part1=1".parse::<Manifest>(),
+            Err(ParseManifestError::InvalidLine(1, _))
+        ));
+    }
+
+    #[test]
+    fn unlisted_files_fail() {
+        let directory = std::env::temp_dir().join(format!("aoc-unlisted-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join(MANIFEST_NAME), "2024-01.txt part1=11\n").unwrap();
+        fs::write(directory.join("2024-01.txt"), "").unwrap();
+
+        assert!(read_manifest(&directory).is_ok());
+
+        fs::write(directory.join("2024-02.txt"), "").unwrap();
+        fs::write(directory.join("notes.md"), "").unwrap();
+        let result = read_manifest(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(matches!(result, Err(CheckError::UnlistedFiles(_, files)) if files == "2024-02.txt"));
+    }
+
+    #[test]
+    fn examples() {
+        let results = check_directory(&workspace_directory("examples"), Profile::Example).unwrap();
+
+        assert!(!results.is_empty());
+        assert_all_pass(&results);
+    }
+
+    /// Real inputs aren't checked in; put them and a manifest in `inputs/` to run this.
+    #[test]
+    fn real_inputs() {
+        let directory = workspace_directory("inputs");
+        if !directory.join(MANIFEST_NAME).exists() {
+            return;
+        }
+
+        assert_all_pass(&check_directory(&directory, Profile::Full).unwrap());
+    }
+}
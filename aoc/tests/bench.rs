@@ -0,0 +1,28 @@
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+
+/// Days that print while solving would break the report, so this runs the real binary and
+/// parses everything it writes to stdout.
+#[test]
+pub fn json_report_on_stdout() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .current_dir(workspace)
+        .args(["bench", "--examples", "--format", "json", "--warmup", "0", "--repeats", "1"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = report.as_array().unwrap();
+    let manifest = std::fs::read_to_string(workspace.join("examples/answers.txt")).unwrap();
+
+    let entries = manifest.lines().filter(|line| !line.is_empty() && !line.starts_with('#')).count();
+
+    assert_eq!(results.len(), entries);
+    assert!(results.iter().all(|result| result["part1"].is_object() || result["part2"].is_object()));
+}
@@ -0,0 +1,274 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use thiserror::Error;
+
+use params::{Param, ParamError, ParamOptions, Params};
+
+pub mod params;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const BOTH: &'static [Part] = &[Part::One, Part::Two];
+    pub const ONE: &'static [Part] = &[Part::One];
+    pub const TWO: &'static [Part] = &[Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = ParsePartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "part1" => Ok(Part::One),
+            "2" | "part2" => Ok(Part::Two),
+            _ => Err(ParsePartError::UnknownPart(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "part1"),
+            Part::Two => write!(f, "part2")
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParsePartError {
+    #[error("unknown part: {0}")]
+    UnknownPart(String)
+}
+
+/// A single day's puzzle as seen by the `aoc` runner. Each day crate exposes a
+/// unit struct implementing this trait, and the runner's registry lists them.
+pub trait Puzzle: Sync {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    /// The parts this crate answers. Most crates answer both, but 2021 split
+    /// each part into its own crate.
+    fn parts(&self) -> &'static [Part] {
+        Part::BOTH
+    }
+
+    fn run(&self, part: Part, input: &Path) -> Result<String, Box<dyn Error>>;
+
+    /// Parameters the answers depend on besides the input.
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    fn run_with(&self, part: Part, input: &Path, _params: &Params) -> Result<String, Box<dyn Error>> {
+        self.run(part, input)
+    }
+
+    /// Runs each of `parts` this puzzle has once, timing parsing and solving separately where
+    /// the puzzle allows it. This default can only time whole runs, so reading and parsing
+    /// the input are counted in each part.
+    fn time(&self, input: &Path, params: &Params, parts: &[Part]) -> Result<Timings, Box<dyn Error>> {
+        let mut timings = Timings::default();
+
+        for &part in self.parts().iter().filter(|part| parts.contains(part)) {
+            let start = Instant::now();
+            self.run_with(part, input, params)?;
+            timings.set_part(part, start.elapsed());
+        }
+
+        Ok(timings)
+    }
+}
+
+/// How long each stage of a run took. Stages that weren't run are `None`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Timings {
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>
+}
+
+impl Timings {
+    pub fn part(&self, part: Part) -> Option<Duration> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2
+        }
+    }
+
+    fn set_part(&mut self, part: Part, duration: Duration) {
+        match part {
+            Part::One => self.part1 = Some(duration),
+            Part::Two => self.part2 = Some(duration)
+        }
+    }
+}
+
+/// A day written as a parser plus one function per part. Implementing this is
+/// enough to be run by `aoc`, by the day's own binary through [`run`], and by
+/// anything else that wants typed answers.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    type Input: FromStr<Err: Error>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, SolutionError>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, SolutionError>;
+
+    /// Parameters the answers depend on besides the input, like the size of a grid. Days
+    /// with parameters override [`Solution::part1_with`] and [`Solution::part2_with`], and
+    /// have `part1` and `part2` call them with [`Params::defaults`].
+    const PARAMS: &'static [Param] = &[];
+
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Answer1, SolutionError> {
+        Self::part1(input)
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Self::Answer2, SolutionError> {
+        Self::part2(input)
+    }
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, part: Part, input: &Path) -> Result<String, Box<dyn Error>> {
+        let input = fs::read_to_string(input)?;
+
+        Ok(solve::<S>(part, &input)?)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn run_with(&self, part: Part, input: &Path, params: &Params) -> Result<String, Box<dyn Error>> {
+        let input = fs::read_to_string(input)?;
+
+        Ok(solve_with::<S>(part, &input, params)?)
+    }
+
+    fn time(&self, input: &Path, params: &Params, parts: &[Part]) -> Result<Timings, Box<dyn Error>> {
+        let input = fs::read_to_string(input)?;
+        let mut timings = Timings::default();
+
+        let start = Instant::now();
+        let input = input.parse::<S::Input>()
+            .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
+        timings.parse = Some(start.elapsed());
+
+        for &part in parts {
+            let start = Instant::now();
+            let result = match part {
+                Part::One => S::part1_with(&input, params).map(|_| ()),
+                Part::Two => S::part2_with(&input, params).map(|_| ())
+            };
+
+            // Parts a day doesn't have are left out rather than failing the whole run
+            match result {
+                Ok(_) => timings.set_part(part, start.elapsed()),
+                Err(SolutionError::NoSuchPart(_)) => { },
+                Err(err) => return Err(err.into())
+            }
+        }
+
+        Ok(timings)
+    }
+}
+
+pub fn solve<S: Solution>(part: Part, input: &str) -> Result<String, ApplicationError> {
+    solve_with::<S>(part, input, &Params::defaults(S::PARAMS))
+}
+
+pub fn solve_with<S: Solution>(part: Part, input: &str, params: &Params) -> Result<String, ApplicationError> {
+    let input = input.parse::<S::Input>()
+        .map_err(|err| ApplicationError::CouldntParseInput(err.to_string()))?;
+
+    let answer = match part {
+        Part::One => S::part1_with(&input, params)?.to_string(),
+        Part::Two => S::part2_with(&input, params)?.to_string()
+    };
+
+    Ok(answer)
+}
+
+#[derive(Parser)]
+pub struct CliOptions {
+    part: Part,
+    input: PathBuf,
+
+    #[command(flatten)]
+    params: ParamOptions
+}
+
+pub fn run<S: Solution>(options: CliOptions) -> Result<String, ApplicationError> {
+    let input = fs::read_to_string(options.input)?;
+    let params = options.params.resolve(S::PARAMS)?;
+
+    solve_with::<S>(options.part, &input, &params)
+}
+
+#[derive(Debug, Error)]
+pub enum SolutionError {
+    #[error("couldn't find solution")]
+    NoSolution,
+    #[error("couldn't find solution: {0}")]
+    Unsolvable(String),
+    #[error("this puzzle has no {0}")]
+    NoSuchPart(Part),
+    #[error("{0}")]
+    InvalidParams(#[from] ParamError)
+}
+
+#[derive(Debug, Error)]
+pub enum ApplicationError {
+    #[error("couldn't read puzzle input: {0}")]
+    CouldntReadInput(#[from] io::Error),
+    #[error("couldn't parse puzzle input: {0}")]
+    CouldntParseInput(String),
+    #[error("{0}")]
+    CouldntSolve(#[from] SolutionError),
+    #[error("{0}")]
+    InvalidParams(#[from] ParamError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_part() {
+        assert_eq!("part1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("part3".parse::<Part>().is_err());
+    }
+}
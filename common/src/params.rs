@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use clap::Args;
+use thiserror::Error;
+
+/// A puzzle parameter that isn't part of the input, such as the size of a grid. `default`
+/// is the value for real inputs and `example` the one the puzzle text uses for its example.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: &'static str,
+    pub example: &'static str
+}
+
+/// Which set of defaults to start from.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Profile {
+    #[default]
+    Full,
+    Example
+}
+
+/// Values for a day's parameters, looked up by name.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Params {
+    values: BTreeMap<&'static str, String>
+}
+
+impl Params {
+    /// The values for real inputs.
+    pub fn defaults(params: &'static [Param]) -> Params {
+        Params::profile(params, Profile::Full)
+    }
+
+    pub fn profile(params: &'static [Param], profile: Profile) -> Params {
+        let values = params.iter()
+            .map(|param| {
+                let value = match profile {
+                    Profile::Full => param.default,
+                    Profile::Example => param.example
+                };
+
+                (param.name, value.to_string())
+            })
+            .collect();
+
+        Params { values }
+    }
+
+    /// Starts from `profile` and applies `overrides` in order. Overriding a name the day
+    /// doesn't have is an error, so typos don't go unnoticed.
+    pub fn new(params: &'static [Param], profile: Profile, overrides: &[ParamOverride]) -> Result<Params, ParamError> {
+        let mut result = Params::profile(params, profile);
+
+        for ParamOverride { name, value } in overrides {
+            let param = params.iter()
+                .find(|param| param.name == name)
+                .ok_or_else(|| ParamError::UnknownParam(name.clone()))?;
+
+            result.values.insert(param.name, value.clone());
+        }
+
+        Ok(result)
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, ParamError> {
+        let value = self.values.get(name).ok_or_else(|| ParamError::UnknownParam(name.to_string()))?;
+
+        value.parse().map_err(|_| ParamError::InvalidValue(name.to_string(), value.clone()))
+    }
+}
+
+/// A `key=value` pair from the command line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParamOverride {
+    name: String,
+    value: String
+}
+
+impl FromStr for ParamOverride {
+    type Err = ParamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s.split_once('=').ok_or_else(|| ParamError::MissingValue(s.to_string()))?;
+
+        Ok(ParamOverride { name: name.trim().to_string(), value: value.trim().to_string() })
+    }
+}
+
+/// Flags for picking parameters, for flattening into a day's own options.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ParamOptions {
+    /// Use the parameters from the puzzle's worked example
+    #[arg(long)]
+    example: bool,
+
+    /// Override a parameter
+    #[arg(long = "param", value_name = "KEY=VALUE")]
+    overrides: Vec<ParamOverride>
+}
+
+impl ParamOptions {
+    pub fn resolve(&self, params: &'static [Param]) -> Result<Params, ParamError> {
+        let profile = if self.example { Profile::Example } else { Profile::Full };
+
+        Params::new(params, profile, &self.overrides)
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ParamError {
+    #[error("unknown parameter '{0}'")]
+    UnknownParam(String),
+    #[error("expected key=value, got '{0}'")]
+    MissingValue(String),
+    #[error("invalid value '{1}' for parameter '{0}'")]
+    InvalidValue(String, String),
+    #[error("parameters don't fit the input: {0}")]
+    DoesntFitInput(String)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param { name: "width", description: "arena width", default: "101", example: "11" },
+        Param { name: "height", description: "arena height", default: "103", example: "7" }
+    ];
+
+    #[test]
+    pub fn profiles_and_overrides() {
+        let defaults = Params::defaults(PARAMS);
+        assert_eq!(defaults.get::<i64>("width"), Ok(101));
+
+        let overrides = vec!["height=9".parse().unwrap()];
+        let params = Params::new(PARAMS, Profile::Example, &overrides).unwrap();
+        assert_eq!(params.get::<i64>("width"), Ok(11));
+        assert_eq!(params.get::<i64>("height"), Ok(9));
+
+        let unknown = vec!["depth=3".parse().unwrap()];
+        assert_eq!(Params::new(PARAMS, Profile::Full, &unknown), Err(ParamError::UnknownParam("depth".to_string())));
+
+        let invalid = vec!["width=wide".parse().unwrap()];
+        let params = Params::new(PARAMS, Profile::Full, &invalid).unwrap();
+        assert_eq!(params.get::<i64>("width"), Err(ParamError::InvalidValue("width".to_string(), "wide".to_string())));
+
+        assert!("width".parse::<ParamOverride>().is_err());
+    }
+}
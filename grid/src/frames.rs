@@ -0,0 +1,267 @@
+use std::fmt::{Display, Write as _};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::{Grid, Point};
+
+/// One picture of a simulation. Every cell has a character for text output and a level for
+/// image output, where 0 is empty background and `max_level` is the most prominent.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Frame {
+    cells: Grid<(char, u8)>,
+    max_level: u8
+}
+
+impl Frame {
+    pub fn from_fn(width: usize, height: usize, max_level: u8, mut f: impl FnMut(Point) -> (char, u8)) -> Frame {
+        let cells = Grid::from_fn(width, height, |point| {
+            let (char, level) = f(point);
+            (char, level.min(max_level))
+        });
+
+        Frame { cells, max_level: max_level.max(1) }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, max_level: u8, mut f: impl FnMut(Point, &T) -> (char, u8)) -> Frame {
+        Frame::from_fn(grid.width(), grid.height(), max_level, |point| f(point, &grid[point]))
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Plain PBM: any cell above level 0 is black.
+    pub fn to_pbm(&self) -> String {
+        let mut output = format!("P1\n{} {}\n", self.width(), self.height());
+
+        for row in self.cells.rows() {
+            let row: Vec<&str> = row.iter().map(|(_, level)| if *level > 0 { "1" } else { "0" }).collect();
+            writeln!(output, "{}", row.join(" ")).unwrap();
+        }
+
+        output
+    }
+
+    /// Plain PGM on a white background, so higher levels come out darker.
+    pub fn to_pgm(&self) -> String {
+        let mut output = format!("P2\n{} {}\n{}\n", self.width(), self.height(), self.max_level);
+
+        for row in self.cells.rows() {
+            let row: Vec<String> = row.iter().map(|(_, level)| (self.max_level - level).to_string()).collect();
+            writeln!(output, "{}", row.join(" ")).unwrap();
+        }
+
+        output
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.cells.render(|_, (char, _)| *char))
+    }
+}
+
+/// Somewhere to send a sequence of frames. `time` is in seconds from the first frame.
+pub trait FrameSink {
+    fn write_frame(&mut self, frame: &Frame, time: f64) -> io::Result<()>;
+}
+
+/// Writes each frame as text followed by a blank line.
+pub struct TextSink<W: Write> {
+    writer: W
+}
+
+impl<W: Write> TextSink<W> {
+    pub fn new(writer: W) -> TextSink<W> {
+        TextSink { writer }
+    }
+}
+
+impl TextSink<io::Stdout> {
+    pub fn stdout() -> TextSink<io::Stdout> {
+        TextSink::new(io::stdout())
+    }
+}
+
+impl<W: Write> FrameSink for TextSink<W> {
+    fn write_frame(&mut self, frame: &Frame, _time: f64) -> io::Result<()> {
+        writeln!(self.writer, "{}\n", frame)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NetpbmFormat {
+    Pbm,
+    Pgm
+}
+
+/// Writes each frame to its own numbered file in a directory, which is created if needed.
+pub struct NetpbmSink {
+    directory: PathBuf,
+    format: NetpbmFormat,
+    count: usize
+}
+
+impl NetpbmSink {
+    pub fn new(directory: impl AsRef<Path>, format: NetpbmFormat) -> io::Result<NetpbmSink> {
+        fs::create_dir_all(&directory)?;
+
+        Ok(NetpbmSink { directory: directory.as_ref().to_path_buf(), format, count: 0 })
+    }
+}
+
+impl FrameSink for NetpbmSink {
+    fn write_frame(&mut self, frame: &Frame, _time: f64) -> io::Result<()> {
+        let (extension, contents) = match self.format {
+            NetpbmFormat::Pbm => ("pbm", frame.to_pbm()),
+            NetpbmFormat::Pgm => ("pgm", frame.to_pgm())
+        };
+
+        let path = self.directory.join(format!("frame_{:06}.{}", self.count, extension));
+        fs::write(path, contents)?;
+
+        self.count += 1;
+
+        Ok(())
+    }
+}
+
+/// Writes an asciicast v2 recording, which terminal players can pause and scrub through.
+/// The header is sized to the first frame.
+pub struct AsciicastSink<W: Write> {
+    writer: W,
+    started: bool
+}
+
+impl<W: Write> AsciicastSink<W> {
+    pub fn new(writer: W) -> AsciicastSink<W> {
+        AsciicastSink { writer, started: false }
+    }
+}
+
+impl AsciicastSink<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> io::Result<AsciicastSink<BufWriter<File>>> {
+        Ok(AsciicastSink::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> FrameSink for AsciicastSink<W> {
+    fn write_frame(&mut self, frame: &Frame, time: f64) -> io::Result<()> {
+        if !self.started {
+            writeln!(self.writer, "{{\"version\": 2, \"width\": {}, \"height\": {}}}", frame.width(), frame.height())?;
+            self.started = true;
+        }
+
+        // Home the cursor and clear the screen before drawing
+        let text = format!("\u{1b}[H\u{1b}[2J{}", frame.to_string().replace('\n', "\r\n"));
+        writeln!(self.writer, "[{:.3}, \"o\", \"{}\"]", time, escape_json(&text))?;
+
+        self.writer.flush()
+    }
+}
+
+fn escape_json(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+
+    for char in s.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            char if char.is_control() => write!(output, "\\u{:04x}", char as u32).unwrap(),
+            char => output.push(char)
+        }
+    }
+
+    output
+}
+
+/// The sinks that can be picked by name, e.g. from a command-line flag.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FrameFormat {
+    Text,
+    Pbm,
+    Pgm,
+    Asciicast
+}
+
+impl FrameFormat {
+    /// Text and asciicast formats write to the file at `path`; Netpbm formats treat it as
+    /// a directory.
+    pub fn create_sink(self, path: impl AsRef<Path>) -> io::Result<Box<dyn FrameSink>> {
+        let sink: Box<dyn FrameSink> = match self {
+            FrameFormat::Text => Box::new(TextSink::new(BufWriter::new(File::create(path)?))),
+            FrameFormat::Pbm => Box::new(NetpbmSink::new(path, NetpbmFormat::Pbm)?),
+            FrameFormat::Pgm => Box::new(NetpbmSink::new(path, NetpbmFormat::Pgm)?),
+            FrameFormat::Asciicast => Box::new(AsciicastSink::create(path)?)
+        };
+
+        Ok(sink)
+    }
+}
+
+impl FromStr for FrameFormat {
+    type Err = ParseFrameFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(FrameFormat::Text),
+            "pbm" => Ok(FrameFormat::Pbm),
+            "pgm" => Ok(FrameFormat::Pgm),
+            "cast" | "asciicast" => Ok(FrameFormat::Asciicast),
+            _ => Err(ParseFrameFormatError::UnknownFormat(s.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ParseFrameFormatError {
+    #[error("unknown frame format '{0}', expected text, pbm, pgm or cast")]
+    UnknownFormat(String)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Frame {
+        Frame::from_fn(3, 2, 2, |point| match (point.x, point.y) {
+            (0, 0) => ('#', 2),
+            (2, 1) => ('o', 1),
+            _ => ('.', 0)
+        })
+    }
+
+    #[test]
+    pub fn netpbm() {
+        let frame = example();
+
+        assert_eq!(frame.to_string(), "#..\n..o");
+        assert_eq!(frame.to_pbm(), "P1\n3 2\n1 0 0\n0 0 1\n");
+        assert_eq!(frame.to_pgm(), "P2\n3 2\n2\n0 2 2\n2 2 1\n");
+    }
+
+    #[test]
+    pub fn asciicast() {
+        let mut output = vec![];
+        let mut sink = AsciicastSink::new(&mut output);
+        sink.write_frame(&example(), 0.0).unwrap();
+        sink.write_frame(&example(), 0.5).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "{\"version\": 2, \"width\": 3, \"height\": 2}");
+        assert_eq!(lines[2], "[0.500, \"o\", \"\\u001b[H\\u001b[2J#..\\r\\n..o\"]");
+    }
+}
@@ -0,0 +1,348 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+pub use point::{Direction, Point};
+
+pub mod frames;
+pub mod point;
+
+/// A rectangular grid of cells stored in row-major order, addressed by [`Point`]
+/// with `(0, 0)` at the top-left corner.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "cell count doesn't match grid dimensions");
+
+        Grid { width, height, cells }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| point_at(x, y)))
+            .map(&mut f)
+            .collect();
+
+        Grid { width, height, cells }
+    }
+
+    /// Parses a character map, converting each character with `f`. Empty lines are skipped.
+    pub fn from_chars(s: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseGridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for line in s.lines().filter(|line| !line.is_empty()) {
+            let row_width = line.chars().count();
+            match width {
+                None => { width = Some(row_width); }
+                Some(width) if width != row_width => {
+                    return Err(ParseGridError::InconsistentWidth(height));
+                }
+                _ => { }
+            }
+
+            for char in line.chars() {
+                let cell = f(char).ok_or(ParseGridError::UnexpectedCharacter(char))?;
+                cells.push(cell);
+            }
+
+            height += 1;
+        }
+
+        match width {
+            None => Err(ParseGridError::Empty),
+            Some(width) => Ok(Grid { width, height, cells })
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| point_at(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(point, _)| point)
+    }
+
+    /// In-bounds orthogonal neighbors of `point`, clockwise from north.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point.neighbors4().into_iter()
+            .filter_map(|neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    /// In-bounds orthogonal and diagonal neighbors of `point`, clockwise from north.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point.neighbors8().into_iter()
+            .filter_map(|neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    /// Walks from `start` (inclusive) in steps of `step` until leaving the grid.
+    pub fn ray(&self, start: Point, step: Point) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(Some(start), move |&point| Some(point + step))
+            .map_while(|point| self.get(point).map(|cell| (point, cell)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals running down and to the right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let left_edge = (0..self.height).rev().map(|y| point_at(0, y));
+        let top_edge = (1..self.width).map(|x| point_at(x, 0));
+
+        left_edge.chain(top_edge)
+            .map(|start| self.ray(start, Point::new(1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// Diagonals running down and to the left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let right = self.width.saturating_sub(1);
+        let top_edge = (0..self.width).map(|x| point_at(x, 0));
+        let right_edge = (1..self.height).map(move |y| point_at(right, y));
+
+        top_edge.chain(right_edge)
+            .map(|start| self.ray(start, Point::new(-1, 1)).map(|(_, cell)| cell))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        let cells = self.cells.iter().map(f).collect();
+
+        Grid { width: self.width, height: self.height, cells }
+    }
+
+    /// Renders one character per cell, with rows separated by newlines.
+    pub fn render(&self, mut f: impl FnMut(Point, &T) -> char) -> String {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+
+        for (point, cell) in self.iter() {
+            if point.x == 0 && point.y != 0 {
+                output.push('\n');
+            }
+
+            output.push(f(point, cell));
+        }
+
+        output
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |Point { x, y }| self[Point::new(y, x)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let bottom = to_coordinate(self.height) - 1;
+
+        Grid::from_fn(self.height, self.width, |Point { x, y }| self[Point::new(y, bottom - x)].clone())
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Grid<T> {
+        let right = to_coordinate(self.width) - 1;
+
+        Grid::from_fn(self.height, self.width, |Point { x, y }| self[Point::new(right - y, x)].clone())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::from_chars(s, |char| T::try_from(char).ok())
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ParseGridError {
+    #[error("no rows")]
+    Empty,
+    #[error("row {0} has a different width to the rows above it")]
+    InconsistentWidth(usize),
+    #[error("unexpected character '{0}'")]
+    UnexpectedCharacter(char)
+}
+
+fn point_at(x: usize, y: usize) -> Point {
+    Point::new(to_coordinate(x), to_coordinate(y))
+}
+
+fn to_coordinate(n: usize) -> i32 {
+    n.try_into().expect("grid dimension exceeds i32::MAX")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    pub fn parse_char_map() {
+        let grid = example();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+    }
+
+    #[test]
+    pub fn parse_errors() {
+        assert_eq!("".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        assert_eq!("ab\nc".parse::<Grid<char>>(), Err(ParseGridError::InconsistentWidth(1)));
+        assert_eq!(
+            Grid::from_chars("12\n3x", |c| c.to_digit(10)),
+            Err(ParseGridError::UnexpectedCharacter('x'))
+        );
+    }
+
+    #[test]
+    pub fn neighbors() {
+        let grid = example();
+
+        let neighbors4: Vec<_> = grid.neighbors4(Point::new(0, 0)).map(|(_, c)| *c).collect();
+        assert_eq!(neighbors4, vec!['b', 'd']);
+
+        let neighbors8: Vec<_> = grid.neighbors8(Point::new(1, 1)).map(|(_, c)| *c).collect();
+        assert_eq!(neighbors8, vec!['b', 'c', 'f', 'd', 'a']);
+    }
+
+    #[test]
+    pub fn views() {
+        let grid = example();
+
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+
+        let diagonals: Vec<String> = grid.diagonals().map(|d| d.collect()).collect();
+        assert_eq!(diagonals, vec!["d", "ae", "bf", "c"]);
+
+        let anti_diagonals: Vec<String> = grid.anti_diagonals().map(|d| d.collect()).collect();
+        assert_eq!(anti_diagonals, vec!["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    pub fn transformations() {
+        let grid = example();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
@@ -0,0 +1,120 @@
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    pub fn step(&self, direction: Direction) -> Point {
+        *self + direction.offset()
+    }
+
+    /// Orthogonal neighbors, clockwise from north.
+    pub fn neighbors4(&self) -> [Point; 4] {
+        Direction::ALL.map(|direction| self.step(direction))
+    }
+
+    /// Orthogonal and diagonal neighbors, clockwise from north.
+    pub fn neighbors8(&self) -> [Point; 8] {
+        let Point { x, y } = *self;
+
+        [
+            Point::new(x, y - 1),
+            Point::new(x + 1, y - 1),
+            Point::new(x + 1, y),
+            Point::new(x + 1, y + 1),
+            Point::new(x, y + 1),
+            Point::new(x - 1, y + 1),
+            Point::new(x - 1, y),
+            Point::new(x - 1, y - 1)
+        ]
+    }
+
+    pub fn manhattan_distance(&self, other: &Point) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn offset(&self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0)
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East
+        }
+    }
+
+    pub fn rotate_clockwise(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North
+        }
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South
+        }
+    }
+}
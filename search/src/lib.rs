@@ -0,0 +1,367 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Edge weights for weighted searches. `Default` is taken to be zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {
+}
+
+/// The result of exhaustively searching from a start state: the optimal cost of every
+/// reachable state, plus every predecessor that lies on an optimal path to it.
+#[derive(Debug, Clone)]
+pub struct SearchTree<S, C> {
+    start: S,
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+    settled: HashSet<S>
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> SearchTree<S, C> {
+    fn new(start: S) -> SearchTree<S, C> {
+        let mut costs = HashMap::new();
+        costs.insert(start.clone(), C::default());
+
+        SearchTree { start, costs, predecessors: HashMap::new(), settled: HashSet::new() }
+    }
+
+    /// Marks `state` as having its final cost, returning false if it already had.
+    fn settle(&mut self, state: &S) -> bool {
+        self.settled.insert(state.clone())
+    }
+
+    /// Records `cost` for `state` via `predecessor`, returning true if it improved on
+    /// the best known cost. Equal-cost predecessors are kept alongside the existing ones,
+    /// but only until `state` is settled: with zero-cost edges, a later one could lead
+    /// back around a cycle. A cheaper cost reopens a settled state, which only happens
+    /// under an inconsistent A* heuristic. The start never gets any predecessors.
+    fn relax(&mut self, state: &S, predecessor: &S, cost: C) -> bool {
+        if *state == self.start {
+            return false;
+        }
+
+        match self.costs.get(state) {
+            Some(&best) if cost > best => false,
+            Some(&best) if cost == best && self.settled.contains(state) => false,
+            Some(&best) if cost == best => {
+                let predecessors = self.predecessors.entry(state.clone()).or_default();
+                if !predecessors.contains(predecessor) {
+                    predecessors.push(predecessor.clone());
+                }
+
+                false
+            },
+            _ => {
+                self.settled.remove(state);
+                self.costs.insert(state.clone(), cost);
+                self.predecessors.insert(state.clone(), vec![predecessor.clone()]);
+
+                true
+            }
+        }
+    }
+
+    pub fn start(&self) -> &S {
+        &self.start
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn costs(&self) -> &HashMap<S, C> {
+        &self.costs
+    }
+
+    pub fn into_costs(self) -> HashMap<S, C> {
+        self.costs
+    }
+
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map(|p| p.as_slice()).unwrap_or(&[])
+    }
+
+    /// One optimal path from the start to `goal`, inclusive of both ends.
+    pub fn path_to(&self, goal: &S) -> Option<Vec<S>> {
+        self.costs.get(goal)?;
+
+        let mut path = vec![goal.clone()];
+        let mut current = goal;
+        while let Some(previous) = self.predecessors(current).first() {
+            path.push(previous.clone());
+            current = previous;
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every optimal path from the start to `goal`. The number of paths can grow
+    /// exponentially; prefer [`SearchTree::states_on_paths_to`] when only the states matter.
+    pub fn all_paths_to(&self, goal: &S) -> Vec<Vec<S>> {
+        if !self.costs.contains_key(goal) {
+            return vec![];
+        }
+
+        let mut paths = vec![];
+        let mut stack = vec![vec![goal.clone()]];
+
+        while let Some(path) = stack.pop() {
+            let current = path.last().unwrap();
+            let predecessors = self.predecessors(current);
+
+            if predecessors.is_empty() {
+                let mut path = path;
+                path.reverse();
+                paths.push(path);
+            } else {
+                for previous in predecessors {
+                    let mut next_path = path.clone();
+                    next_path.push(previous.clone());
+                    stack.push(next_path);
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// All states lying on at least one optimal path to any of `goals`.
+    pub fn states_on_paths_to(&self, goals: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut visited = HashSet::new();
+        let mut stack: Vec<S> = goals.into_iter()
+            .filter(|goal| self.costs.contains_key(goal))
+            .collect();
+
+        while let Some(state) = stack.pop() {
+            if visited.insert(state.clone()) {
+                stack.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+
+        visited
+    }
+}
+
+/// Breadth-first search over unit-cost edges, exploring everything reachable from `start`.
+pub fn bfs<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> SearchTree<S, usize>
+where S: Clone + Eq + Hash, I: IntoIterator<Item = S> {
+    let mut tree = SearchTree::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let next_cost = tree.costs[&state] + 1;
+
+        for next in neighbors(&state) {
+            if tree.relax(&next, &state, next_cost) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    tree
+}
+
+/// Dijkstra's algorithm, exploring everything reachable from `start`. `neighbors` yields
+/// each successor along with the cost of the edge to it.
+pub fn dijkstra<S, C, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> SearchTree<S, C>
+where S: Clone + Eq + Hash, C: Cost, I: IntoIterator<Item = (S, C)> {
+    let mut tree = SearchTree::new(start.clone());
+    let mut heap = BinaryHeap::from([QueueEntry { priority: C::default(), state: start }]);
+
+    while let Some(QueueEntry { state, .. }) = heap.pop() {
+        if !tree.settle(&state) {
+            continue;
+        }
+
+        let cost = tree.costs[&state];
+        for (next, edge_cost) in neighbors(&state) {
+            let next_cost = cost + edge_cost;
+
+            if tree.relax(&next, &state, next_cost) {
+                heap.push(QueueEntry { priority: next_cost, state: next });
+            }
+        }
+    }
+
+    tree
+}
+
+/// A* search from `start` to the first state satisfying `is_goal`, returning the path
+/// (inclusive of both ends) and its cost. `heuristic` must never overestimate. It doesn't
+/// have to be consistent, since a state is expanded again if a cheaper route to it turns up
+/// after it was settled.
+pub fn astar<S, C, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool
+) -> Option<(Vec<S>, C)>
+where S: Clone + Eq + Hash, C: Cost, I: IntoIterator<Item = (S, C)> {
+    let mut tree = SearchTree::new(start.clone());
+    let mut heap = BinaryHeap::from([QueueEntry { priority: heuristic(&start), state: start }]);
+
+    while let Some(QueueEntry { state, .. }) = heap.pop() {
+        if !tree.settle(&state) {
+            continue;
+        }
+
+        let cost = tree.costs[&state];
+        if is_goal(&state) {
+            return tree.path_to(&state).map(|path| (path, cost));
+        }
+
+        for (next, edge_cost) in neighbors(&state) {
+            let next_cost = cost + edge_cost;
+
+            if tree.relax(&next, &state, next_cost) {
+                let priority = next_cost + heuristic(&next);
+                heap.push(QueueEntry { priority, state: next });
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm stopping at the first state satisfying `is_goal`.
+pub fn shortest_path<S, C, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool
+) -> Option<(Vec<S>, C)>
+where S: Clone + Eq + Hash, C: Cost, I: IntoIterator<Item = (S, C)> {
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+/// Min-heap entry ordered only by priority, so states don't need to be `Ord`.
+struct QueueEntry<S, C> {
+    priority: C,
+    state: S
+}
+
+impl<S, C: Ord> Ord for QueueEntry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority) // Flip cost comparison
+    }
+}
+
+impl<S, C: Ord> PartialOrd for QueueEntry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for QueueEntry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for QueueEntry<S, C> {
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 4x3 room with a wall segment at x = 1, y = 0..=1.
+    fn open_neighbors(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+            .into_iter()
+            .filter(|&(x, y)| (0..4).contains(&x) && (0..3).contains(&y))
+            .filter(|&(x, y)| !(x == 1 && y < 2))
+            .collect()
+    }
+
+    #[test]
+    pub fn bfs_costs_and_path() {
+        let tree = bfs((0, 0), open_neighbors);
+
+        assert_eq!(tree.cost(&(2, 0)), Some(6));
+        assert_eq!(tree.cost(&(1, 0)), None);
+        assert_eq!(
+            tree.path_to(&(2, 0)),
+            Some(vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1), (2, 0)])
+        );
+    }
+
+    #[test]
+    pub fn all_optimal_paths() {
+        let tree = bfs((0, 2), open_neighbors);
+
+        // (0, 2) -> (3, 1): the step up can happen at x = 2 or x = 3, but not into the wall
+        let paths = tree.all_paths_to(&(3, 1));
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|path| path.len() == 5 && path[0] == (0, 2) && path[4] == (3, 1)));
+
+        let states = tree.states_on_paths_to([(3, 1)]);
+        assert_eq!(states.len(), 6);
+    }
+
+    #[test]
+    pub fn weighted_searches_agree() {
+        let costs = [[1, 9, 1, 1], [1, 9, 1, 9], [1, 1, 1, 1]];
+        let weighted = |state: &(i32, i32)| {
+            open_neighbors(state).into_iter()
+                .map(|(x, y)| ((x, y), costs[y as usize][x as usize]))
+                .collect::<Vec<_>>()
+        };
+
+        let tree = dijkstra((0, 0), weighted);
+        assert_eq!(tree.cost(&(3, 0)), Some(7));
+
+        let (path, cost) = shortest_path((0, 0), weighted, |&state| state == (3, 0)).unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path.last(), Some(&(3, 0)));
+
+        let manhattan = |&(x, y): &(i32, i32)| (3 - x).abs() + y;
+        let (_, cost) = astar((0, 0), weighted, manhattan, |&state| state == (3, 0)).unwrap();
+        assert_eq!(cost, 7);
+    }
+
+    #[test]
+    pub fn zero_cost_cycles() {
+        // a and b are joined both ways for free, and b and c both reach d at cost 1
+        let edges = |&state: &char| match state {
+            'a' => vec![('b', 0), ('c', 0)],
+            'b' => vec![('a', 0), ('c', 0), ('d', 1)],
+            'c' => vec![('b', 0), ('d', 1)],
+            _ => vec![]
+        };
+
+        let tree = dijkstra('a', edges);
+        assert!(tree.predecessors(&'a').is_empty());
+        assert_eq!(tree.cost(&'d'), Some(1));
+        assert_eq!(tree.path_to(&'d').map(|path| path[0]), Some('a'));
+
+        let paths = tree.all_paths_to(&'d');
+        assert!(paths.iter().all(|path| path.first() == Some(&'a') && path.last() == Some(&'d')));
+        assert!(paths.len() >= 2);
+        assert_eq!(tree.states_on_paths_to(['d']).len(), 4);
+
+        let (path, cost) = shortest_path('a', edges, |&state| state == 'd').unwrap();
+        assert_eq!((path.len(), cost), (3, 1));
+    }
+
+    #[test]
+    pub fn inconsistent_heuristic() {
+        // The heuristic is exact at a but zero at c, so c is first settled by way of b
+        let edges = |&state: &char| match state {
+            's' => vec![('a', 1), ('b', 1)],
+            'a' => vec![('c', 1)],
+            'b' => vec![('c', 3)],
+            'c' => vec![('g', 10)],
+            _ => vec![]
+        };
+        let heuristic = |&state: &char| if state == 'a' { 11 } else { 0 };
+
+        let (path, cost) = astar('s', edges, heuristic, |&state| state == 'g').unwrap();
+        assert_eq!(path, vec!['s', 'a', 'c', 'g']);
+        assert_eq!(cost, 12);
+    }
+}